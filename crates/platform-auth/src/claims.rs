@@ -24,6 +24,7 @@ use uuid::Uuid;
 /// ```rust,no_run
 /// use platform_auth::claims::PlatformClaims;
 ///
+/// let user_id = uuid::Uuid::now_v7();
 /// let claims = PlatformClaims::new(
 ///     user_id,
 ///     "user@example.com",
@@ -243,10 +244,11 @@ pub struct OrgClaim {
 }
 
 /// Token type enumeration.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum TokenType {
     /// Access token (short-lived)
+    #[default]
     Access,
 
     /// Refresh token (long-lived)
@@ -259,17 +261,12 @@ pub enum TokenType {
     Service,
 }
 
-impl Default for TokenType {
-    fn default() -> Self {
-        TokenType::Access
-    }
-}
-
 /// Authentication method used.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum AuthMethod {
     /// Username/password authentication
+    #[default]
     Password,
 
     /// OAuth 2.0 provider
@@ -291,12 +288,6 @@ pub enum AuthMethod {
     Service,
 }
 
-impl Default for AuthMethod {
-    fn default() -> Self {
-        AuthMethod::Password
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
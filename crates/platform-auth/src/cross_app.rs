@@ -45,14 +45,19 @@ use uuid::Uuid;
 ///
 /// Each application has its own identity and can issue tokens for
 /// cross-app communication.
+///
+/// Serialized in snake_case (`note_man`); the [`as_str`](Self::as_str)
+/// spelling (`noteman`) is accepted when deserializing.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum AppId {
     /// Verity - AI-powered knowledge and verification platform
     Verity,
     /// NoteMan - Note management and organization
+    #[serde(alias = "noteman")]
     NoteMan,
     /// ShipCheck - Shipping and logistics tracking
+    #[serde(alias = "shipcheck")]
     ShipCheck,
 }
 
@@ -67,6 +72,7 @@ impl AppId {
     }
 
     /// Parse AppId from string.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "verity" => Some(AppId::Verity),
@@ -438,6 +444,18 @@ mod tests {
         assert_eq!(AppId::from_str("invalid"), None);
     }
 
+    #[test]
+    fn test_app_id_serde_accepts_both_spellings() {
+        assert_eq!(
+            serde_json::to_value(AppId::NoteMan).unwrap(),
+            serde_json::json!("note_man")
+        );
+        for spelling in ["ship_check", "shipcheck"] {
+            let app: AppId = serde_json::from_value(serde_json::json!(spelling)).unwrap();
+            assert_eq!(app, AppId::ShipCheck);
+        }
+    }
+
     #[test]
     fn test_cross_app_token_creation() {
         let user_id = Uuid::now_v7();
@@ -14,6 +14,12 @@ use jsonwebtoken::{
     decode, encode, Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation,
 };
 
+/// Issuers accepted on cross-app and service tokens: every platform app, in
+/// both its serialized (`note_man`) and its `AppId::as_str` (`noteman`)
+/// spelling.
+#[cfg(feature = "jwt")]
+const APP_ISSUERS: &[&str] = &["verity", "note_man", "noteman", "ship_check", "shipcheck"];
+
 /// JWT configuration for token generation and validation.
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
@@ -385,7 +391,7 @@ impl JwtService {
     ) -> AuthResult<crate::cross_app::CrossAppToken> {
         let mut validation = Validation::new(self.config.algorithm.into());
         // Cross-app tokens can be issued by any platform app
-        validation.set_issuer(&["verity", "noteman", "shipcheck"]);
+        validation.set_issuer(APP_ISSUERS);
         // Don't validate audience here - let the caller check if token is valid for their app
         validation.validate_aud = false;
 
@@ -456,7 +462,7 @@ impl JwtService {
     pub fn decode_service_token(&self, token: &str) -> AuthResult<crate::cross_app::ServiceToken> {
         let mut validation = Validation::new(self.config.algorithm.into());
         // Service tokens can be issued by any platform app
-        validation.set_issuer(&["verity", "noteman", "shipcheck"]);
+        validation.set_issuer(APP_ISSUERS);
         // Don't validate audience here - let the caller check
         validation.validate_aud = false;
 
@@ -598,6 +604,18 @@ mod tests {
         assert!(decoded.has_capability("webhooks.receive"));
     }
 
+    #[test]
+    fn test_service_tokens_from_every_app_decode() {
+        use crate::cross_app::{AppId, ServiceToken};
+
+        let service = JwtService::with_secret(test_secret()).unwrap();
+        for issuer in [AppId::Verity, AppId::NoteMan, AppId::ShipCheck] {
+            let token = ServiceToken::new(issuer, AppId::Verity, vec![]);
+            let encoded = service.encode_service_token(&token).unwrap();
+            assert_eq!(service.decode_service_token(&encoded).unwrap().iss, issuer);
+        }
+    }
+
     #[test]
     fn test_expired_cross_app_token() {
         use crate::cross_app::{AppId, CrossAppToken};
@@ -283,7 +283,6 @@ impl OAuthState {
     /// Create with PKCE support.
     pub fn with_pkce() -> Self {
         use rand::Rng;
-        use sha2::{Digest, Sha256};
 
         let code_verifier: String = rand::thread_rng()
             .sample_iter(&rand::distributions::Alphanumeric)
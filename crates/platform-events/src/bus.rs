@@ -3,12 +3,13 @@
 //! This module provides the event bus abstraction and implementations
 //! for publishing and subscribing to events across applications.
 
+use crate::dlq::{DeadLetter, DeadLetterQueue, DeadLetterStore, Retrier, RetryPolicy};
 use crate::types::Event;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, RwLock};
 
 /// Event bus error types.
 #[derive(Debug, Error)]
@@ -32,28 +33,110 @@ pub enum EventBusError {
     /// Channel closed
     #[error("Channel closed")]
     ChannelClosed,
+
+    /// The subscriber fell behind and missed this many events; receiving
+    /// continues with the oldest event still queued
+    #[error("Subscriber lagged behind, {0} events skipped")]
+    Lagged(u64),
+
+    /// Dead letter not found
+    #[error("Dead letter not found: {0}")]
+    DeadLetterNotFound(uuid::Uuid),
+
+    /// No handler registered under a name
+    #[error("Handler not registered: {0}")]
+    HandlerNotFound(String),
+
+    /// A handler failed to process an event
+    #[error("Handler failed: {0}")]
+    HandlerFailed(String),
+
+    /// Failed to decode a domain event
+    #[error("Failed to decode event: {0}")]
+    DecodeError(#[from] crate::types::DecodeError),
 }
 
 /// Result type for event bus operations.
 pub type EventBusResult<T> = Result<T, EventBusError>;
 
 /// Subscription handle for receiving events.
+///
+/// Dropping the handle ends the subscription, like
+/// [`EventBus::unsubscribe`].
 pub struct Subscription {
     /// Subscription ID
     pub id: String,
     /// Topic pattern
     pub topic: String,
     /// Event receiver
-    pub receiver: broadcast::Receiver<Event>,
+    receiver: SubscriptionReceiver,
+}
+
+/// Receiving end of a subscription's queue.
+enum SubscriptionReceiver {
+    /// Ring buffer: the oldest events are dropped when the subscriber lags.
+    Broadcast(broadcast::Receiver<Event>),
+    /// Bounded queue: publishers wait for room.
+    Bounded(mpsc::Receiver<Event>),
 }
 
 impl Subscription {
+    /// Create a subscription receiving from a broadcast channel.
+    pub fn new(id: String, topic: String, receiver: broadcast::Receiver<Event>) -> Self {
+        Self {
+            id,
+            topic,
+            receiver: SubscriptionReceiver::Broadcast(receiver),
+        }
+    }
+
+    /// Create a subscription receiving from a bounded queue.
+    pub fn bounded(id: String, topic: String, receiver: mpsc::Receiver<Event>) -> Self {
+        Self {
+            id,
+            topic,
+            receiver: SubscriptionReceiver::Bounded(receiver),
+        }
+    }
+
     /// Receive the next event.
+    ///
+    /// Returns [`EventBusError::Lagged`] when the subscriber fell behind and
+    /// events were skipped; call `recv` again to continue with the next
+    /// event. Returns [`EventBusError::ChannelClosed`] once the subscription
+    /// ended.
     pub async fn recv(&mut self) -> EventBusResult<Event> {
-        self.receiver
-            .recv()
-            .await
-            .map_err(|_| EventBusError::ChannelClosed)
+        match self.receiver {
+            SubscriptionReceiver::Broadcast(ref mut receiver) => {
+                receiver.recv().await.map_err(|e| match e {
+                    broadcast::error::RecvError::Lagged(skipped) => EventBusError::Lagged(skipped),
+                    broadcast::error::RecvError::Closed => EventBusError::ChannelClosed,
+                })
+            }
+            SubscriptionReceiver::Bounded(ref mut receiver) => {
+                receiver.recv().await.ok_or(EventBusError::ChannelClosed)
+            }
+        }
+    }
+}
+
+/// Delivery details of an event passed to a handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delivery {
+    /// Delivery attempt, starting at 1; higher for redeliveries.
+    pub attempt: u32,
+}
+
+impl Delivery {
+    /// The first delivery of an event.
+    pub fn first() -> Self {
+        Self { attempt: 1 }
+    }
+}
+
+impl Default for Delivery {
+    fn default() -> Self {
+        Self::first()
     }
 }
 
@@ -63,8 +146,29 @@ pub trait EventHandler: Send + Sync {
     /// Handle an event.
     async fn handle(&self, event: Event) -> EventBusResult<()>;
 
+    /// Handle a delivery of an event.
+    ///
+    /// Backends that redeliver failed events call this with the delivery
+    /// attempt. Defaults to [`handle`](Self::handle); override it to act on
+    /// the attempt, e.g. to give up on an event after some attempts.
+    async fn handle_delivery(&self, event: Event, delivery: Delivery) -> EventBusResult<()> {
+        let _ = delivery;
+        self.handle(event).await
+    }
+
     /// Get the topics this handler is interested in.
     fn topics(&self) -> Vec<String>;
+
+    /// Get the name of this handler, recorded with its dead letters and
+    /// used to redrive them. Defaults to the type name.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Get the retry policy of this handler; `None` uses the bus's policy.
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
 }
 
 /// Event bus trait for publish/subscribe operations.
@@ -107,25 +211,149 @@ pub struct EventBusStats {
     pub registered_handlers: usize,
 }
 
+/// Check whether a topic matches a topic pattern.
+///
+/// Patterns are split on `.`; `*` matches exactly one segment and `#`
+/// matches zero or more segments:
+///
+/// ```
+/// use platform_events::topic_matches;
+///
+/// assert!(topic_matches("noteman.meeting.*", "noteman.meeting.ended"));
+/// assert!(topic_matches("*.document.#", "verity.document.created"));
+/// assert!(!topic_matches("verity.*", "verity.document.created"));
+/// ```
+pub fn topic_matches(pattern: &str, topic: &str) -> bool {
+    let pattern_parts: Vec<&str> = pattern.split('.').collect();
+    let topic_parts: Vec<&str> = topic.split('.').collect();
+
+    let mut p_idx = 0;
+    let mut t_idx = 0;
+
+    while p_idx < pattern_parts.len() && t_idx < topic_parts.len() {
+        match pattern_parts[p_idx] {
+            "*" => {
+                // Match single segment
+                p_idx += 1;
+                t_idx += 1;
+            }
+            "#" => {
+                // Match zero or more segments
+                if p_idx == pattern_parts.len() - 1 {
+                    // # at end matches everything remaining
+                    return true;
+                }
+                // Try matching remaining pattern
+                for i in t_idx..=topic_parts.len() {
+                    if topic_matches(
+                        &pattern_parts[p_idx + 1..].join("."),
+                        &topic_parts[i..].join("."),
+                    ) {
+                        return true;
+                    }
+                }
+                return false;
+            }
+            segment => {
+                if segment != topic_parts[t_idx] {
+                    return false;
+                }
+                p_idx += 1;
+                t_idx += 1;
+            }
+        }
+    }
+
+    // Handle trailing # in pattern
+    if p_idx < pattern_parts.len() && pattern_parts[p_idx] == "#" {
+        p_idx += 1;
+    }
+
+    p_idx == pattern_parts.len() && t_idx == topic_parts.len()
+}
+
+/// Sending end of a subscription's queue.
+#[derive(Clone)]
+enum SubscriberSender {
+    Broadcast(broadcast::Sender<Event>),
+    Bounded {
+        sender: mpsc::Sender<Event>,
+        /// Set on unsubscribe, releasing publishers waiting for room.
+        removed: Arc<watch::Sender<bool>>,
+    },
+}
+
+impl SubscriberSender {
+    fn bounded(sender: mpsc::Sender<Event>) -> Self {
+        Self::Bounded {
+            sender,
+            removed: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Check whether the subscription handle was dropped.
+    fn is_closed(&self) -> bool {
+        match self {
+            Self::Broadcast(sender) => sender.receiver_count() == 0,
+            Self::Bounded { sender, .. } => sender.is_closed(),
+        }
+    }
+
+    /// Queue an event, waiting for room in bounded mode. Returns whether the
+    /// subscriber is still there to receive it.
+    async fn send(&self, event: Event) -> bool {
+        match self {
+            Self::Broadcast(sender) => sender.send(event).is_ok(),
+            Self::Bounded { sender, removed } => {
+                let mut removed = removed.subscribe();
+                if *removed.borrow_and_update() {
+                    return false;
+                }
+                tokio::select! {
+                    result = sender.send(event) => result.is_ok(),
+                    _ = removed.changed() => false,
+                }
+            }
+        }
+    }
+
+    /// Mark the subscription as removed.
+    fn remove(&self) {
+        if let Self::Bounded { removed, .. } = self {
+            removed.send_replace(true);
+        }
+    }
+}
+
 /// In-memory event bus implementation.
 ///
 /// This is suitable for single-process applications and testing.
 /// For distributed systems, use Redis or NATS backend.
+///
+/// Each subscription has its own queue of `capacity` events. By default, a
+/// subscriber that falls behind loses the oldest events and gets
+/// [`EventBusError::Lagged`]. With [`with_backpressure`](Self::with_backpressure),
+/// publishers wait for room instead.
 pub struct MemoryEventBus {
-    /// Topic subscribers
-    subscribers: Arc<RwLock<HashMap<String, broadcast::Sender<Event>>>>,
+    /// Subscriptions by ID: topic pattern and queue
+    subscribers: Arc<RwLock<HashMap<String, (String, SubscriberSender)>>>,
     /// Registered handlers
     handlers: Arc<RwLock<Vec<Arc<dyn EventHandler>>>>,
     /// Statistics
     stats: Arc<RwLock<EventBusStats>>,
     /// Default channel capacity
     channel_capacity: usize,
+    /// Whether publishers wait for room in full subscription queues
+    backpressure: bool,
+    /// Handler retry policy and dead-letter store
+    retrier: Retrier,
 }
 
 impl std::fmt::Debug for MemoryEventBus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MemoryEventBus")
             .field("channel_capacity", &self.channel_capacity)
+            .field("backpressure", &self.backpressure)
             .finish()
     }
 }
@@ -143,57 +371,43 @@ impl MemoryEventBus {
             handlers: Arc::new(RwLock::new(Vec::new())),
             stats: Arc::new(RwLock::new(EventBusStats::default())),
             channel_capacity: capacity,
+            backpressure: false,
+            retrier: Retrier::default(),
         }
     }
 
-    /// Check if a topic matches a pattern.
-    fn topic_matches(pattern: &str, topic: &str) -> bool {
-        let pattern_parts: Vec<&str> = pattern.split('.').collect();
-        let topic_parts: Vec<&str> = topic.split('.').collect();
-
-        let mut p_idx = 0;
-        let mut t_idx = 0;
-
-        while p_idx < pattern_parts.len() && t_idx < topic_parts.len() {
-            match pattern_parts[p_idx] {
-                "*" => {
-                    // Match single segment
-                    p_idx += 1;
-                    t_idx += 1;
-                }
-                "#" => {
-                    // Match zero or more segments
-                    if p_idx == pattern_parts.len() - 1 {
-                        // # at end matches everything remaining
-                        return true;
-                    }
-                    // Try matching remaining pattern
-                    for i in t_idx..=topic_parts.len() {
-                        if Self::topic_matches(
-                            &pattern_parts[p_idx + 1..].join("."),
-                            &topic_parts[i..].join("."),
-                        ) {
-                            return true;
-                        }
-                    }
-                    return false;
-                }
-                segment => {
-                    if segment != topic_parts[t_idx] {
-                        return false;
-                    }
-                    p_idx += 1;
-                    t_idx += 1;
-                }
-            }
-        }
+    /// Give subscriptions bounded queues: `publish` waits until every
+    /// matching subscription has room, so no events are lost but a slow
+    /// subscriber slows down publishers. Unsubscribe or drop subscriptions
+    /// that are no longer read.
+    pub fn with_backpressure(mut self) -> Self {
+        self.backpressure = true;
+        self
+    }
 
-        // Handle trailing # in pattern
-        if p_idx < pattern_parts.len() && pattern_parts[p_idx] == "#" {
-            p_idx += 1;
-        }
+    /// Set the retry policy of handlers without their own
+    /// (default: [`RetryPolicy::default`]).
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retrier.policy = policy;
+        self
+    }
+
+    /// Set where events that handlers failed to process go
+    /// (default: a [`MemoryDeadLetterStore`](crate::MemoryDeadLetterStore)).
+    pub fn with_dead_letter_store(mut self, store: Arc<dyn DeadLetterStore>) -> Self {
+        self.retrier.store = store;
+        self
+    }
+
+    /// Check if a topic matches a pattern (see [`topic_matches`]).
+    fn topic_matches(pattern: &str, topic: &str) -> bool {
+        topic_matches(pattern, topic)
+    }
 
-        p_idx == pattern_parts.len() && t_idx == topic_parts.len()
+    /// Remove subscriptions whose handle was dropped.
+    async fn remove_dropped(&self) {
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.retain(|_, (_, sender)| !sender.is_closed());
     }
 }
 
@@ -214,60 +428,74 @@ impl EventBus for MemoryEventBus {
             stats.events_published += 1;
         }
 
-        // Notify matching subscribers
-        let subscribers = self.subscribers.read().await;
-        for (pattern, sender) in subscribers.iter() {
-            if Self::topic_matches(pattern, &topic) {
-                let _ = sender.send(event.clone());
+        // Notify matching subscribers, without holding the lock while
+        // waiting for room
+        let senders: Vec<SubscriberSender> = self
+            .subscribers
+            .read()
+            .await
+            .values()
+            .filter(|(pattern, _)| Self::topic_matches(pattern, &topic))
+            .map(|(_, sender)| sender.clone())
+            .collect();
+        let mut delivered = 0;
+        let mut dropped = false;
+        for sender in senders {
+            if sender.send(event.clone()).await {
+                delivered += 1;
+            } else {
+                dropped = true;
             }
         }
+        if dropped {
+            self.remove_dropped().await;
+        }
 
         // Notify handlers
         let handlers = self.handlers.read().await;
         for handler in handlers.iter() {
             for handler_topic in handler.topics() {
                 if Self::topic_matches(&handler_topic, &topic) {
+                    delivered += 1;
                     let handler = handler.clone();
                     let event = event.clone();
+                    let retrier = self.retrier.clone();
                     tokio::task::spawn(async move {
-                        if let Err(e) = handler.handle(event).await {
-                            eprintln!("Handler error: {}", e);
-                        }
+                        let _ = retrier.deliver(handler.as_ref(), event).await;
                     });
                     break;
                 }
             }
         }
 
+        self.stats.write().await.events_delivered += delivered;
+
         Ok(())
     }
 
     async fn subscribe(&self, topic: &str) -> EventBusResult<Subscription> {
         let id = uuid::Uuid::now_v7().to_string();
 
-        let receiver = {
-            let mut subscribers = self.subscribers.write().await;
-
-            if let Some(sender) = subscribers.get(topic) {
-                sender.subscribe()
-            } else {
-                let (sender, receiver) = broadcast::channel(self.channel_capacity);
-                subscribers.insert(topic.to_string(), sender);
-                receiver
-            }
+        let (sender, subscription) = if self.backpressure {
+            let (sender, receiver) = mpsc::channel(self.channel_capacity);
+            (
+                SubscriberSender::bounded(sender),
+                Subscription::bounded(id.clone(), topic.to_string(), receiver),
+            )
+        } else {
+            let (sender, receiver) = broadcast::channel(self.channel_capacity);
+            (
+                SubscriberSender::Broadcast(sender),
+                Subscription::new(id.clone(), topic.to_string(), receiver),
+            )
         };
 
-        // Update stats
-        {
-            let mut stats = self.stats.write().await;
-            stats.active_subscriptions += 1;
-        }
+        self.subscribers
+            .write()
+            .await
+            .insert(id, (topic.to_string(), sender));
 
-        Ok(Subscription {
-            id,
-            topic: topic.to_string(),
-            receiver,
-        })
+        Ok(subscription)
     }
 
     async fn register_handler(&self, handler: Arc<dyn EventHandler>) -> EventBusResult<()> {
@@ -283,477 +511,244 @@ impl EventBus for MemoryEventBus {
         Ok(())
     }
 
-    async fn unsubscribe(&self, _subscription_id: &str) -> EventBusResult<()> {
-        // Update stats
-        {
-            let mut stats = self.stats.write().await;
-            if stats.active_subscriptions > 0 {
-                stats.active_subscriptions -= 1;
-            }
+    async fn unsubscribe(&self, subscription_id: &str) -> EventBusResult<()> {
+        // Dropping the sender closes the subscription once publishers
+        // waiting on it give up
+        if let Some((_, sender)) = self.subscribers.write().await.remove(subscription_id) {
+            sender.remove();
         }
-
         Ok(())
     }
 
     async fn stats(&self) -> EventBusStats {
-        self.stats.read().await.clone()
+        self.remove_dropped().await;
+        let mut stats = self.stats.read().await.clone();
+        stats.active_subscriptions = self.subscribers.read().await.len();
+        stats
     }
 }
 
-// ============================================================================
-// Redis Event Bus (Feature: redis)
-// ============================================================================
-
-#[cfg(feature = "redis")]
-pub mod redis_bus {
-    //! Redis-backed event bus for distributed systems.
-    //!
-    //! Uses Redis Pub/Sub for real-time event delivery and Redis Streams
-    //! for event persistence and replay.
-
-    use super::*;
-    use redis::aio::ConnectionManager;
-    use redis::AsyncCommands;
-    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-
-    /// Redis event bus configuration.
-    #[derive(Debug, Clone)]
-    pub struct RedisEventBusConfig {
-        /// Redis connection URL (e.g., redis://localhost:6379).
-        pub url: String,
-
-        /// Prefix for all Redis keys (default: "platform_events").
-        pub key_prefix: String,
-
-        /// Use Redis Streams for persistence (default: true).
-        pub use_streams: bool,
-
-        /// Maximum stream length (MAXLEN, default: 10000).
-        pub stream_max_len: usize,
-
-        /// Consumer group name for streams (default: "platform_consumers").
-        pub consumer_group: String,
-
-        /// Consumer name within the group (default: hostname or random).
-        pub consumer_name: String,
-    }
-
-    impl Default for RedisEventBusConfig {
-        fn default() -> Self {
-            Self {
-                url: std::env::var("REDIS_URL")
-                    .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
-                key_prefix: "platform_events".to_string(),
-                use_streams: true,
-                stream_max_len: 10000,
-                consumer_group: "platform_consumers".to_string(),
-                consumer_name: hostname::get()
-                    .ok()
-                    .and_then(|h| h.into_string().ok())
-                    .unwrap_or_else(|| uuid::Uuid::now_v7().to_string()),
-            }
-        }
+#[async_trait]
+impl DeadLetterQueue for MemoryEventBus {
+    async fn dead_letters(&self) -> EventBusResult<Vec<DeadLetter>> {
+        self.retrier.store.list().await
     }
 
-    /// Redis-backed event bus implementation.
-    ///
-    /// Features:
-    /// - Pub/Sub for real-time event delivery
-    /// - Streams for event persistence and replay
-    /// - Consumer groups for load balancing
-    /// - Automatic reconnection
-    ///
-    /// # Example
-    ///
-    /// ```rust,no_run
-    /// use platform_events::bus::redis_bus::{RedisEventBus, RedisEventBusConfig};
-    ///
-    /// let config = RedisEventBusConfig::default();
-    /// let bus = RedisEventBus::new(config).await?;
-    ///
-    /// // Subscribe to events
-    /// let mut sub = bus.subscribe("verity.document.*").await?;
-    ///
-    /// // Publish event
-    /// bus.publish(event).await?;
-    /// ```
-    pub struct RedisEventBus {
-        /// Redis connection manager for commands.
-        conn: ConnectionManager,
-
-        /// Configuration.
-        config: RedisEventBusConfig,
-
-        /// Local broadcast channels for subscriptions.
-        local_channels: Arc<RwLock<HashMap<String, broadcast::Sender<Event>>>>,
-
-        /// Registered handlers.
-        handlers: Arc<RwLock<Vec<Arc<dyn EventHandler>>>>,
-
-        /// Statistics.
-        events_published: AtomicU64,
-        events_delivered: AtomicU64,
-        active_subscriptions: AtomicU64,
-
-        /// Running flag for background tasks.
-        running: Arc<AtomicBool>,
+    async fn dead_letter(&self, id: uuid::Uuid) -> EventBusResult<Option<DeadLetter>> {
+        self.retrier.store.get(id).await
     }
 
-    impl std::fmt::Debug for RedisEventBus {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.debug_struct("RedisEventBus")
-                .field("config", &self.config)
-                .field("running", &self.running.load(Ordering::Relaxed))
-                .finish()
-        }
+    async fn redrive_dead_letter(&self, id: uuid::Uuid) -> EventBusResult<()> {
+        let handlers = self.handlers.read().await.clone();
+        self.retrier.redrive(id, &handlers).await
     }
 
-    impl RedisEventBus {
-        /// Create a new Redis event bus.
-        pub async fn new(config: RedisEventBusConfig) -> EventBusResult<Self> {
-            let client = redis::Client::open(config.url.as_str())
-                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
-
-            let conn = ConnectionManager::new(client)
-                .await
-                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
-
-            let bus = Self {
-                conn,
-                config,
-                local_channels: Arc::new(RwLock::new(HashMap::new())),
-                handlers: Arc::new(RwLock::new(Vec::new())),
-                events_published: AtomicU64::new(0),
-                events_delivered: AtomicU64::new(0),
-                active_subscriptions: AtomicU64::new(0),
-                running: Arc::new(AtomicBool::new(true)),
-            };
-
-            Ok(bus)
-        }
-
-        /// Create with default configuration.
-        pub async fn with_defaults() -> EventBusResult<Self> {
-            Self::new(RedisEventBusConfig::default()).await
-        }
-
-        /// Get the stream key for a topic.
-        fn stream_key(&self, topic: &str) -> String {
-            format!(
-                "{}:stream:{}",
-                self.config.key_prefix,
-                topic.replace('.', ":")
-            )
-        }
-
-        /// Get the pubsub channel for a topic.
-        fn channel_key(&self, topic: &str) -> String {
-            format!(
-                "{}:pubsub:{}",
-                self.config.key_prefix,
-                topic.replace('.', ":")
-            )
-        }
-
-        /// Publish to Redis Stream for persistence.
-        async fn publish_to_stream(&self, event: &Event) -> EventBusResult<()> {
-            if !self.config.use_streams {
-                return Ok(());
-            }
-
-            let stream_key = self.stream_key(&event.topic());
-            let event_json = serde_json::to_string(event)
-                .map_err(|e| EventBusError::SerializationError(e.to_string()))?;
-
-            let mut conn = self.conn.clone();
-
-            // Add to stream with MAXLEN for automatic trimming
-            let _: String = redis::cmd("XADD")
-                .arg(&stream_key)
-                .arg("MAXLEN")
-                .arg("~")
-                .arg(self.config.stream_max_len)
-                .arg("*")
-                .arg("event")
-                .arg(&event_json)
-                .arg("topic")
-                .arg(event.topic())
-                .arg("timestamp")
-                .arg(event.timestamp.to_rfc3339())
-                .query_async(&mut conn)
-                .await
-                .map_err(|e| EventBusError::PublishError(e.to_string()))?;
-
-            Ok(())
-        }
-
-        /// Publish to Redis Pub/Sub for real-time delivery.
-        async fn publish_to_pubsub(&self, event: &Event) -> EventBusResult<()> {
-            let channel = self.channel_key(&event.topic());
-            let event_json = serde_json::to_string(event)
-                .map_err(|e| EventBusError::SerializationError(e.to_string()))?;
+    async fn purge_dead_letters(&self) -> EventBusResult<usize> {
+        self.retrier.store.purge().await
+    }
+}
 
-            let mut conn = self.conn.clone();
-            let _: i32 = conn
-                .publish(&channel, &event_json)
-                .await
-                .map_err(|e| EventBusError::PublishError(e.to_string()))?;
+// ============================================================================
+// Tests
+// ============================================================================
 
-            Ok(())
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use platform_rbac::App;
 
-        /// Start a background subscription listener.
-        pub async fn start_subscription_listener(&self, topic: &str) -> EventBusResult<()> {
-            let client = redis::Client::open(self.config.url.as_str())
-                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
-
-            let mut pubsub = client
-                .get_async_pubsub()
-                .await
-                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
-
-            // Convert topic pattern to Redis pattern
-            let pattern = self
-                .channel_key(topic)
-                .replace("*", "[^:]*")
-                .replace("#", "*");
-
-            pubsub
-                .psubscribe(&pattern)
-                .await
-                .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
-
-            let local_channels = self.local_channels.clone();
-            let handlers = self.handlers.clone();
-            let running = self.running.clone();
-            let events_delivered = &self.events_delivered;
-
-            // Spawn listener task
-            tokio::spawn(async move {
-                let mut stream = pubsub.on_message();
-
-                while running.load(Ordering::Relaxed) {
-                    match tokio::time::timeout(std::time::Duration::from_secs(1), stream.next())
-                        .await
-                    {
-                        Ok(Some(msg)) => {
-                            let payload: String = match msg.get_payload() {
-                                Ok(p) => p,
-                                Err(_) => continue,
-                            };
-
-                            let event: Event = match serde_json::from_str(&payload) {
-                                Ok(e) => e,
-                                Err(_) => continue,
-                            };
-
-                            // Notify local subscribers
-                            let channels = local_channels.read().await;
-                            for (pattern, sender) in channels.iter() {
-                                if MemoryEventBus::topic_matches(pattern, &event.topic()) {
-                                    let _ = sender.send(event.clone());
-                                }
-                            }
-
-                            // Notify handlers
-                            let hdlrs = handlers.read().await;
-                            for handler in hdlrs.iter() {
-                                for handler_topic in handler.topics() {
-                                    if MemoryEventBus::topic_matches(&handler_topic, &event.topic())
-                                    {
-                                        let handler = handler.clone();
-                                        let event = event.clone();
-                                        tokio::spawn(async move {
-                                            if let Err(e) = handler.handle(event).await {
-                                                tracing::error!("Handler error: {}", e);
-                                            }
-                                        });
-                                        break;
-                                    }
-                                }
-                            }
-                        }
-                        Ok(None) => break,
-                        Err(_) => continue, // Timeout, check running flag
-                    }
-                }
-            });
+    #[tokio::test]
+    async fn test_memory_event_bus_publish_subscribe() {
+        let bus = MemoryEventBus::new();
 
-            Ok(())
-        }
+        // Subscribe to topic
+        let mut sub = bus.subscribe("verity.document.*").await.unwrap();
 
-        /// Replay events from a stream.
-        ///
-        /// # Arguments
-        ///
-        /// * `topic` - Topic to replay
-        /// * `since` - Start from this stream ID (e.g., "0" for all, or a specific ID)
-        /// * `count` - Maximum number of events to replay
-        ///
-        /// # Returns
-        ///
-        /// Vector of replayed events
-        pub async fn replay_events(
-            &self,
-            topic: &str,
-            since: &str,
-            count: usize,
-        ) -> EventBusResult<Vec<Event>> {
-            if !self.config.use_streams {
-                return Ok(Vec::new());
-            }
+        // Publish event
+        let event = Event::new("document.created", App::Verity, serde_json::json!({}));
+        bus.publish(event.clone()).await.unwrap();
 
-            let stream_key = self.stream_key(topic);
-            let mut conn = self.conn.clone();
-
-            let result: Vec<redis::Value> = redis::cmd("XREAD")
-                .arg("COUNT")
-                .arg(count)
-                .arg("STREAMS")
-                .arg(&stream_key)
-                .arg(since)
-                .query_async(&mut conn)
-                .await
-                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
-
-            let mut events = Vec::new();
-
-            // Parse XREAD response
-            for stream_data in result {
-                if let redis::Value::Array(entries) = stream_data {
-                    for entry in entries {
-                        if let redis::Value::Array(fields) = entry {
-                            for field in fields {
-                                if let redis::Value::BulkString(data) = field {
-                                    if let Ok(s) = String::from_utf8(data) {
-                                        if let Ok(event) = serde_json::from_str::<Event>(&s) {
-                                            events.push(event);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        // Receive event
+        let received =
+            tokio::time::timeout(std::time::Duration::from_millis(100), sub.recv()).await;
 
-            Ok(events)
-        }
+        assert!(received.is_ok());
+    }
 
-        /// Shutdown the event bus.
-        pub fn shutdown(&self) {
-            self.running.store(false, Ordering::Relaxed);
-        }
+    #[tokio::test]
+    async fn test_memory_event_bus_conformance() {
+        crate::conformance::run(MemoryEventBus::new()).await;
     }
 
-    use futures::StreamExt;
+    /// Handler failing until `failures` is used up.
+    struct Flaky {
+        failures: std::sync::atomic::AtomicU32,
+        attempts: tokio::sync::mpsc::UnboundedSender<u32>,
+    }
 
     #[async_trait]
-    impl EventBus for RedisEventBus {
-        async fn publish(&self, event: Event) -> EventBusResult<()> {
-            // Publish to both stream (persistence) and pubsub (real-time)
-            self.publish_to_stream(&event).await?;
-            self.publish_to_pubsub(&event).await?;
-
-            self.events_published.fetch_add(1, Ordering::Relaxed);
-
-            tracing::debug!(
-                topic = %event.topic(),
-                event_id = %event.id,
-                "Event published to Redis"
-            );
-
-            Ok(())
-        }
-
-        async fn subscribe(&self, topic: &str) -> EventBusResult<Subscription> {
-            let id = uuid::Uuid::now_v7().to_string();
-
-            // Create local broadcast channel
-            let receiver = {
-                let mut channels = self.local_channels.write().await;
-
-                if let Some(sender) = channels.get(topic) {
-                    sender.subscribe()
-                } else {
-                    let (sender, receiver) = broadcast::channel(1024);
-                    channels.insert(topic.to_string(), sender);
-                    receiver
-                }
-            };
-
-            // Start background listener if not already running
-            self.start_subscription_listener(topic).await?;
-
-            self.active_subscriptions.fetch_add(1, Ordering::Relaxed);
-
-            Ok(Subscription {
-                id,
-                topic: topic.to_string(),
-                receiver,
-            })
+    impl EventHandler for Flaky {
+        async fn handle(&self, event: Event) -> EventBusResult<()> {
+            self.handle_delivery(event, Delivery::first()).await
         }
 
-        async fn register_handler(&self, handler: Arc<dyn EventHandler>) -> EventBusResult<()> {
-            let topics = handler.topics();
-
-            {
-                let mut handlers = self.handlers.write().await;
-                handlers.push(handler);
+        async fn handle_delivery(&self, _event: Event, delivery: Delivery) -> EventBusResult<()> {
+            let _ = self.attempts.send(delivery.attempt);
+            let remaining = self.failures.load(std::sync::atomic::Ordering::SeqCst);
+            if remaining > 0 {
+                self.failures
+                    .store(remaining - 1, std::sync::atomic::Ordering::SeqCst);
+                return Err(EventBusError::PublishError("index unavailable".into()));
             }
-
-            // Start listeners for handler topics
-            for topic in topics {
-                self.start_subscription_listener(&topic).await?;
-            }
-
             Ok(())
         }
 
-        async fn unsubscribe(&self, _subscription_id: &str) -> EventBusResult<()> {
-            self.active_subscriptions.fetch_sub(1, Ordering::Relaxed);
-            Ok(())
+        fn topics(&self) -> Vec<String> {
+            vec!["noteman.#".to_string()]
         }
 
-        async fn stats(&self) -> EventBusStats {
-            EventBusStats {
-                events_published: self.events_published.load(Ordering::Relaxed),
-                events_delivered: self.events_delivered.load(Ordering::Relaxed),
-                active_subscriptions: self.active_subscriptions.load(Ordering::Relaxed) as usize,
-                registered_handlers: self.handlers.read().await.len(),
-            }
+        fn name(&self) -> &str {
+            "indexer"
         }
-    }
-}
 
-#[cfg(feature = "redis")]
-pub use redis_bus::{RedisEventBus, RedisEventBusConfig};
+        fn retry_policy(&self) -> Option<RetryPolicy> {
+            Some(RetryPolicy::default().with_max_attempts(2).with_backoff(
+                std::time::Duration::from_millis(10),
+                std::time::Duration::from_millis(10),
+            ))
+        }
+    }
 
-// ============================================================================
-// Tests
-// ============================================================================
+    #[tokio::test]
+    async fn test_failing_handler_is_retried_then_dead_lettered() {
+        let bus = MemoryEventBus::new();
+        let (sender, mut attempts) = tokio::sync::mpsc::unbounded_channel();
+        bus.register_handler(Arc::new(Flaky {
+            failures: 3.into(),
+            attempts: sender,
+        }))
+        .await
+        .unwrap();
+
+        let event = Event::new("meeting.ended", App::NoteMan, serde_json::json!({}));
+        bus.publish(event.clone()).await.unwrap();
+        assert_eq!(attempts.recv().await, Some(1));
+        assert_eq!(attempts.recv().await, Some(2));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use platform_rbac::App;
+        // Both attempts failed: the event is dead-lettered.
+        let dead_letter = loop {
+            if let Some(dead_letter) = bus.dead_letters().await.unwrap().pop() {
+                break dead_letter;
+            }
+            tokio::task::yield_now().await;
+        };
+        assert_eq!(dead_letter.handler, "indexer");
+        assert_eq!(dead_letter.event.id, event.id);
+        assert_eq!(dead_letter.attempts, 2);
+        assert!(dead_letter.error.contains("index unavailable"));
+
+        // Redriving fails once more, then succeeds.
+        bus.redrive_dead_letter(dead_letter.id).await.unwrap();
+        assert!(bus.dead_letter(dead_letter.id).await.unwrap().is_none());
+        assert_eq!(attempts.recv().await, Some(1));
+        assert_eq!(attempts.recv().await, Some(2));
+        assert!(bus.dead_letters().await.unwrap().is_empty());
+
+        assert!(matches!(
+            bus.redrive_dead_letter(dead_letter.id).await,
+            Err(EventBusError::DeadLetterNotFound(_))
+        ));
+        assert_eq!(bus.purge_dead_letters().await.unwrap(), 0);
+    }
 
     #[tokio::test]
-    async fn test_memory_event_bus_publish_subscribe() {
+    async fn test_unsubscribe_and_drop_end_subscriptions() {
         let bus = MemoryEventBus::new();
+        let mut sub = bus.subscribe("verity.#").await.unwrap();
+        let dropped = bus.subscribe("verity.#").await.unwrap();
+        assert_eq!(bus.stats().await.active_subscriptions, 2);
 
-        // Subscribe to topic
-        let mut sub = bus.subscribe("verity.document.*").await.unwrap();
+        drop(dropped);
+        assert_eq!(bus.stats().await.active_subscriptions, 1);
 
-        // Publish event
+        bus.unsubscribe(&sub.id).await.unwrap();
+        assert_eq!(bus.stats().await.active_subscriptions, 0);
         let event = Event::new("document.created", App::Verity, serde_json::json!({}));
-        bus.publish(event.clone()).await.unwrap();
+        bus.publish(event).await.unwrap();
+        assert!(matches!(
+            sub.recv().await,
+            Err(EventBusError::ChannelClosed)
+        ));
+        assert_eq!(bus.stats().await.events_delivered, 0);
+    }
 
-        // Receive event
-        let received =
-            tokio::time::timeout(std::time::Duration::from_millis(100), sub.recv()).await;
+    #[tokio::test]
+    async fn test_lagged_subscriber_continues() {
+        let bus = MemoryEventBus::with_capacity(2);
+        let mut sub = bus.subscribe("verity.#").await.unwrap();
+
+        let mut events = Vec::new();
+        for _ in 0..4 {
+            let event = Event::new("document.created", App::Verity, serde_json::json!({}));
+            bus.publish(event.clone()).await.unwrap();
+            events.push(event);
+        }
 
-        assert!(received.is_ok());
+        assert!(matches!(sub.recv().await, Err(EventBusError::Lagged(2))));
+        assert_eq!(sub.recv().await.unwrap().id, events[2].id);
+        assert_eq!(sub.recv().await.unwrap().id, events[3].id);
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_holds_publisher() {
+        let bus = Arc::new(MemoryEventBus::with_capacity(1).with_backpressure());
+        let mut sub = bus.subscribe("verity.#").await.unwrap();
+        let event = || Event::new("document.created", App::Verity, serde_json::json!({}));
+
+        bus.publish(event()).await.unwrap();
+        let publisher = tokio::spawn({
+            let bus = bus.clone();
+            async move { bus.publish(event()).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!publisher.is_finished());
+
+        // Reading makes room; the second event is queued, not dropped.
+        sub.recv().await.unwrap();
+        publisher.await.unwrap().unwrap();
+        sub.recv().await.unwrap();
+        assert_eq!(bus.stats().await.events_delivered, 2);
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_releases_held_publisher() {
+        let bus = Arc::new(MemoryEventBus::with_capacity(1).with_backpressure());
+        let mut sub = bus.subscribe("verity.#").await.unwrap();
+        let event = || Event::new("document.created", App::Verity, serde_json::json!({}));
+
+        bus.publish(event()).await.unwrap();
+        let publisher = tokio::spawn({
+            let bus = bus.clone();
+            async move { bus.publish(event()).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!publisher.is_finished());
+
+        // The subscription is still held but no longer read.
+        bus.unsubscribe(&sub.id).await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(1), publisher)
+            .await
+            .expect("publisher should be released")
+            .unwrap()
+            .unwrap();
+        assert_eq!(bus.stats().await.events_delivered, 1);
+
+        // Events queued before unsubscribing can still be read.
+        sub.recv().await.unwrap();
+        assert!(matches!(
+            sub.recv().await,
+            Err(EventBusError::ChannelClosed)
+        ));
     }
 
     #[test]
@@ -807,7 +802,7 @@ mod tests {
         assert_eq!(stats.events_published, 0);
         assert_eq!(stats.active_subscriptions, 0);
 
-        let _ = bus.subscribe("test.*").await.unwrap();
+        let _sub = bus.subscribe("test.*").await.unwrap();
         let stats = bus.stats().await;
         assert_eq!(stats.active_subscriptions, 1);
 
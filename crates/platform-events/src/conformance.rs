@@ -0,0 +1,123 @@
+//! Conformance suite shared by the event bus backends.
+//!
+//! Every backend must deliver events by topic pattern and count
+//! [`EventBusStats`] the same way. Each backend's tests call [`run`] on a
+//! fresh bus.
+
+use crate::bus::{EventBus, EventBusError, EventBusResult, EventHandler, Subscription};
+use crate::types::Event;
+use async_trait::async_trait;
+use platform_rbac::App;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How long to wait for a delivery.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait before concluding that an event is not delivered.
+const QUIET_PERIOD: Duration = Duration::from_millis(300);
+
+/// Handler forwarding its events to a channel.
+struct Recorder {
+    topic: String,
+    events: mpsc::UnboundedSender<Event>,
+}
+
+#[async_trait]
+impl EventHandler for Recorder {
+    async fn handle(&self, event: Event) -> EventBusResult<()> {
+        let _ = self.events.send(event);
+        Ok(())
+    }
+
+    fn topics(&self) -> Vec<String> {
+        vec![self.topic.clone()]
+    }
+}
+
+async fn next(sub: &mut Subscription) -> Event {
+    tokio::time::timeout(DELIVERY_TIMEOUT, sub.recv())
+        .await
+        .expect("event not delivered")
+        .expect("subscription closed")
+}
+
+async fn assert_quiet(sub: &mut Subscription) {
+    let extra = tokio::time::timeout(QUIET_PERIOD, sub.recv()).await;
+    assert!(extra.is_err(), "unexpected event delivered: {:?}", extra);
+}
+
+/// Run the conformance suite against a fresh bus.
+pub(crate) async fn run<B: EventBus>(bus: B) {
+    let stats = bus.stats().await;
+    assert_eq!(stats.events_published, 0);
+    assert_eq!(stats.events_delivered, 0);
+    assert_eq!(stats.active_subscriptions, 0);
+    assert_eq!(stats.registered_handlers, 0);
+
+    // Subscriptions only receive events matching their pattern.
+    let mut documents = bus.subscribe("verity.document.*").await.unwrap();
+    let mut any_app = bus.subscribe("*.document.#").await.unwrap();
+    assert_eq!(bus.stats().await.active_subscriptions, 2);
+
+    let meeting = Event::new("meeting.ended", App::NoteMan, serde_json::json!({}));
+    let document = Event::new("document.created", App::Verity, serde_json::json!({}));
+    let verified = Event::new(
+        "document.verification.completed",
+        App::Verity,
+        serde_json::json!({}),
+    );
+    bus.publish(meeting.clone()).await.unwrap();
+    bus.publish(document.clone()).await.unwrap();
+    bus.publish(verified.clone()).await.unwrap();
+
+    assert_eq!(next(&mut documents).await.id, document.id);
+    assert_quiet(&mut documents).await;
+    assert_eq!(next(&mut any_app).await.id, document.id);
+    assert_eq!(next(&mut any_app).await.id, verified.id);
+    assert_quiet(&mut any_app).await;
+
+    let stats = bus.stats().await;
+    assert_eq!(stats.events_published, 3);
+    assert_eq!(stats.events_delivered, 3);
+
+    // Handlers receive matching events once, however many topics match.
+    let (sender, mut handled) = mpsc::unbounded_channel();
+    bus.register_handler(Arc::new(Recorder {
+        topic: "noteman.#".to_string(),
+        events: sender,
+    }))
+    .await
+    .unwrap();
+    assert_eq!(bus.stats().await.registered_handlers, 1);
+
+    bus.publish(document.clone()).await.unwrap();
+    bus.publish(meeting.clone()).await.unwrap();
+    let received = tokio::time::timeout(DELIVERY_TIMEOUT, handled.recv())
+        .await
+        .expect("event not handled")
+        .unwrap();
+    assert_eq!(received.id, meeting.id);
+    assert!(tokio::time::timeout(QUIET_PERIOD, handled.recv())
+        .await
+        .is_err());
+
+    // The document event reached both subscriptions, the meeting the handler.
+    assert_eq!(next(&mut documents).await.id, document.id);
+    assert_eq!(next(&mut any_app).await.id, document.id);
+    let stats = bus.stats().await;
+    assert_eq!(stats.events_published, 5);
+    assert_eq!(stats.events_delivered, 6);
+
+    // Unsubscribing removes and closes the subscription.
+    bus.unsubscribe(&documents.id).await.unwrap();
+    bus.unsubscribe(&any_app.id).await.unwrap();
+    assert_eq!(bus.stats().await.active_subscriptions, 0);
+    bus.publish(document.clone()).await.unwrap();
+    let closed = tokio::time::timeout(DELIVERY_TIMEOUT, documents.recv())
+        .await
+        .expect("subscription not closed");
+    assert!(matches!(closed, Err(EventBusError::ChannelClosed)));
+    assert_eq!(bus.stats().await.events_delivered, 6);
+}
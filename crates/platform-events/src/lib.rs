@@ -6,16 +6,20 @@
 //! ## Overview
 //!
 //! The platform-events crate handles:
-//! - **Event Types**: Strongly-typed events for each application
+//! - **Event Types**: Strongly-typed events for each application, decoded
+//!   back from the bus through typed subscriptions and handlers
 //! - **Event Bus**: Publish/subscribe messaging
 //! - **Cross-App Events**: Events for workflows spanning multiple apps
-//! - **Event Handlers**: Async event processing
+//! - **Event Handlers**: Async event processing, with retries and a
+//!   dead-letter queue for events handlers fail to process
 //!
 //! ## Features
 //!
 //! - `memory` (default): In-memory event bus for single-process apps
-//! - `redis`: Redis-backed event bus for distributed systems
-//! - `nats`: NATS-backed event bus for high-throughput systems
+//! - `redis`: Redis-backed event bus for distributed systems, over Streams
+//!   (persistent, replayable) or Pub/Sub
+//! - `nats`: NATS-backed event bus for high-throughput systems, with durable
+//!   consumers and replay through JetStream
 //!
 //! ## Event Types
 //!
@@ -37,7 +41,7 @@
 //! ### Publishing Events
 //!
 //! ```rust,no_run
-//! use platform_events::{Event, EventBus, MemoryEventBus, DocumentEvent};
+//! use platform_events::{DocumentEvent, DomainEvent, EventBus, MemoryEventBus};
 //! use platform_rbac::App;
 //! use uuid::Uuid;
 //!
@@ -77,6 +81,24 @@
 //! }
 //! ```
 //!
+//! ### Receiving Typed Events
+//!
+//! ```rust,no_run
+//! use platform_events::{DocumentEvent, MemoryEventBus, TypedEventBus};
+//!
+//! async fn typed_example() {
+//!     let bus = MemoryEventBus::new();
+//!
+//!     // Receive decoded document events with their envelope context
+//!     let mut documents = bus.subscribe_typed::<DocumentEvent>().await.unwrap();
+//!     while let Ok(document) = documents.recv().await {
+//!         if let DocumentEvent::Verified { document_id, score, .. } = document.data {
+//!             println!("{} verified ({}) in org {:?}", document_id, score, document.org_id);
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! ## Topic Patterns
 //!
 //! Topics are structured as `{app}.{event_type}`:
@@ -107,20 +129,31 @@
 //!    - Verity verifies repository documentation
 
 pub mod bus;
+pub mod dlq;
+pub mod typed;
 pub mod types;
 
+#[cfg(test)]
+mod conformance;
+
+#[cfg(feature = "nats")]
+pub mod nats;
 #[cfg(feature = "redis")]
 pub mod redis;
 
 // Re-export main types
 pub use bus::{
-    EventBus, EventBusError, EventBusResult, EventBusStats, EventHandler, MemoryEventBus,
-    Subscription,
+    topic_matches, Delivery, EventBus, EventBusError, EventBusResult, EventBusStats, EventHandler,
+    MemoryEventBus, Subscription,
 };
+pub use dlq::{DeadLetter, DeadLetterQueue, DeadLetterStore, MemoryDeadLetterStore, RetryPolicy};
+pub use typed::{TypedEvent, TypedEventBus, TypedEventHandler, TypedHandler, TypedSubscription};
 pub use types::{
-    ActionItem, AssertionEvent, CrossAppEvent, DocumentEvent, Event, EventCategory, MeetingEvent,
-    RepositoryEvent,
+    ActionItem, AssertionEvent, CrossAppEvent, DecodeError, DocumentEvent, DomainEvent, Event,
+    EventCategory, MeetingEvent, RepositoryEvent,
 };
 
+#[cfg(feature = "nats")]
+pub use nats::{NatsEventBus, NatsEventBusConfig};
 #[cfg(feature = "redis")]
-pub use redis::RedisEventBus;
+pub use redis::{RedisDeadLetterStore, RedisEventBus, RedisEventBusConfig};
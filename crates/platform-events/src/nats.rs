@@ -0,0 +1,775 @@
+//! NATS-backed event bus for distributed deployments.
+//!
+//! Events are published on NATS subjects `{prefix}.{topic}`, e.g.
+//! `platform_events.verity.document.created`. Subscriptions map topic
+//! wildcards onto NATS subject wildcards:
+//!
+//! - `*` is the NATS `*`: exactly one segment.
+//! - A trailing `#` becomes the NATS `>` plus the subject before it, since
+//!   `>` needs at least one segment where `#` also matches none.
+//! - A `#` in the middle of a pattern cannot be expressed in NATS, so
+//!   everything after the segments before it is subscribed to.
+//!
+//! Every received event is checked against the topic pattern again (see
+//! [`topic_matches`]), so delivery follows the same rules as the other
+//! backends.
+//!
+//! With JetStream enabled (the default), published events are also stored
+//! in a stream. The stream allows durable handlers that resume where they
+//! left off after a restart ([`NatsEventBus::register_durable_handler`]) and
+//! replay of past events ([`NatsEventBus::replay_events`]). The stream
+//! deduplicates events by ID, so a retried publish is stored once.
+//!
+//! Failing handlers are retried with their [`RetryPolicy`]; events that still
+//! fail go to the dead-letter queue (see [`crate::dlq`]).
+
+use crate::bus::{
+    topic_matches, Delivery, EventBus, EventBusError, EventBusResult, EventBusStats, EventHandler,
+    Subscription,
+};
+use crate::dlq::{DeadLetter, DeadLetterQueue, DeadLetterStore, Retrier, RetryPolicy};
+use crate::types::Event;
+use async_nats::jetstream::{self, consumer, stream, AckKind};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// NATS event bus configuration.
+#[derive(Debug, Clone)]
+pub struct NatsEventBusConfig {
+    /// NATS server URL (default: `NATS_URL` or `nats://127.0.0.1:4222`).
+    pub url: String,
+
+    /// Prefix of all subjects (default: "platform_events").
+    pub subject_prefix: String,
+
+    /// Store events in a JetStream stream (default: true).
+    pub jetstream: bool,
+
+    /// Name of the JetStream stream (default: "PLATFORM_EVENTS").
+    pub stream_name: String,
+
+    /// How long the stream keeps events (default: 7 days).
+    pub max_age: Duration,
+
+    /// Maximum number of events in the stream (default: 1,000,000).
+    pub max_messages: i64,
+
+    /// Window in which events with the same ID are stored once
+    /// (default: 2 minutes).
+    pub duplicate_window: Duration,
+
+    /// Capacity of each subscription's channel (default: 1024).
+    pub channel_capacity: usize,
+}
+
+impl Default for NatsEventBusConfig {
+    fn default() -> Self {
+        Self {
+            url: std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string()),
+            subject_prefix: "platform_events".to_string(),
+            jetstream: true,
+            stream_name: "PLATFORM_EVENTS".to_string(),
+            max_age: Duration::from_secs(7 * 24 * 60 * 60),
+            max_messages: 1_000_000,
+            duplicate_window: Duration::from_secs(2 * 60),
+            channel_capacity: 1024,
+        }
+    }
+}
+
+/// A handler and the task delivering its events.
+type HandlerTask = (Arc<dyn EventHandler>, JoinHandle<()>);
+
+/// Delivery counters shared with the background tasks.
+#[derive(Debug, Default)]
+struct Counters {
+    published: AtomicU64,
+    delivered: AtomicU64,
+}
+
+/// NATS-backed event bus.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use platform_events::{EventBus, NatsEventBus, NatsEventBusConfig};
+///
+/// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+///     let bus = NatsEventBus::new(NatsEventBusConfig::default()).await?;
+///     let mut sub = bus.subscribe("verity.document.*").await?;
+///     let event = sub.recv().await?;
+///     println!("Received: {}", event.event_type);
+///     Ok(())
+/// }
+/// ```
+pub struct NatsEventBus {
+    /// NATS client.
+    client: async_nats::Client,
+
+    /// JetStream context, if enabled.
+    jetstream: Option<jetstream::Context>,
+
+    /// Configuration.
+    config: NatsEventBusConfig,
+
+    /// Forwarding tasks of the active subscriptions, by subscription ID.
+    subscriptions: Mutex<HashMap<String, JoinHandle<()>>>,
+
+    /// Registered handlers and their tasks.
+    handlers: Mutex<Vec<HandlerTask>>,
+
+    /// Handler retry policy and dead-letter store.
+    retrier: Retrier,
+
+    /// Statistics.
+    counters: Arc<Counters>,
+}
+
+impl std::fmt::Debug for NatsEventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NatsEventBus")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl NatsEventBus {
+    /// Connect to NATS and, with JetStream enabled, create the stream if it
+    /// does not exist.
+    pub async fn new(config: NatsEventBusConfig) -> EventBusResult<Self> {
+        let client = async_nats::connect(config.url.as_str())
+            .await
+            .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+
+        let jetstream = if config.jetstream {
+            let context = jetstream::new(client.clone());
+            context
+                .get_or_create_stream(stream::Config {
+                    name: config.stream_name.clone(),
+                    subjects: vec![format!("{}.>", config.subject_prefix)],
+                    max_age: config.max_age,
+                    max_messages: config.max_messages,
+                    duplicate_window: config.duplicate_window,
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+            Some(context)
+        } else {
+            None
+        };
+
+        tracing::info!(url = %config.url, jetstream = config.jetstream, "Connected NATS event bus");
+
+        Ok(Self {
+            client,
+            jetstream,
+            config,
+            subscriptions: Mutex::new(HashMap::new()),
+            handlers: Mutex::new(Vec::new()),
+            retrier: Retrier::default(),
+            counters: Arc::new(Counters::default()),
+        })
+    }
+
+    /// Create with default configuration.
+    pub async fn with_defaults() -> EventBusResult<Self> {
+        Self::new(NatsEventBusConfig::default()).await
+    }
+
+    /// Set the retry policy of handlers without their own
+    /// (default: [`RetryPolicy::default`]).
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retrier.policy = policy;
+        self
+    }
+
+    /// Set where events that handlers failed to process go
+    /// (default: a [`MemoryDeadLetterStore`](crate::MemoryDeadLetterStore)).
+    pub fn with_dead_letter_store(mut self, store: Arc<dyn DeadLetterStore>) -> Self {
+        self.retrier.store = store;
+        self
+    }
+
+    /// Get the subject of a topic.
+    fn subject(&self, topic: &str) -> String {
+        format!("{}.{}", self.config.subject_prefix, topic)
+    }
+
+    /// Get the subjects to subscribe to for a topic pattern.
+    fn subjects(&self, pattern: &str) -> Vec<String> {
+        pattern_subjects(&self.config.subject_prefix, pattern)
+    }
+
+    /// Get the JetStream stream.
+    async fn stream(&self) -> EventBusResult<stream::Stream> {
+        let context = self.jetstream.as_ref().ok_or_else(|| {
+            EventBusError::SubscribeError("JetStream is disabled for this bus".to_string())
+        })?;
+        context
+            .get_stream(&self.config.stream_name)
+            .await
+            .map_err(|e| EventBusError::ConnectionError(e.to_string()))
+    }
+
+    /// Subscribe to every subject of `patterns`, returning one stream of
+    /// the events matching any of them.
+    async fn subscribe_events(
+        &self,
+        patterns: &[String],
+    ) -> EventBusResult<futures::stream::BoxStream<'static, Event>> {
+        let mut subscribers = Vec::new();
+        for pattern in patterns {
+            for subject in self.subjects(pattern) {
+                let subscriber = self
+                    .client
+                    .subscribe(subject)
+                    .await
+                    .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
+                subscribers.push(subscriber);
+            }
+        }
+        // Make sure the server knows the interest before returning, so that
+        // events published next are received.
+        self.client
+            .flush()
+            .await
+            .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+
+        let patterns = patterns.to_vec();
+        let events = futures::stream::select_all(subscribers).filter_map(move |message| {
+            let event = decode(&message.payload)
+                .filter(|event| patterns.iter().any(|p| topic_matches(p, &event.topic())));
+            async move { event }
+        });
+        Ok(events.boxed())
+    }
+
+    /// Register a handler that receives every matching event at least once,
+    /// including events published while it was not running.
+    ///
+    /// The handler reads from a durable JetStream consumer named `durable`.
+    /// Registering a handler with the same name after a restart resumes where
+    /// the previous one stopped; the first registration starts with events
+    /// published from then on. An event is acknowledged when the handler
+    /// returns `Ok` and redelivered after the retry policy's backoff when it
+    /// returns an error, until its attempts are used up and it is
+    /// dead-lettered. The handler gets the delivery attempt through
+    /// [`EventHandler::handle_delivery`].
+    pub async fn register_durable_handler(
+        &self,
+        durable: &str,
+        handler: Arc<dyn EventHandler>,
+    ) -> EventBusResult<()> {
+        let topics = handler.topics();
+        let mut subjects: Vec<String> = topics.iter().flat_map(|t| self.subjects(t)).collect();
+        subjects.sort();
+        subjects.dedup();
+
+        let mut config = consumer::pull::Config {
+            durable_name: Some(durable.to_string()),
+            deliver_policy: consumer::DeliverPolicy::New,
+            ack_policy: consumer::AckPolicy::Explicit,
+            ..Default::default()
+        };
+        set_filter(&mut config, subjects);
+
+        let consumer = self
+            .stream()
+            .await?
+            .get_or_create_consumer(durable, config)
+            .await
+            .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
+        let mut messages = consumer
+            .messages()
+            .await
+            .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
+
+        let counters = self.counters.clone();
+        let retrier = self.retrier.clone();
+        let durable = durable.to_string();
+        let registered = handler.clone();
+        let task = tokio::spawn(async move {
+            while let Some(message) = messages.next().await {
+                let message = match message {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::warn!(durable = %durable, error = %e, "Durable consumer error");
+                        continue;
+                    }
+                };
+                let event = decode(&message.payload)
+                    .filter(|event| topics.iter().any(|t| topic_matches(t, &event.topic())));
+                let delivery = delivery(&message);
+                let ack = match event {
+                    Some(event) => match handler.handle_delivery(event.clone(), delivery).await {
+                        Ok(()) => {
+                            counters.delivered.fetch_add(1, Ordering::Relaxed);
+                            message.ack().await
+                        }
+                        Err(e) => {
+                            let policy = retrier.policy(handler.as_ref());
+                            if delivery.attempt < policy.max_attempts {
+                                let backoff = policy.backoff(delivery.attempt);
+                                tracing::warn!(durable = %durable, error = %e, retry_in = ?backoff, "Handler failed, event will be redelivered");
+                                message.ack_with(AckKind::Nak(Some(backoff))).await
+                            } else {
+                                // Out of attempts: dead-letter and acknowledge.
+                                retrier
+                                    .dead_letter(handler.as_ref(), event, &e, delivery.attempt)
+                                    .await;
+                                message.ack().await
+                            }
+                        }
+                    },
+                    // Not for this handler, or not an event.
+                    None => message.ack().await,
+                };
+                if let Err(e) = ack {
+                    tracing::warn!(durable = %durable, error = %e, "Failed to acknowledge event");
+                }
+            }
+        });
+
+        self.lock_handlers().push((registered, task));
+        Ok(())
+    }
+
+    /// Replay stored events matching `topic`, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - Topic pattern to replay
+    /// * `since` - Only replay events stored from this time; all if `None`
+    /// * `count` - Maximum number of events to replay
+    pub async fn replay_events(
+        &self,
+        topic: &str,
+        since: Option<DateTime<Utc>>,
+        count: usize,
+    ) -> EventBusResult<Vec<Event>> {
+        let deliver_policy = match since {
+            Some(since) => consumer::DeliverPolicy::ByStartTime {
+                start_time: time::OffsetDateTime::from_unix_timestamp_nanos(
+                    since.timestamp_nanos_opt().unwrap_or_default() as i128,
+                )
+                .map_err(|e| EventBusError::SubscribeError(e.to_string()))?,
+            },
+            None => consumer::DeliverPolicy::All,
+        };
+        let mut config = consumer::pull::Config {
+            deliver_policy,
+            ack_policy: consumer::AckPolicy::None,
+            inactive_threshold: Duration::from_secs(30),
+            ..Default::default()
+        };
+        set_filter(&mut config, self.subjects(topic));
+
+        let consumer = self
+            .stream()
+            .await?
+            .create_consumer(config)
+            .await
+            .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
+
+        let mut events = Vec::new();
+        while events.len() < count {
+            let mut batch = consumer
+                .fetch()
+                .max_messages(count - events.len())
+                .messages()
+                .await
+                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+            let mut fetched = 0;
+            while let Some(message) = batch.next().await {
+                let message = message.map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+                fetched += 1;
+                if let Some(event) =
+                    decode(&message.payload).filter(|event| topic_matches(topic, &event.topic()))
+                {
+                    events.push(event);
+                }
+            }
+            if fetched == 0 {
+                break;
+            }
+        }
+        Ok(events)
+    }
+
+    /// Stop all subscriptions and handlers.
+    pub fn shutdown(&self) {
+        for (_, task) in self.lock_subscriptions().drain() {
+            task.abort();
+        }
+        for (_, task) in self.lock_handlers().drain(..) {
+            task.abort();
+        }
+    }
+
+    fn lock_subscriptions(&self) -> std::sync::MutexGuard<'_, HashMap<String, JoinHandle<()>>> {
+        self.subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_handlers(&self) -> std::sync::MutexGuard<'_, Vec<HandlerTask>> {
+        self.handlers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl EventBus for NatsEventBus {
+    async fn publish(&self, event: Event) -> EventBusResult<()> {
+        let subject = self.subject(&event.topic());
+        let payload = serde_json::to_vec(&event)
+            .map_err(|e| EventBusError::SerializationError(e.to_string()))?;
+
+        match self.jetstream {
+            Some(ref context) => {
+                let publish = jetstream::context::Publish::build()
+                    .payload(payload.into())
+                    .message_id(event.id.to_string());
+                context
+                    .send_publish(subject, publish)
+                    .await
+                    .map_err(|e| EventBusError::PublishError(e.to_string()))?
+                    .await
+                    .map_err(|e| EventBusError::PublishError(e.to_string()))?;
+            }
+            None => self
+                .client
+                .publish(subject, payload.into())
+                .await
+                .map_err(|e| EventBusError::PublishError(e.to_string()))?,
+        }
+
+        self.counters.published.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(topic = %event.topic(), event_id = %event.id, "Event published to NATS");
+
+        Ok(())
+    }
+
+    async fn subscribe(&self, topic: &str) -> EventBusResult<Subscription> {
+        let id = uuid::Uuid::now_v7().to_string();
+        let mut events = self.subscribe_events(&[topic.to_string()]).await?;
+        let (sender, receiver) = broadcast::channel(self.config.channel_capacity);
+
+        let counters = self.counters.clone();
+        let task = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                if sender.send(event).is_err() {
+                    // The subscription handle was dropped.
+                    break;
+                }
+                counters.delivered.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        self.lock_subscriptions().insert(id.clone(), task);
+
+        Ok(Subscription::new(id, topic.to_string(), receiver))
+    }
+
+    async fn register_handler(&self, handler: Arc<dyn EventHandler>) -> EventBusResult<()> {
+        let mut events = self.subscribe_events(&handler.topics()).await?;
+
+        let counters = self.counters.clone();
+        let retrier = self.retrier.clone();
+        let registered = handler.clone();
+        let task = tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                counters.delivered.fetch_add(1, Ordering::Relaxed);
+                let handler = handler.clone();
+                let retrier = retrier.clone();
+                tokio::spawn(async move {
+                    let _ = retrier.deliver(handler.as_ref(), event).await;
+                });
+            }
+        });
+
+        self.lock_handlers().push((registered, task));
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, subscription_id: &str) -> EventBusResult<()> {
+        // Dropping the NATS subscribers in the aborted task unsubscribes them.
+        if let Some(task) = self.lock_subscriptions().remove(subscription_id) {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    async fn stats(&self) -> EventBusStats {
+        EventBusStats {
+            events_published: self.counters.published.load(Ordering::Relaxed),
+            events_delivered: self.counters.delivered.load(Ordering::Relaxed),
+            active_subscriptions: {
+                let mut subscriptions = self.lock_subscriptions();
+                subscriptions.retain(|_, task| !task.is_finished());
+                subscriptions.len()
+            },
+            registered_handlers: self.lock_handlers().len(),
+        }
+    }
+}
+
+#[async_trait]
+impl DeadLetterQueue for NatsEventBus {
+    async fn dead_letters(&self) -> EventBusResult<Vec<DeadLetter>> {
+        self.retrier.store.list().await
+    }
+
+    async fn dead_letter(&self, id: uuid::Uuid) -> EventBusResult<Option<DeadLetter>> {
+        self.retrier.store.get(id).await
+    }
+
+    async fn redrive_dead_letter(&self, id: uuid::Uuid) -> EventBusResult<()> {
+        let handlers: Vec<_> = self
+            .lock_handlers()
+            .iter()
+            .map(|(h, _)| h.clone())
+            .collect();
+        self.retrier.redrive(id, &handlers).await
+    }
+
+    async fn purge_dead_letters(&self) -> EventBusResult<usize> {
+        self.retrier.store.purge().await
+    }
+}
+
+impl Drop for NatsEventBus {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Get the NATS subjects that cover a topic pattern.
+fn pattern_subjects(prefix: &str, pattern: &str) -> Vec<String> {
+    let segments: Vec<&str> = pattern.split('.').collect();
+    let Some(hash) = segments.iter().position(|s| *s == "#") else {
+        return vec![format!("{}.{}", prefix, pattern)];
+    };
+
+    let before = segments[..hash].join(".");
+    let (any, exact) = if before.is_empty() {
+        (format!("{}.>", prefix), None)
+    } else {
+        (
+            format!("{}.{}.>", prefix, before),
+            Some(format!("{}.{}", prefix, before)),
+        )
+    };
+    // The segments before the `#` alone match only if the rest can be empty.
+    let rest_can_be_empty = segments[hash..].iter().all(|s| *s == "#");
+    match exact {
+        Some(exact) if rest_can_be_empty => vec![exact, any],
+        _ => vec![any],
+    }
+}
+
+/// Filter a consumer on `subjects`.
+fn set_filter(config: &mut consumer::pull::Config, mut subjects: Vec<String>) {
+    if subjects.len() == 1 {
+        config.filter_subject = subjects.remove(0);
+    } else {
+        config.filter_subjects = subjects;
+    }
+}
+
+/// Get the delivery details of a JetStream message.
+fn delivery(message: &jetstream::Message) -> Delivery {
+    let attempt = message
+        .info()
+        .map(|info| info.delivered.max(1) as u32)
+        .unwrap_or(1);
+    Delivery { attempt }
+}
+
+/// Decode an event, skipping payloads that are not events.
+fn decode(payload: &[u8]) -> Option<Event> {
+    serde_json::from_slice(payload)
+        .map_err(|e| tracing::warn!(error = %e, "Failed to deserialize event"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use platform_rbac::App;
+    use std::process::{Child, Command, Stdio};
+
+    #[test]
+    fn test_pattern_subjects() {
+        let subjects = |pattern| pattern_subjects("relay", pattern);
+        assert_eq!(
+            subjects("verity.document.created"),
+            vec!["relay.verity.document.created"]
+        );
+        assert_eq!(subjects("verity.*.created"), vec!["relay.verity.*.created"]);
+        assert_eq!(subjects("verity.#"), vec!["relay.verity", "relay.verity.>"]);
+        assert_eq!(
+            subjects("*.document.#"),
+            vec!["relay.*.document", "relay.*.document.>"]
+        );
+        assert_eq!(subjects("#"), vec!["relay.>"]);
+        assert_eq!(subjects("verity.#.created"), vec!["relay.verity.>"]);
+    }
+
+    /// A `nats-server` with JetStream on a free port, stopped on drop.
+    ///
+    /// Tests that need it are ignored by default; run them with
+    /// `cargo test -p platform-events --features nats -- --ignored`.
+    struct NatsServer {
+        process: Child,
+        url: String,
+        _store: std::path::PathBuf,
+    }
+
+    impl NatsServer {
+        /// Start `nats-server` (or `NATS_SERVER_BIN`).
+        fn start() -> Self {
+            let binary = std::env::var("NATS_SERVER_BIN").unwrap_or_else(|_| "nats-server".into());
+            let port = std::net::TcpListener::bind("127.0.0.1:0")
+                .and_then(|listener| listener.local_addr())
+                .expect("no free port")
+                .port();
+            let store = std::env::temp_dir().join(format!("nats-{}", uuid::Uuid::now_v7()));
+            let process = Command::new(binary)
+                .args(["-js", "-a", "127.0.0.1", "-p", &port.to_string(), "-sd"])
+                .arg(&store)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .unwrap_or_else(|e| panic!("cannot start nats-server: {}", e));
+            Self {
+                process,
+                url: format!("nats://127.0.0.1:{}", port),
+                _store: store,
+            }
+        }
+
+        async fn bus(&self) -> NatsEventBus {
+            for _ in 0..50 {
+                let config = NatsEventBusConfig {
+                    url: self.url.clone(),
+                    ..Default::default()
+                };
+                if let Ok(bus) = NatsEventBus::new(config).await {
+                    return bus;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            panic!("nats-server did not start");
+        }
+    }
+
+    impl Drop for NatsServer {
+        fn drop(&mut self) {
+            let _ = self.process.kill();
+            let _ = std::fs::remove_dir_all(&self._store);
+        }
+    }
+
+    struct Recorder {
+        events: tokio::sync::mpsc::UnboundedSender<Event>,
+    }
+
+    #[async_trait]
+    impl EventHandler for Recorder {
+        async fn handle(&self, event: Event) -> EventBusResult<()> {
+            let _ = self.events.send(event);
+            Ok(())
+        }
+
+        fn topics(&self) -> Vec<String> {
+            vec!["noteman.meeting.#".to_string()]
+        }
+    }
+
+    fn within<T>(
+        future: impl std::future::Future<Output = T>,
+    ) -> tokio::time::Timeout<impl std::future::Future<Output = T>> {
+        tokio::time::timeout(Duration::from_secs(5), future)
+    }
+
+    #[tokio::test]
+    #[ignore = "requires nats-server"]
+    async fn test_nats_conformance() {
+        let server = NatsServer::start();
+        crate::conformance::run(server.bus().await).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires nats-server"]
+    async fn test_nats_publish_subscribe_and_stats() {
+        let server = NatsServer::start();
+        let bus = server.bus().await;
+
+        let mut sub = bus.subscribe("*.document.#").await.unwrap();
+        bus.publish(Event::new(
+            "meeting.ended",
+            App::NoteMan,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+        let event = Event::new("document.created", App::Verity, serde_json::json!({}));
+        bus.publish(event.clone()).await.unwrap();
+
+        let received = within(sub.recv()).await.unwrap().unwrap();
+        assert_eq!(received.id, event.id);
+
+        let stats = bus.stats().await;
+        assert_eq!(stats.events_published, 2);
+        assert_eq!(stats.events_delivered, 1);
+        assert_eq!(stats.active_subscriptions, 1);
+
+        bus.unsubscribe(&sub.id).await.unwrap();
+        assert_eq!(bus.stats().await.active_subscriptions, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires nats-server"]
+    async fn test_nats_durable_handler_resumes_and_replays() {
+        let server = NatsServer::start();
+        let (sender, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let handler = Arc::new(Recorder { events: sender });
+
+        // Create the durable consumer, then stop it.
+        let first = server.bus().await;
+        first
+            .register_durable_handler("meeting-indexer", handler.clone())
+            .await
+            .unwrap();
+        drop(first);
+
+        // Events published while the handler is not running are kept...
+        let publisher = server.bus().await;
+        let event = Event::new("meeting.ended", App::NoteMan, serde_json::json!({}));
+        publisher.publish(event.clone()).await.unwrap();
+        // ...and stored once, however often the publish is retried.
+        publisher.publish(event.clone()).await.unwrap();
+
+        // ...and delivered when it comes back.
+        let second = server.bus().await;
+        second
+            .register_durable_handler("meeting-indexer", handler)
+            .await
+            .unwrap();
+        let resumed = within(received.recv()).await.unwrap().unwrap();
+        assert_eq!(resumed.id, event.id);
+        assert_eq!(second.stats().await.registered_handlers, 1);
+
+        let replayed = publisher
+            .replay_events("noteman.#", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].id, event.id);
+    }
+}
@@ -1,333 +1,974 @@
 //! Redis-backed event bus for distributed deployments.
 //!
-//! This module provides a Redis-based implementation of the event bus,
-//! enabling multiple application instances to communicate across servers.
+//! Events travel through one of two transports, chosen by
+//! [`RedisEventBusConfig::use_streams`]:
+//!
+//! - **Streams** (default): events are appended to a single stream
+//!   (`{prefix}:events`, trimmed to `stream_max_len`). Listeners read it with
+//!   `XREAD BLOCK` and resume after the last entry they saw when they
+//!   reconnect, so no events are missed during a reconnect. Stored events
+//!   can be replayed with [`RedisEventBus::replay_events`].
+//! - **Pub/Sub**: events are published on `{prefix}:pubsub:{topic}` and are
+//!   only delivered to listeners connected at that time.
+//!
+//! Each bus runs one listener, started by the first subscription or handler.
+//! The listener delivers every event to the subscriptions and handlers whose
+//! topic pattern matches (see [`topic_matches`]), so delivery follows the same
+//! rules as the other backends. When the connection fails, the listener
+//! reconnects with exponential backoff.
+//!
+//! Delivery to subscriptions and handlers is at most once. For at-least-once
+//! delivery, register a durable handler
+//! ([`RedisEventBus::register_durable_handler`]): it reads the stream through
+//! a consumer group, acknowledges events it handled and takes over the events
+//! of crashed consumers.
+//!
+//! Failing handlers are retried with their [`RetryPolicy`]; events that still
+//! fail go to the dead-letter queue, by default a [`RedisDeadLetterStore`]
+//! shared by all buses with the same key prefix.
 
 use crate::bus::{
-    EventBus, EventBusError, EventBusResult, EventBusStats, EventHandler, Subscription,
+    topic_matches, Delivery, EventBus, EventBusError, EventBusResult, EventBusStats, EventHandler,
+    Subscription,
 };
+use crate::dlq::{DeadLetter, DeadLetterQueue, DeadLetterStore, Retrier, RetryPolicy};
 use crate::types::Event;
 use async_trait::async_trait;
-use redis::aio::MultiplexedConnection;
-use redis::{AsyncCommands, Client, RedisError};
+use futures::StreamExt;
+use redis::aio::{ConnectionManager, MultiplexedConnection, PubSub};
+use redis::streams::{
+    StreamId, StreamPendingCountReply, StreamRangeReply, StreamReadOptions, StreamReadReply,
+};
+use redis::{AsyncCommands, Client};
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
-/// Redis-backed event bus for distributed applications.
+/// How long a blocking read waits before checking whether the bus stopped.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum number of stream entries read at once.
+const READ_BATCH: usize = 100;
+
+/// Redis event bus configuration.
+#[derive(Debug, Clone)]
+pub struct RedisEventBusConfig {
+    /// Redis connection URL (e.g., redis://localhost:6379).
+    pub url: String,
+
+    /// Prefix for all Redis keys (default: "platform_events").
+    pub key_prefix: String,
+
+    /// Use Redis Streams for persistence (default: true).
+    pub use_streams: bool,
+
+    /// Maximum stream length (MAXLEN, default: 10000).
+    pub stream_max_len: usize,
+
+    /// Prefix of the consumer groups of durable handlers
+    /// (default: "platform_consumers").
+    pub consumer_group: String,
+
+    /// Consumer name within the group (default: hostname or random).
+    ///
+    /// Must differ between replicas sharing a group.
+    pub consumer_name: String,
+
+    /// How long a durable handler's event may stay unacknowledged before it
+    /// is delivered again, to this or another consumer (default: 60s).
+    pub claim_idle: Duration,
+
+    /// Capacity of each subscription's channel (default: 1024).
+    pub channel_capacity: usize,
+
+    /// Delay before the first reconnect attempt (default: 100ms).
+    pub reconnect_delay: Duration,
+
+    /// Maximum delay between reconnect attempts (default: 30s).
+    pub max_reconnect_delay: Duration,
+}
+
+impl Default for RedisEventBusConfig {
+    fn default() -> Self {
+        Self {
+            url: std::env::var("REDIS_URL")
+                .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+            key_prefix: "platform_events".to_string(),
+            use_streams: true,
+            stream_max_len: 10000,
+            consumer_group: "platform_consumers".to_string(),
+            consumer_name: hostname::get()
+                .ok()
+                .and_then(|h| h.into_string().ok())
+                .unwrap_or_else(|| uuid::Uuid::now_v7().to_string()),
+            claim_idle: Duration::from_secs(60),
+            channel_capacity: 1024,
+            reconnect_delay: Duration::from_millis(100),
+            max_reconnect_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A handler and the task delivering its events.
+type HandlerTask = (Arc<dyn EventHandler>, JoinHandle<()>);
+
+/// State shared between the bus and its listener.
+struct Shared {
+    /// Redis client, used to open listener connections.
+    client: Client,
+
+    /// Configuration.
+    config: RedisEventBusConfig,
+
+    /// Active subscriptions: ID to topic pattern and channel.
+    subscriptions: Mutex<HashMap<String, (String, broadcast::Sender<Event>)>>,
+
+    /// Registered handlers.
+    handlers: Mutex<Vec<Arc<dyn EventHandler>>>,
+
+    /// Handler retry policy and dead-letter store.
+    retrier: Mutex<Retrier>,
+
+    /// Statistics.
+    events_published: AtomicU64,
+    events_delivered: AtomicU64,
+
+    /// Running flag for the listener.
+    running: AtomicBool,
+}
+
+impl Shared {
+    /// Get the stream key.
+    fn stream_key(&self) -> String {
+        format!("{}:events", self.config.key_prefix)
+    }
+
+    /// Get the pubsub channel for a topic.
+    fn channel_key(&self, topic: &str) -> String {
+        format!(
+            "{}:pubsub:{}",
+            self.config.key_prefix,
+            topic.replace('.', ":")
+        )
+    }
+
+    /// Deliver an event to the matching subscriptions and handlers.
+    fn dispatch(&self, event: Event) {
+        let topic = event.topic();
+
+        // Subscriptions whose handle was dropped are removed.
+        lock(&self.subscriptions).retain(|_, (pattern, sender)| {
+            if !topic_matches(pattern, &topic) {
+                return sender.receiver_count() > 0;
+            }
+            let sent = sender.send(event.clone()).is_ok();
+            if sent {
+                self.events_delivered.fetch_add(1, Ordering::Relaxed);
+            }
+            sent
+        });
+
+        let handlers = lock(&self.handlers).clone();
+        for handler in handlers {
+            if handler.topics().iter().any(|t| topic_matches(t, &topic)) {
+                self.events_delivered.fetch_add(1, Ordering::Relaxed);
+                let event = event.clone();
+                let retrier = lock(&self.retrier).clone();
+                tokio::spawn(async move {
+                    let _ = retrier.deliver(handler.as_ref(), event).await;
+                });
+            }
+        }
+    }
+}
+
+/// A listener connection.
+enum Feed {
+    /// Reading the stream after the given entry ID.
+    Stream(MultiplexedConnection, String),
+    /// Subscribed to all pub/sub channels of the bus.
+    PubSub(PubSub),
+}
+
+/// Redis-backed event bus implementation.
 ///
-/// Uses Redis pub/sub for cross-process event distribution.
-/// Maintains a local broadcast channel for efficient in-process delivery.
+/// Features:
+/// - Streams for event persistence and replay, or Pub/Sub for fire-and-forget
+///   delivery
+/// - Topic pattern filtering for subscriptions and handlers
+/// - Automatic reconnection
 ///
 /// # Example
 ///
 /// ```rust,no_run
-/// use platform_events::RedisEventBus;
+/// use platform_events::{EventBus, RedisEventBus, RedisEventBusConfig};
 ///
 /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
-///     let bus = RedisEventBus::new("redis://localhost:6379", "relay").await?;
-///     bus.start_listener().await?;
+///     let bus = RedisEventBus::new(RedisEventBusConfig::default()).await?;
+///     let mut sub = bus.subscribe("verity.document.*").await?;
+///     let event = sub.recv().await?;
+///     println!("Received: {}", event.event_type);
 ///     Ok(())
 /// }
 /// ```
 pub struct RedisEventBus {
-    /// Redis client
-    client: Client,
-
-    /// Key prefix for all Redis operations
-    prefix: String,
+    /// Redis connection manager for commands.
+    conn: ConnectionManager,
 
-    /// Local broadcast channel for efficient in-process delivery
-    local_bus: broadcast::Sender<Event>,
+    /// State shared with the listener.
+    shared: Arc<Shared>,
 
-    /// Background listener task handle
-    listener_handle: Arc<RwLock<Option<JoinHandle<()>>>>,
+    /// Listener task, once started.
+    listener: tokio::sync::Mutex<Option<JoinHandle<()>>>,
 
-    /// Event statistics
-    stats: Arc<RwLock<EventBusStats>>,
+    /// Durable handlers and their tasks.
+    durable: Mutex<Vec<HandlerTask>>,
+}
 
-    /// Registered event handlers
-    handlers: Arc<RwLock<HashMap<String, Vec<Arc<dyn EventHandler>>>>>,
+impl std::fmt::Debug for RedisEventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisEventBus")
+            .field("config", &self.shared.config)
+            .field("running", &self.shared.running.load(Ordering::Relaxed))
+            .finish()
+    }
 }
 
 impl RedisEventBus {
     /// Create a new Redis event bus.
-    ///
-    /// # Arguments
-    ///
-    /// * `redis_url` - Redis connection URL (e.g., `redis://localhost:6379`)
-    /// * `prefix` - Key prefix for Redis operations (e.g., `relay`)
-    ///
-    /// # Returns
-    ///
-    /// A new `RedisEventBus` instance or an error
-    ///
-    /// # Example
-    ///
-    /// ```rust,no_run
-    /// use platform_events::RedisEventBus;
-    ///
-    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let bus = RedisEventBus::new("redis://localhost:6379", "relay").await?;
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn new(redis_url: &str, prefix: &str) -> EventBusResult<Self> {
-        let client =
-            Client::open(redis_url).map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
-
-        // Test connection
-        let _ = client
-            .get_multiplexed_async_connection()
+    pub async fn new(config: RedisEventBusConfig) -> EventBusResult<Self> {
+        let client = Client::open(config.url.as_str())
+            .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+
+        let conn = ConnectionManager::new(client.clone())
             .await
             .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
 
-        let (tx, _) = broadcast::channel(1000);
+        let retrier = Retrier {
+            policy: RetryPolicy::default(),
+            store: Arc::new(RedisDeadLetterStore::new(
+                conn.clone(),
+                format!("{}:dead_letters", config.key_prefix),
+            )),
+        };
 
         Ok(Self {
-            client,
-            prefix: prefix.to_string(),
-            local_bus: tx,
-            listener_handle: Arc::new(RwLock::new(None)),
-            stats: Arc::new(RwLock::new(EventBusStats::default())),
-            handlers: Arc::new(RwLock::new(HashMap::new())),
+            conn,
+            shared: Arc::new(Shared {
+                client,
+                config,
+                subscriptions: Mutex::new(HashMap::new()),
+                handlers: Mutex::new(Vec::new()),
+                retrier: Mutex::new(retrier),
+                events_published: AtomicU64::new(0),
+                events_delivered: AtomicU64::new(0),
+                running: AtomicBool::new(true),
+            }),
+            listener: tokio::sync::Mutex::new(None),
+            durable: Mutex::new(Vec::new()),
         })
     }
 
-    /// Start the Redis subscription listener.
+    /// Create with default configuration.
+    pub async fn with_defaults() -> EventBusResult<Self> {
+        Self::new(RedisEventBusConfig::default()).await
+    }
+
+    /// Set the retry policy of handlers without their own
+    /// (default: [`RetryPolicy::default`]).
     ///
-    /// This spawns a background task that listens for events from Redis
-    /// and broadcasts them to local subscribers.
+    /// Durable handlers are retried by redelivery, so only the policy's
+    /// `max_attempts` applies to them; `claim_idle` is their backoff.
+    pub fn with_retry_policy(self, policy: RetryPolicy) -> Self {
+        lock(&self.shared.retrier).policy = policy;
+        self
+    }
+
+    /// Set where events that handlers failed to process go
+    /// (default: a [`RedisDeadLetterStore`] at `{key_prefix}:dead_letters`).
+    pub fn with_dead_letter_store(self, store: Arc<dyn DeadLetterStore>) -> Self {
+        lock(&self.shared.retrier).store = store;
+        self
+    }
+
+    /// Start the listener if it is not running yet.
     ///
-    /// # Returns
+    /// The first connection is made before returning, so that events
+    /// published afterwards are delivered.
+    async fn ensure_listener(&self) -> EventBusResult<()> {
+        let mut listener = self.listener.lock().await;
+        if listener.is_some() {
+            return Ok(());
+        }
+
+        let start = if self.shared.config.use_streams {
+            Some(self.last_stream_id().await?)
+        } else {
+            None
+        };
+        let feed = connect(&self.shared, start).await?;
+
+        let shared = self.shared.clone();
+        *listener = Some(tokio::spawn(listen(shared, feed)));
+        tracing::info!(key_prefix = %self.shared.config.key_prefix, "Redis event bus listener started");
+
+        Ok(())
+    }
+
+    /// Get the ID of the newest stream entry, or "0-0" if there is none.
+    async fn last_stream_id(&self) -> EventBusResult<String> {
+        let mut conn = self.conn.clone();
+        let newest: StreamRangeReply = conn
+            .xrevrange_count(self.shared.stream_key(), "+", "-", 1)
+            .await
+            .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+        Ok(newest
+            .ids
+            .into_iter()
+            .next()
+            .map(|entry| entry.id)
+            .unwrap_or_else(|| "0-0".to_string()))
+    }
+
+    /// Register a handler that receives every matching event at least once.
     ///
-    /// Ok if the listener started successfully, or an error
+    /// The handler reads the stream through the consumer group
+    /// `{consumer_group}:{name}` as consumer `consumer_name`. Replicas that
+    /// register a handler under the same name share the group, so each event
+    /// goes to one of them. The group is created at the end of the stream;
+    /// from then on, events published while no replica runs are delivered
+    /// when one starts again.
     ///
-    /// # Example
+    /// An event is acknowledged (`XACK`) when the handler returns `Ok`.
+    /// Otherwise it stays pending, and is claimed (`XAUTOCLAIM`) and delivered
+    /// again once it has been pending for `claim_idle`. This also hands the
+    /// events of a crashed consumer to the remaining ones. The handler gets the
+    /// delivery attempt through [`EventHandler::handle_delivery`].
     ///
-    /// ```rust,no_run
-    /// use platform_events::RedisEventBus;
+    /// Requires `use_streams`.
+    pub async fn register_durable_handler(
+        &self,
+        name: &str,
+        handler: Arc<dyn EventHandler>,
+    ) -> EventBusResult<()> {
+        if !self.shared.config.use_streams {
+            return Err(EventBusError::SubscribeError(
+                "durable handlers require Redis Streams".to_string(),
+            ));
+        }
+
+        let group = format!("{}:{}", self.shared.config.consumer_group, name);
+        let mut conn = self.conn.clone();
+        let created: redis::RedisResult<()> = conn
+            .xgroup_create_mkstream(self.shared.stream_key(), &group, "$")
+            .await;
+        if let Err(e) = created {
+            if e.code() != Some("BUSYGROUP") {
+                return Err(EventBusError::SubscribeError(e.to_string()));
+            }
+        }
+
+        let consumer = DurableConsumer {
+            shared: self.shared.clone(),
+            group,
+            handler: handler.clone(),
+        };
+        lock(&self.durable).push((handler, tokio::spawn(consumer.run())));
+        Ok(())
+    }
+
+    /// Replay stored events matching a topic pattern, oldest first.
     ///
-    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let bus = RedisEventBus::new("redis://localhost:6379", "relay").await?;
-    ///     bus.start_listener().await?;
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn start_listener(&self) -> EventBusResult<()> {
-        let mut handle_lock = self.listener_handle.write().await;
-
-        // Don't start if already running
-        if handle_lock.is_some() {
-            return Ok(());
+    /// # Arguments
+    ///
+    /// * `topic` - Topic pattern to replay
+    /// * `since` - Start from this stream ID (e.g., "0" for all, or a specific ID)
+    /// * `count` - Maximum number of events to replay
+    ///
+    /// # Returns
+    ///
+    /// Vector of replayed events
+    pub async fn replay_events(
+        &self,
+        topic: &str,
+        since: &str,
+        count: usize,
+    ) -> EventBusResult<Vec<Event>> {
+        if !self.shared.config.use_streams {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.conn.clone();
+        let mut start = since.to_string();
+        let mut events = Vec::new();
+
+        while events.len() < count {
+            let page: StreamRangeReply = conn
+                .xrange_count(self.shared.stream_key(), &start, "+", READ_BATCH)
+                .await
+                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+            let Some(last) = page.ids.last() else {
+                break;
+            };
+            start = format!("({}", last.id);
+
+            events.extend(
+                page.ids
+                    .iter()
+                    .filter_map(decode_entry)
+                    .filter(|event| topic_matches(topic, &event.topic())),
+            );
         }
 
-        let client = self.client.clone();
-        let prefix = self.prefix.clone();
-        let tx = self.local_bus.clone();
-        let stats = self.stats.clone();
-        let handlers = self.handlers.clone();
+        events.truncate(count);
+        Ok(events)
+    }
 
-        let handle = tokio::spawn(async move {
-            if let Err(e) = redis_listener_loop(client, prefix, tx, stats, handlers).await {
-                tracing::error!(error = %e, "Redis listener loop failed");
+    /// Shutdown the event bus.
+    pub fn shutdown(&self) {
+        self.shared.running.store(false, Ordering::Relaxed);
+        if let Ok(mut listener) = self.listener.try_lock() {
+            if let Some(handle) = listener.take() {
+                handle.abort();
             }
-        });
+        }
+        for (_, task) in lock(&self.durable).drain(..) {
+            task.abort();
+        }
+    }
+}
 
-        *handle_lock = Some(handle);
+#[async_trait]
+impl EventBus for RedisEventBus {
+    async fn publish(&self, event: Event) -> EventBusResult<()> {
+        let event_json = serde_json::to_string(&event)
+            .map_err(|e| EventBusError::SerializationError(e.to_string()))?;
+        let mut conn = self.conn.clone();
+
+        if self.shared.config.use_streams {
+            // Add to stream with MAXLEN for automatic trimming
+            let _: String = redis::cmd("XADD")
+                .arg(self.shared.stream_key())
+                .arg("MAXLEN")
+                .arg("~")
+                .arg(self.shared.config.stream_max_len)
+                .arg("*")
+                .arg("event")
+                .arg(&event_json)
+                .arg("topic")
+                .arg(event.topic())
+                .arg("timestamp")
+                .arg(event.timestamp.to_rfc3339())
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| EventBusError::PublishError(e.to_string()))?;
+        } else {
+            let _: i64 = conn
+                .publish(self.shared.channel_key(&event.topic()), &event_json)
+                .await
+                .map_err(|e| EventBusError::PublishError(e.to_string()))?;
+        }
+
+        self.shared.events_published.fetch_add(1, Ordering::Relaxed);
+
+        tracing::debug!(
+            topic = %event.topic(),
+            event_id = %event.id,
+            "Event published to Redis"
+        );
 
         Ok(())
     }
 
-    /// Stop the Redis subscription listener.
-    pub async fn stop_listener(&self) {
-        let mut handle_lock = self.listener_handle.write().await;
+    async fn subscribe(&self, topic: &str) -> EventBusResult<Subscription> {
+        self.ensure_listener().await?;
+
+        let id = uuid::Uuid::now_v7().to_string();
+        let (sender, receiver) = broadcast::channel(self.shared.config.channel_capacity);
+        lock(&self.shared.subscriptions).insert(id.clone(), (topic.to_string(), sender));
 
-        if let Some(handle) = handle_lock.take() {
-            handle.abort();
+        Ok(Subscription::new(id, topic.to_string(), receiver))
+    }
+
+    async fn register_handler(&self, handler: Arc<dyn EventHandler>) -> EventBusResult<()> {
+        self.ensure_listener().await?;
+        lock(&self.shared.handlers).push(handler);
+        Ok(())
+    }
+
+    async fn unsubscribe(&self, subscription_id: &str) -> EventBusResult<()> {
+        lock(&self.shared.subscriptions).remove(subscription_id);
+        Ok(())
+    }
+
+    async fn stats(&self) -> EventBusStats {
+        EventBusStats {
+            events_published: self.shared.events_published.load(Ordering::Relaxed),
+            events_delivered: self.shared.events_delivered.load(Ordering::Relaxed),
+            active_subscriptions: {
+                let mut subscriptions = lock(&self.shared.subscriptions);
+                subscriptions.retain(|_, (_, sender)| sender.receiver_count() > 0);
+                subscriptions.len()
+            },
+            registered_handlers: lock(&self.shared.handlers).len() + lock(&self.durable).len(),
         }
     }
+}
 
-    /// Get a Redis connection.
-    async fn get_connection(&self) -> EventBusResult<MultiplexedConnection> {
-        self.client
-            .get_multiplexed_async_connection()
-            .await
-            .map_err(|e| EventBusError::ConnectionError(e.to_string()))
+#[async_trait]
+impl DeadLetterQueue for RedisEventBus {
+    async fn dead_letters(&self) -> EventBusResult<Vec<DeadLetter>> {
+        let store = lock(&self.shared.retrier).store.clone();
+        store.list().await
+    }
+
+    async fn dead_letter(&self, id: uuid::Uuid) -> EventBusResult<Option<DeadLetter>> {
+        let store = lock(&self.shared.retrier).store.clone();
+        store.get(id).await
+    }
+
+    async fn redrive_dead_letter(&self, id: uuid::Uuid) -> EventBusResult<()> {
+        let mut handlers = lock(&self.shared.handlers).clone();
+        handlers.extend(lock(&self.durable).iter().map(|(h, _)| h.clone()));
+        let retrier = lock(&self.shared.retrier).clone();
+        retrier.redrive(id, &handlers).await
+    }
+
+    async fn purge_dead_letters(&self) -> EventBusResult<usize> {
+        let store = lock(&self.shared.retrier).store.clone();
+        store.purge().await
     }
 }
 
-/// Redis listener loop that receives events and broadcasts them locally.
-async fn redis_listener_loop(
-    client: Client,
-    prefix: String,
-    tx: broadcast::Sender<Event>,
-    stats: Arc<RwLock<EventBusStats>>,
-    handlers: Arc<RwLock<HashMap<String, Vec<Arc<dyn EventHandler>>>>>,
-) -> EventBusResult<()> {
-    use redis::aio::PubSub;
-
-    let mut conn = client
-        .get_async_pubsub()
-        .await
-        .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+impl Drop for RedisEventBus {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
 
-    // Subscribe to all events with the prefix
-    let pattern = format!("{}:*", prefix);
-    conn.psubscribe(&pattern)
-        .await
-        .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
+/// Open a listener connection.
+///
+/// With streams, `after` is the ID of the last entry already seen.
+async fn connect(shared: &Shared, after: Option<String>) -> EventBusResult<Feed> {
+    match after {
+        Some(after) => {
+            // A dedicated connection, since blocking reads hold it up.
+            let conn = shared
+                .client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
+            Ok(Feed::Stream(conn, after))
+        }
+        None => {
+            let mut pubsub = shared
+                .client
+                .get_async_connection()
+                .await
+                .map_err(|e| EventBusError::ConnectionError(e.to_string()))?
+                .into_pubsub();
+            pubsub
+                .psubscribe(format!("{}:pubsub:*", shared.config.key_prefix))
+                .await
+                .map_err(|e| EventBusError::SubscribeError(e.to_string()))?;
+            Ok(Feed::PubSub(pubsub))
+        }
+    }
+}
 
-    tracing::info!(pattern = %pattern, "Redis event bus listener started");
+/// Listener loop: deliver events until the bus stops, reconnecting with
+/// exponential backoff when the connection fails.
+async fn listen(shared: Arc<Shared>, mut feed: Feed) {
+    let mut delay = shared.config.reconnect_delay;
+    let mut last_id = match feed {
+        Feed::Stream(_, ref id) => Some(id.clone()),
+        Feed::PubSub(_) => None,
+    };
 
     loop {
-        match conn.on_message().next().await {
-            Some(msg) => {
-                let payload: String = match msg.get_payload() {
-                    Ok(p) => p,
-                    Err(e) => {
-                        tracing::warn!(error = %e, "Failed to get message payload");
-                        continue;
-                    }
-                };
-
-                let event: Event = match serde_json::from_str(&payload) {
-                    Ok(e) => e,
-                    Err(e) => {
-                        tracing::warn!(error = %e, payload = %payload, "Failed to deserialize event");
-                        continue;
-                    }
-                };
+        let result = consume(&shared, feed, &mut last_id).await;
+        if !shared.running.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Err(e) = result {
+            tracing::warn!(error = %e, retry_in = ?delay, "Redis listener disconnected");
+        }
 
-                // Update stats
-                {
-                    let mut stats = stats.write().await;
-                    stats.events_received += 1;
+        // Reconnect, resuming after the last stream entry seen.
+        feed = loop {
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(shared.config.max_reconnect_delay);
+            if !shared.running.load(Ordering::Relaxed) {
+                return;
+            }
+            match connect(&shared, last_id.clone()).await {
+                Ok(feed) => break feed,
+                Err(e) => {
+                    tracing::warn!(error = %e, retry_in = ?delay, "Redis listener reconnect failed")
                 }
+            }
+        };
+        delay = shared.config.reconnect_delay;
+        tracing::info!("Redis listener reconnected");
+    }
+}
+
+/// Deliver events from a listener connection until the bus stops or the
+/// connection fails.
+async fn consume(shared: &Shared, feed: Feed, last_id: &mut Option<String>) -> EventBusResult<()> {
+    match feed {
+        Feed::Stream(mut conn, _) => {
+            let key = shared.stream_key();
+            let options = StreamReadOptions::default()
+                .block(POLL_INTERVAL.as_millis() as usize)
+                .count(READ_BATCH);
+
+            while shared.running.load(Ordering::Relaxed) {
+                let after = last_id.clone().unwrap_or_else(|| "0-0".to_string());
+                let reply: Option<StreamReadReply> = conn
+                    .xread_options(&[&key], &[&after], &options)
+                    .await
+                    .map_err(|e| EventBusError::ConnectionError(e.to_string()))?;
 
-                // Broadcast to local subscribers
-                if let Err(e) = tx.send(event.clone()) {
-                    tracing::warn!(error = %e, "Failed to broadcast event locally");
+                for entry in entries(reply) {
+                    *last_id = Some(entry.id.clone());
+                    if let Some(event) = decode_entry(&entry) {
+                        shared.dispatch(event);
+                    }
                 }
+            }
+            Ok(())
+        }
+        Feed::PubSub(mut pubsub) => {
+            let mut messages = pubsub.on_message();
 
-                // Invoke registered handlers
-                let handlers = handlers.read().await;
-                for handler_list in handlers.values() {
-                    for handler in handler_list {
-                        let handler = handler.clone();
-                        let event = event.clone();
-                        tokio::spawn(async move {
-                            if let Err(e) = handler.handle(event).await {
-                                tracing::warn!(error = %e, "Event handler failed");
+            while shared.running.load(Ordering::Relaxed) {
+                match tokio::time::timeout(POLL_INTERVAL, messages.next()).await {
+                    Ok(Some(msg)) => {
+                        let payload: String = match msg.get_payload() {
+                            Ok(p) => p,
+                            Err(e) => {
+                                tracing::warn!(error = %e, "Failed to get message payload");
+                                continue;
                             }
-                        });
+                        };
+                        if let Some(event) = decode(&payload) {
+                            shared.dispatch(event);
+                        }
+                    }
+                    Ok(None) => {
+                        return Err(EventBusError::ConnectionError(
+                            "pub/sub connection closed".to_string(),
+                        ))
                     }
+                    Err(_) => continue, // Timeout, check running flag
                 }
             }
-            None => {
-                tracing::warn!("Redis pub/sub stream ended");
-                break;
-            }
+            Ok(())
         }
     }
+}
 
-    Ok(())
+/// A durable handler reading the stream through a consumer group.
+struct DurableConsumer {
+    shared: Arc<Shared>,
+    group: String,
+    handler: Arc<dyn EventHandler>,
 }
 
-#[async_trait]
-impl EventBus for RedisEventBus {
-    /// Publish an event to Redis.
-    ///
-    /// The event is serialized to JSON and published to a Redis channel
-    /// based on its topic.
-    async fn publish(&self, event: Event) -> EventBusResult<()> {
-        let mut conn = self.get_connection().await?;
+impl DurableConsumer {
+    /// Deliver events until the bus stops, reconnecting with exponential
+    /// backoff when the connection fails.
+    async fn run(self) {
+        let mut delay = self.shared.config.reconnect_delay;
+        // First deliver the events this consumer read before a restart
+        // without acknowledging them.
+        let mut own_pending = true;
 
-        let channel = format!("{}:{}", self.prefix, event.topic);
-        let payload = serde_json::to_string(&event)
-            .map_err(|e| EventBusError::SerializationError(e.to_string()))?;
+        while self.shared.running.load(Ordering::Relaxed) {
+            match self.consume(&mut own_pending, &mut delay).await {
+                Ok(()) => return,
+                Err(e) => {
+                    tracing::warn!(group = %self.group, error = %e, retry_in = ?delay, "Durable consumer disconnected")
+                }
+            }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(self.shared.config.max_reconnect_delay);
+        }
+    }
 
-        conn.publish::<_, _, ()>(&channel, &payload)
+    /// Deliver events until the bus stops or the connection fails.
+    async fn consume(&self, own_pending: &mut bool, delay: &mut Duration) -> EventBusResult<()> {
+        let mut conn = self
+            .shared
+            .client
+            .get_multiplexed_async_connection()
             .await
-            .map_err(|e| EventBusError::PublishError(e.to_string()))?;
+            .map_err(connection_error)?;
+        *delay = self.shared.config.reconnect_delay;
 
-        // Update stats
-        {
-            let mut stats = self.stats.write().await;
-            stats.events_published += 1;
-        }
+        let key = self.shared.stream_key();
+        let options = || {
+            StreamReadOptions::default()
+                .group(&self.group, &self.shared.config.consumer_name)
+                .count(READ_BATCH)
+        };
+        let mut pending_after = "0-0".to_string();
+        let mut last_claim: Option<Instant> = None;
 
-        tracing::debug!(topic = %event.topic, event_id = %event.id, "Published event to Redis");
+        while self.shared.running.load(Ordering::Relaxed) {
+            if *own_pending {
+                let reply: Option<StreamReadReply> = conn
+                    .xread_options(&[&key], &[&pending_after], &options())
+                    .await
+                    .map_err(connection_error)?;
+                let entries = entries(reply);
+                match entries.last() {
+                    Some(last) => pending_after = last.id.clone(),
+                    None => *own_pending = false,
+                }
+                for entry in entries {
+                    let attempt = self.attempt(&mut conn, &entry.id).await?;
+                    self.deliver(&mut conn, entry, attempt).await?;
+                }
+                continue;
+            }
+
+            if last_claim.is_none_or(|at| at.elapsed() >= self.shared.config.claim_idle) {
+                self.claim(&mut conn).await?;
+                last_claim = Some(Instant::now());
+            }
 
+            let reply: Option<StreamReadReply> = conn
+                .xread_options(
+                    &[&key],
+                    &[">"],
+                    &options().block(POLL_INTERVAL.as_millis() as usize),
+                )
+                .await
+                .map_err(connection_error)?;
+            for entry in entries(reply) {
+                self.deliver(&mut conn, entry, 1).await?;
+            }
+        }
         Ok(())
     }
 
-    /// Subscribe to events matching a topic pattern.
-    ///
-    /// Events are received from the local broadcast channel after being
-    /// routed through Redis.
-    async fn subscribe(&self, topic: &str) -> EventBusResult<Subscription> {
-        let rx = self.local_bus.subscribe();
-        let topic = topic.to_string();
-        let id = uuid::Uuid::now_v7().to_string();
+    /// Claim and deliver the events pending for longer than `claim_idle`.
+    async fn claim(&self, conn: &mut MultiplexedConnection) -> EventBusResult<()> {
+        let mut start = "0-0".to_string();
+        loop {
+            let reply: Vec<redis::Value> = redis::cmd("XAUTOCLAIM")
+                .arg(self.shared.stream_key())
+                .arg(&self.group)
+                .arg(&self.shared.config.consumer_name)
+                .arg(self.shared.config.claim_idle.as_millis() as u64)
+                .arg(&start)
+                .arg("COUNT")
+                .arg(READ_BATCH)
+                .query_async(conn)
+                .await
+                .map_err(connection_error)?;
 
-        // Update stats
-        {
-            let mut stats = self.stats.write().await;
-            stats.active_subscriptions += 1;
+            // Reply: next start ID, claimed entries, and (Redis 7) deleted IDs.
+            let mut reply = reply.iter();
+            let next: String = match reply.next() {
+                Some(value) => redis::from_redis_value(value).map_err(connection_error)?,
+                None => return Ok(()),
+            };
+            let claimed: StreamRangeReply = match reply.next() {
+                Some(value) => redis::from_redis_value(value).map_err(connection_error)?,
+                None => StreamRangeReply::default(),
+            };
+
+            for entry in claimed.ids {
+                let attempt = self.attempt(conn, &entry.id).await?;
+                self.deliver(conn, entry, attempt).await?;
+            }
+
+            if next == "0-0" {
+                return Ok(());
+            }
+            start = next;
         }
+    }
 
-        Ok(Subscription {
-            id,
-            topic,
-            receiver: rx,
-        })
+    /// Get the delivery attempt of a pending entry.
+    async fn attempt(&self, conn: &mut MultiplexedConnection, id: &str) -> EventBusResult<u32> {
+        let pending: StreamPendingCountReply = conn
+            .xpending_count(self.shared.stream_key(), &self.group, id, id, 1)
+            .await
+            .map_err(connection_error)?;
+        Ok(pending
+            .ids
+            .first()
+            .map(|p| p.times_delivered.max(1) as u32)
+            .unwrap_or(1))
     }
 
-    /// Register an event handler.
-    async fn register_handler(&self, handler: Arc<dyn EventHandler>) -> EventBusResult<()> {
-        let mut handlers = self.handlers.write().await;
+    /// Hand an entry to the handler, acknowledging it unless the handler
+    /// fails.
+    async fn deliver(
+        &self,
+        conn: &mut MultiplexedConnection,
+        entry: StreamId,
+        attempt: u32,
+    ) -> EventBusResult<()> {
+        let topics = self.handler.topics();
+        let event = decode_entry(&entry)
+            .filter(|event| topics.iter().any(|t| topic_matches(t, &event.topic())));
 
-        for topic in handler.topics() {
-            handlers
-                .entry(topic.clone())
-                .or_insert_with(Vec::new)
-                .push(handler.clone());
+        // Events not for this handler are acknowledged right away.
+        if let Some(event) = event {
+            match self
+                .handler
+                .handle_delivery(event.clone(), Delivery { attempt })
+                .await
+            {
+                Ok(()) => {
+                    self.shared.events_delivered.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    let retrier = lock(&self.shared.retrier).clone();
+                    if attempt < retrier.policy(self.handler.as_ref()).max_attempts {
+                        tracing::warn!(
+                            group = %self.group,
+                            entry_id = %entry.id,
+                            attempt,
+                            error = %e,
+                            "Handler failed, event will be redelivered"
+                        );
+                        return Ok(());
+                    }
+                    // Out of attempts: dead-letter and acknowledge.
+                    retrier
+                        .dead_letter(self.handler.as_ref(), event, &e, attempt)
+                        .await;
+                }
+            }
         }
 
+        let _: i64 = conn
+            .xack(self.shared.stream_key(), &self.group, &[&entry.id])
+            .await
+            .map_err(connection_error)?;
         Ok(())
     }
+}
 
-    /// Unsubscribe from a topic.
-    async fn unsubscribe(&self, _subscription_id: &str) -> EventBusResult<()> {
-        // Update stats
-        {
-            let mut stats = self.stats.write().await;
-            if stats.active_subscriptions > 0 {
-                stats.active_subscriptions -= 1;
-            }
+/// Dead-letter store in a Redis hash, keyed by dead letter ID.
+pub struct RedisDeadLetterStore {
+    conn: ConnectionManager,
+    key: String,
+}
+
+impl RedisDeadLetterStore {
+    /// Create a store in the hash at `key`.
+    pub fn new(conn: ConnectionManager, key: impl Into<String>) -> Self {
+        Self {
+            conn,
+            key: key.into(),
         }
+    }
+}
 
+#[async_trait]
+impl DeadLetterStore for RedisDeadLetterStore {
+    async fn push(&self, dead_letter: DeadLetter) -> EventBusResult<()> {
+        let json = serde_json::to_string(&dead_letter)
+            .map_err(|e| EventBusError::SerializationError(e.to_string()))?;
+        let mut conn = self.conn.clone();
+        let _: i64 = conn
+            .hset(&self.key, dead_letter.id.to_string(), json)
+            .await
+            .map_err(connection_error)?;
         Ok(())
     }
 
-    /// Get event bus statistics.
-    async fn stats(&self) -> EventBusStats {
-        self.stats.read().await.clone()
+    async fn list(&self) -> EventBusResult<Vec<DeadLetter>> {
+        let mut conn = self.conn.clone();
+        let values: Vec<String> = conn.hvals(&self.key).await.map_err(connection_error)?;
+        let mut dead_letters: Vec<DeadLetter> = values
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect();
+        dead_letters.sort_by_key(|d| d.failed_at);
+        Ok(dead_letters)
     }
-}
 
-impl Drop for RedisEventBus {
-    fn drop(&mut self) {
-        // Note: Can't use async in Drop, so we just log
-        tracing::debug!("RedisEventBus dropped");
+    async fn get(&self, id: uuid::Uuid) -> EventBusResult<Option<DeadLetter>> {
+        let mut conn = self.conn.clone();
+        let json: Option<String> = conn
+            .hget(&self.key, id.to_string())
+            .await
+            .map_err(connection_error)?;
+        json.map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| EventBusError::SerializationError(e.to_string()))
+        })
+        .transpose()
     }
+
+    async fn remove(&self, id: uuid::Uuid) -> EventBusResult<Option<DeadLetter>> {
+        let dead_letter = self.get(id).await?;
+        let mut conn = self.conn.clone();
+        let _: i64 = conn
+            .hdel(&self.key, id.to_string())
+            .await
+            .map_err(connection_error)?;
+        Ok(dead_letter)
+    }
+
+    async fn purge(&self) -> EventBusResult<usize> {
+        let mut conn = self.conn.clone();
+        let (count, _): (usize, i64) = redis::pipe()
+            .atomic()
+            .hlen(&self.key)
+            .del(&self.key)
+            .query_async(&mut conn)
+            .await
+            .map_err(connection_error)?;
+        Ok(count)
+    }
+}
+
+/// Get the entries of a stream read.
+fn entries(reply: Option<StreamReadReply>) -> Vec<StreamId> {
+    reply
+        .into_iter()
+        .flat_map(|r| r.keys)
+        .flat_map(|k| k.ids)
+        .collect()
+}
+
+fn connection_error(e: redis::RedisError) -> EventBusError {
+    EventBusError::ConnectionError(e.to_string())
+}
+
+/// Decode the event of a stream entry.
+fn decode_entry(entry: &StreamId) -> Option<Event> {
+    entry.get::<String>("event").and_then(|json| decode(&json))
+}
+
+/// Decode an event, skipping payloads that are not events.
+fn decode(payload: &str) -> Option<Event> {
+    serde_json::from_str(payload)
+        .map_err(|e| tracing::warn!(error = %e, "Failed to deserialize event"))
+        .ok()
+}
+
+/// Lock a mutex, recovering from poisoning.
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::process::{Child, Command, Stdio};
 
     #[test]
     fn test_redis_url_parsing() {
@@ -337,11 +978,264 @@ mod tests {
         let _ = Client::open("redis+tls://localhost:6380");
     }
 
-    #[test]
-    fn test_channel_format() {
-        let prefix = "relay";
-        let topic = "verity.document.created";
-        let channel = format!("{}:{}", prefix, topic);
-        assert_eq!(channel, "relay:verity.document.created");
+    /// A `redis-server` on a free port, stopped on drop.
+    ///
+    /// Tests that need it are ignored by default; run them with
+    /// `cargo test -p platform-events --features redis -- --ignored`.
+    struct RedisServer {
+        process: Child,
+        url: String,
+    }
+
+    impl RedisServer {
+        /// Start `redis-server` (or `REDIS_SERVER_BIN`).
+        fn start() -> Self {
+            let binary =
+                std::env::var("REDIS_SERVER_BIN").unwrap_or_else(|_| "redis-server".into());
+            let port = std::net::TcpListener::bind("127.0.0.1:0")
+                .and_then(|listener| listener.local_addr())
+                .expect("no free port")
+                .port();
+            let process = Command::new(binary)
+                .args([
+                    "--port",
+                    &port.to_string(),
+                    "--save",
+                    "",
+                    "--appendonly",
+                    "no",
+                ])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .unwrap_or_else(|e| panic!("cannot start redis-server: {}", e));
+            Self {
+                process,
+                url: format!("redis://127.0.0.1:{}", port),
+            }
+        }
+
+        async fn bus(&self, use_streams: bool) -> RedisEventBus {
+            self.bus_with(RedisEventBusConfig {
+                use_streams,
+                ..Default::default()
+            })
+            .await
+        }
+
+        async fn bus_with(&self, config: RedisEventBusConfig) -> RedisEventBus {
+            for _ in 0..50 {
+                let config = RedisEventBusConfig {
+                    url: self.url.clone(),
+                    ..config.clone()
+                };
+                if let Ok(bus) = RedisEventBus::new(config).await {
+                    return bus;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            panic!("redis-server did not start");
+        }
+    }
+
+    impl Drop for RedisServer {
+        fn drop(&mut self) {
+            let _ = self.process.kill();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires redis-server"]
+    async fn test_redis_streams_conformance() {
+        let server = RedisServer::start();
+        crate::conformance::run(server.bus(true).await).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires redis-server"]
+    async fn test_redis_pubsub_conformance() {
+        let server = RedisServer::start();
+        crate::conformance::run(server.bus(false).await).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "requires redis-server"]
+    async fn test_redis_replay_filters_by_topic() {
+        let server = RedisServer::start();
+        let bus = server.bus(true).await;
+        let meeting = Event::new(
+            "meeting.ended",
+            platform_rbac::App::NoteMan,
+            serde_json::json!({}),
+        );
+        bus.publish(Event::new(
+            "document.created",
+            platform_rbac::App::Verity,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+        bus.publish(meeting.clone()).await.unwrap();
+
+        let replayed = bus.replay_events("noteman.#", "0", 10).await.unwrap();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].id, meeting.id);
+    }
+
+    /// Durable handler recording deliveries; fails the first attempt when
+    /// `fail_first` is set.
+    struct Recorder {
+        fail_first: bool,
+        deliveries: tokio::sync::mpsc::UnboundedSender<(Event, Delivery)>,
+    }
+
+    #[async_trait]
+    impl EventHandler for Recorder {
+        async fn handle(&self, event: Event) -> EventBusResult<()> {
+            self.handle_delivery(event, Delivery::first()).await
+        }
+
+        async fn handle_delivery(&self, event: Event, delivery: Delivery) -> EventBusResult<()> {
+            let _ = self.deliveries.send((event, delivery));
+            if self.fail_first && delivery.attempt == 1 {
+                return Err(EventBusError::PublishError("downstream unavailable".into()));
+            }
+            Ok(())
+        }
+
+        fn topics(&self) -> Vec<String> {
+            vec!["noteman.meeting.#".to_string()]
+        }
+    }
+
+    fn replica(name: &str) -> RedisEventBusConfig {
+        RedisEventBusConfig {
+            consumer_name: name.to_string(),
+            claim_idle: Duration::from_millis(200),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires redis-server"]
+    async fn test_redis_durable_handlers_share_a_group() {
+        let server = RedisServer::start();
+        let (sender, mut deliveries) = tokio::sync::mpsc::unbounded_channel();
+        let mut replicas = Vec::new();
+        for name in ["indexer-1", "indexer-2"] {
+            let bus = server.bus_with(replica(name)).await;
+            let handler = Arc::new(Recorder {
+                fail_first: false,
+                deliveries: sender.clone(),
+            });
+            bus.register_durable_handler("indexer", handler)
+                .await
+                .unwrap();
+            replicas.push(bus);
+        }
+
+        let mut published = Vec::new();
+        for _ in 0..10 {
+            let event = Event::new(
+                "meeting.ended",
+                platform_rbac::App::NoteMan,
+                serde_json::json!({}),
+            );
+            replicas[0].publish(event.clone()).await.unwrap();
+            published.push(event.id);
+        }
+
+        // Every event is handled exactly once across the replicas.
+        let mut handled = Vec::new();
+        while handled.len() < published.len() {
+            let (event, delivery) = tokio::time::timeout(Duration::from_secs(5), deliveries.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(delivery.attempt, 1);
+            handled.push(event.id);
+        }
+        handled.sort();
+        published.sort();
+        assert_eq!(handled, published);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires redis-server"]
+    async fn test_redis_durable_handler_redelivers_failed_events() {
+        let server = RedisServer::start();
+        let (sender, mut deliveries) = tokio::sync::mpsc::unbounded_channel();
+        let bus = server.bus_with(replica("indexer-1")).await;
+        let handler = Arc::new(Recorder {
+            fail_first: true,
+            deliveries: sender,
+        });
+        bus.register_durable_handler("indexer", handler)
+            .await
+            .unwrap();
+
+        let event = Event::new(
+            "meeting.ended",
+            platform_rbac::App::NoteMan,
+            serde_json::json!({}),
+        );
+        bus.publish(event.clone()).await.unwrap();
+
+        for attempt in 1..=2 {
+            let (received, delivery) =
+                tokio::time::timeout(Duration::from_secs(5), deliveries.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(received.id, event.id);
+            assert_eq!(delivery.attempt, attempt);
+        }
+        // Counted once the handler returned.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(bus.stats().await.events_delivered, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires redis-server"]
+    async fn test_redis_durable_handler_dead_letters_after_max_attempts() {
+        let server = RedisServer::start();
+        let (sender, mut deliveries) = tokio::sync::mpsc::unbounded_channel();
+        let bus = server
+            .bus_with(replica("indexer-1"))
+            .await
+            .with_retry_policy(RetryPolicy::no_retry());
+        let handler = Arc::new(Recorder {
+            fail_first: true,
+            deliveries: sender,
+        });
+        bus.register_durable_handler("indexer", handler)
+            .await
+            .unwrap();
+
+        let event = Event::new(
+            "meeting.ended",
+            platform_rbac::App::NoteMan,
+            serde_json::json!({}),
+        );
+        bus.publish(event.clone()).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), deliveries.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let dead_letter = loop {
+            if let Some(dead_letter) = bus.dead_letters().await.unwrap().pop() {
+                break dead_letter;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+        assert_eq!(dead_letter.event.id, event.id);
+        assert_eq!(dead_letter.attempts, 1);
+
+        // Redriven to the handler, which now succeeds.
+        bus.redrive_dead_letter(dead_letter.id).await.unwrap();
+        let (_, delivery) = deliveries.recv().await.unwrap();
+        assert_eq!(delivery.attempt, 1);
+        assert!(bus.dead_letters().await.unwrap().is_empty());
     }
 }
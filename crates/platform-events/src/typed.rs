@@ -0,0 +1,357 @@
+//! Typed subscriptions and handlers for domain events.
+//!
+//! Instead of receiving [`Event`]s and decoding their payloads by hand,
+//! consumers subscribe to a [`DomainEvent`] enum and receive
+//! [`TypedEvent`]s: the decoded variant together with the envelope's
+//! context (ID, timestamp, organization, correlation ID, metadata, ...).
+//!
+//! ```rust,no_run
+//! use platform_events::{MemoryEventBus, MeetingEvent, TypedEventBus};
+//!
+//! async fn example() {
+//!     let bus = MemoryEventBus::new();
+//!     let mut meetings = bus.subscribe_typed::<MeetingEvent>().await.unwrap();
+//!
+//!     while let Ok(meeting) = meetings.recv().await {
+//!         if let MeetingEvent::Ended { meeting_id, .. } = meeting.data {
+//!             println!("Meeting {} ended in org {:?}", meeting_id, meeting.org_id);
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::bus::{Delivery, EventBus, EventBusResult, EventHandler, Subscription};
+use crate::dlq::RetryPolicy;
+use crate::types::{DecodeError, DomainEvent, Event};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use platform_rbac::App;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A decoded domain event with its envelope's context.
+#[derive(Debug, Clone)]
+pub struct TypedEvent<E> {
+    /// The decoded domain event
+    pub data: E,
+
+    /// Unique event ID
+    pub id: Uuid,
+
+    /// Source application
+    pub source: App,
+
+    /// Timestamp when event was created
+    pub timestamp: DateTime<Utc>,
+
+    /// Organization context
+    pub org_id: Option<Uuid>,
+
+    /// Project context
+    pub project_id: Option<Uuid>,
+
+    /// User who triggered the event
+    pub user_id: Option<Uuid>,
+
+    /// Correlation ID for tracing
+    pub correlation_id: Option<String>,
+
+    /// Event version
+    pub version: u32,
+
+    /// Additional metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl<E: DomainEvent> TypedEvent<E> {
+    /// Decode an event, keeping its envelope's context.
+    pub fn try_from_event(event: Event) -> Result<Self, DecodeError> {
+        let data = E::try_from_event(&event)?;
+        Ok(Self {
+            data,
+            id: event.id,
+            source: event.source,
+            timestamp: event.timestamp,
+            org_id: event.org_id,
+            project_id: event.project_id,
+            user_id: event.user_id,
+            correlation_id: event.correlation_id,
+            version: event.version,
+            metadata: event.metadata,
+        })
+    }
+
+    /// Convert back to a generic event with the same ID, version and context.
+    pub fn into_event(self) -> Event {
+        let mut event = self.data.to_event();
+        event.id = self.id;
+        event.version = self.version;
+        event.timestamp = self.timestamp;
+        event.org_id = self.org_id;
+        event.project_id = self.project_id;
+        event.user_id = self.user_id;
+        event.correlation_id = self.correlation_id;
+        event.metadata = self.metadata;
+        event
+    }
+}
+
+/// Subscription handle yielding decoded domain events.
+///
+/// Events on the topic that are not of the domain event's types are
+/// skipped. Dropping the handle ends the subscription.
+pub struct TypedSubscription<E> {
+    inner: Subscription,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E: DomainEvent> TypedSubscription<E> {
+    /// Wrap a subscription to [`DomainEvent::TOPIC_PATTERN`].
+    pub fn new(inner: Subscription) -> Self {
+        Self {
+            inner,
+            _event: PhantomData,
+        }
+    }
+
+    /// Get the subscription ID, for [`EventBus::unsubscribe`].
+    pub fn id(&self) -> &str {
+        &self.inner.id
+    }
+
+    /// Receive the next domain event.
+    ///
+    /// Returns [`EventBusError::DecodeError`](crate::EventBusError::DecodeError) for an event of one of the
+    /// domain event's types that fails to decode; call `recv` again to
+    /// continue with the next event. Otherwise fails like
+    /// [`Subscription::recv`].
+    pub async fn recv(&mut self) -> EventBusResult<TypedEvent<E>> {
+        loop {
+            let event = self.inner.recv().await?;
+            if E::accepts(&event) {
+                return Ok(TypedEvent::try_from_event(event)?);
+            }
+        }
+    }
+
+    /// Get the underlying subscription.
+    pub fn into_inner(self) -> Subscription {
+        self.inner
+    }
+}
+
+/// Handler for decoded domain events.
+///
+/// Register it with [`TypedEventBus::register_typed_handler`], or wrap it
+/// in a [`TypedHandler`] wherever an [`EventHandler`] is expected.
+#[async_trait]
+pub trait TypedEventHandler<E: DomainEvent>: Send + Sync {
+    /// Handle a domain event.
+    async fn handle(&self, event: TypedEvent<E>) -> EventBusResult<()>;
+
+    /// Handle a delivery of a domain event; see
+    /// [`EventHandler::handle_delivery`].
+    async fn handle_delivery(
+        &self,
+        event: TypedEvent<E>,
+        delivery: Delivery,
+    ) -> EventBusResult<()> {
+        let _ = delivery;
+        self.handle(event).await
+    }
+
+    /// Get the name of this handler; see [`EventHandler::name`].
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Get the retry policy of this handler; `None` uses the bus's policy.
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
+}
+
+/// [`EventHandler`] decoding events for a [`TypedEventHandler`].
+///
+/// Handles [`DomainEvent::TOPIC_PATTERN`]; events of other types are
+/// acknowledged without calling the handler. Events that fail to decode
+/// fail the delivery, so they end up in the dead-letter queue.
+pub struct TypedHandler<E, H: ?Sized> {
+    handler: Arc<H>,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E, H> TypedHandler<E, H>
+where
+    E: DomainEvent,
+    H: TypedEventHandler<E> + ?Sized,
+{
+    /// Wrap a typed handler.
+    pub fn new(handler: Arc<H>) -> Self {
+        Self {
+            handler,
+            _event: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<E, H> EventHandler for TypedHandler<E, H>
+where
+    E: DomainEvent,
+    H: TypedEventHandler<E> + ?Sized,
+{
+    async fn handle(&self, event: Event) -> EventBusResult<()> {
+        self.handle_delivery(event, Delivery::first()).await
+    }
+
+    async fn handle_delivery(&self, event: Event, delivery: Delivery) -> EventBusResult<()> {
+        if !E::accepts(&event) {
+            return Ok(());
+        }
+        let event = TypedEvent::try_from_event(event)?;
+        self.handler.handle_delivery(event, delivery).await
+    }
+
+    fn topics(&self) -> Vec<String> {
+        vec![E::TOPIC_PATTERN.to_string()]
+    }
+
+    fn name(&self) -> &str {
+        self.handler.name()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.handler.retry_policy()
+    }
+}
+
+/// Typed subscriptions and handlers on any [`EventBus`].
+#[async_trait]
+pub trait TypedEventBus: EventBus {
+    /// Subscribe to a domain event's topic pattern.
+    async fn subscribe_typed<E: DomainEvent>(&self) -> EventBusResult<TypedSubscription<E>> {
+        let subscription = self.subscribe(E::TOPIC_PATTERN).await?;
+        Ok(TypedSubscription::new(subscription))
+    }
+
+    /// Register a handler for a domain event.
+    async fn register_typed_handler<E, H>(&self, handler: Arc<H>) -> EventBusResult<()>
+    where
+        E: DomainEvent,
+        H: TypedEventHandler<E> + 'static,
+    {
+        self.register_handler(Arc::new(TypedHandler::<E, H>::new(handler)))
+            .await
+    }
+}
+
+impl<B: EventBus + ?Sized> TypedEventBus for B {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::{EventBusError, MemoryEventBus};
+    use crate::types::MeetingEvent;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[tokio::test]
+    async fn test_subscribe_typed() {
+        let bus = MemoryEventBus::new();
+        let mut meetings = bus.subscribe_typed::<MeetingEvent>().await.unwrap();
+
+        let org_id = Uuid::now_v7();
+        let meeting_id = Uuid::now_v7();
+        let archived = Event::new("meeting.archived", App::NoteMan, serde_json::json!({}));
+        let ended = MeetingEvent::Ended {
+            meeting_id,
+            duration_seconds: 900,
+        }
+        .to_event()
+        .with_org(org_id)
+        .with_correlation_id("req-1");
+        bus.publish(archived).await.unwrap();
+        bus.publish(ended.clone()).await.unwrap();
+
+        let received = tokio::time::timeout(TIMEOUT, meetings.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            received.data,
+            MeetingEvent::Ended { meeting_id: id, duration_seconds: 900 } if id == meeting_id
+        ));
+        assert_eq!(received.id, ended.id);
+        assert_eq!(received.org_id, Some(org_id));
+        assert_eq!(received.correlation_id.as_deref(), Some("req-1"));
+        let mut reencoded = received.clone().into_event();
+        assert_eq!(reencoded.topic(), ended.topic());
+        assert_eq!(reencoded.version, ended.version);
+        let mut newer = received;
+        newer.version = MeetingEvent::VERSION + 1;
+        reencoded = newer.into_event();
+        assert_eq!(reencoded.version, MeetingEvent::VERSION + 1);
+
+        let mut malformed = ended;
+        malformed.payload = serde_json::json!({"type": "ended"});
+        bus.publish(malformed).await.unwrap();
+        let error = tokio::time::timeout(TIMEOUT, meetings.recv())
+            .await
+            .unwrap();
+        assert!(matches!(error, Err(EventBusError::DecodeError(_))));
+    }
+
+    struct MeetingRecorder {
+        meetings: mpsc::UnboundedSender<TypedEvent<MeetingEvent>>,
+    }
+
+    #[async_trait]
+    impl TypedEventHandler<MeetingEvent> for MeetingRecorder {
+        async fn handle(&self, event: TypedEvent<MeetingEvent>) -> EventBusResult<()> {
+            let _ = self.meetings.send(event);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_typed_handler() {
+        let bus = MemoryEventBus::new();
+        let (sender, mut meetings) = mpsc::unbounded_channel();
+        bus.register_typed_handler(Arc::new(MeetingRecorder { meetings: sender }))
+            .await
+            .unwrap();
+
+        let user_id = Uuid::now_v7();
+        let started = MeetingEvent::Started {
+            meeting_id: Uuid::now_v7(),
+            participants: vec!["Alice".to_string()],
+        }
+        .to_event()
+        .with_user(user_id);
+        bus.publish(Event::new(
+            "meeting.archived",
+            App::NoteMan,
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+        bus.publish(started).await.unwrap();
+
+        let received = tokio::time::timeout(TIMEOUT, meetings.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(received.data, MeetingEvent::Started { .. }));
+        assert_eq!(received.user_id, Some(user_id));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), meetings.recv())
+                .await
+                .is_err()
+        );
+    }
+}
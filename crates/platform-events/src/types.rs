@@ -5,8 +5,10 @@
 
 use chrono::{DateTime, Utc};
 use platform_rbac::App;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use thiserror::Error;
 use uuid::Uuid;
 
 /// Cross-app event envelope.
@@ -163,6 +165,120 @@ impl EventCategory {
     }
 }
 
+// ============================================================================
+// Domain Events
+// ============================================================================
+
+/// Error decoding a domain event from an [`Event`].
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    /// The event type is not one of the domain event's
+    #[error("Unknown event type: {0}")]
+    UnknownEventType(String),
+
+    /// The event was published with a schema version the domain event
+    /// cannot decode
+    #[error("Unsupported version {version} of event type {event_type}")]
+    UnsupportedVersion {
+        /// Event type
+        event_type: String,
+        /// Version of the event
+        version: u32,
+    },
+
+    /// The payload does not match the event type or source
+    #[error("Payload of {event_type} from {} does not match its envelope", app.as_str())]
+    Mismatch {
+        /// Event type of the envelope
+        event_type: String,
+        /// Source application of the envelope
+        app: App,
+    },
+
+    /// The payload could not be deserialized
+    #[error("Invalid payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+}
+
+/// A strongly-typed event enum that converts to and from [`Event`].
+///
+/// Each variant maps to one event type and source application. The enum is
+/// serialized into the payload, tagged by `type`, and the envelope carries
+/// [`VERSION`](Self::VERSION) so consumers can reject payloads written with
+/// a schema they do not know.
+///
+/// ```
+/// use platform_events::{DomainEvent, MeetingEvent};
+/// use uuid::Uuid;
+///
+/// let ended = MeetingEvent::Ended {
+///     meeting_id: Uuid::now_v7(),
+///     duration_seconds: 1800,
+/// };
+/// let event = ended.to_event();
+/// assert_eq!(event.topic(), "noteman.meeting.ended");
+///
+/// let decoded = MeetingEvent::try_from_event(&event).unwrap();
+/// assert!(matches!(decoded, MeetingEvent::Ended { duration_seconds: 1800, .. }));
+/// ```
+pub trait DomainEvent: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Schema version written to [`Event::version`] and accepted on decode.
+    const VERSION: u32 = 1;
+
+    /// Event types of all variants.
+    const EVENT_TYPES: &'static [&'static str];
+
+    /// Topic pattern matching all variants, for subscriptions and handlers.
+    const TOPIC_PATTERN: &'static str;
+
+    /// Get the event type of this variant.
+    fn event_type(&self) -> &'static str;
+
+    /// Get the source application of this variant.
+    fn source(&self) -> App;
+
+    /// Check whether an event has one of this domain event's types.
+    fn accepts(event: &Event) -> bool {
+        Self::EVENT_TYPES.contains(&event.event_type.as_str())
+    }
+
+    /// Convert to generic event.
+    fn to_event(&self) -> Event {
+        let mut event = Event::new(
+            self.event_type(),
+            self.source(),
+            serde_json::to_value(self).unwrap(),
+        );
+        event.version = Self::VERSION;
+        event
+    }
+
+    /// Decode from a generic event.
+    ///
+    /// Fails if the event type is not one of [`EVENT_TYPES`](Self::EVENT_TYPES),
+    /// the version is not [`VERSION`](Self::VERSION), or the payload does
+    /// not decode to a variant of that type and source.
+    fn try_from_event(event: &Event) -> Result<Self, DecodeError> {
+        if !Self::accepts(event) {
+            return Err(DecodeError::UnknownEventType(event.event_type.clone()));
+        }
+        if event.version != Self::VERSION {
+            return Err(DecodeError::UnsupportedVersion {
+                event_type: event.event_type.clone(),
+                version: event.version,
+            });
+        }
+        let decoded: Self = event.parse_payload()?;
+        if decoded.event_type() != event.event_type || decoded.source() != event.source {
+            return Err(DecodeError::Mismatch {
+                event_type: event.event_type.clone(),
+                app: event.source,
+            });
+        }
+        Ok(decoded)
+    }
+}
+
 // ============================================================================
 // Verity Events
 // ============================================================================
@@ -195,16 +311,34 @@ pub enum DocumentEvent {
 }
 
 impl DocumentEvent {
-    /// Convert to generic event.
+    /// Convert to generic event (see [`DomainEvent::to_event`]).
     pub fn to_event(&self) -> Event {
-        let event_type = match self {
+        DomainEvent::to_event(self)
+    }
+}
+
+impl DomainEvent for DocumentEvent {
+    const EVENT_TYPES: &'static [&'static str] = &[
+        "document.created",
+        "document.updated",
+        "document.deleted",
+        "document.verified",
+        "document.verification_failed",
+    ];
+    const TOPIC_PATTERN: &'static str = "verity.document.*";
+
+    fn event_type(&self) -> &'static str {
+        match self {
             DocumentEvent::Created { .. } => "document.created",
             DocumentEvent::Updated { .. } => "document.updated",
             DocumentEvent::Deleted { .. } => "document.deleted",
             DocumentEvent::Verified { .. } => "document.verified",
             DocumentEvent::VerificationFailed { .. } => "document.verification_failed",
-        };
-        Event::new(event_type, App::Verity, serde_json::to_value(self).unwrap())
+        }
+    }
+
+    fn source(&self) -> App {
+        App::Verity
     }
 }
 
@@ -297,9 +431,26 @@ pub struct ActionItem {
 }
 
 impl MeetingEvent {
-    /// Convert to generic event.
+    /// Convert to generic event (see [`DomainEvent::to_event`]).
     pub fn to_event(&self) -> Event {
-        let event_type = match self {
+        DomainEvent::to_event(self)
+    }
+}
+
+impl DomainEvent for MeetingEvent {
+    const EVENT_TYPES: &'static [&'static str] = &[
+        "meeting.scheduled",
+        "meeting.started",
+        "meeting.ended",
+        "meeting.transcript_completed",
+        "meeting.summary_generated",
+        "meeting.action_items_extracted",
+        "meeting.decision_recorded",
+    ];
+    const TOPIC_PATTERN: &'static str = "noteman.meeting.*";
+
+    fn event_type(&self) -> &'static str {
+        match self {
             MeetingEvent::Scheduled { .. } => "meeting.scheduled",
             MeetingEvent::Started { .. } => "meeting.started",
             MeetingEvent::Ended { .. } => "meeting.ended",
@@ -307,12 +458,11 @@ impl MeetingEvent {
             MeetingEvent::SummaryGenerated { .. } => "meeting.summary_generated",
             MeetingEvent::ActionItemsExtracted { .. } => "meeting.action_items_extracted",
             MeetingEvent::DecisionRecorded { .. } => "meeting.decision_recorded",
-        };
-        Event::new(
-            event_type,
-            App::NoteMan,
-            serde_json::to_value(self).unwrap(),
-        )
+        }
+    }
+
+    fn source(&self) -> App {
+        App::NoteMan
     }
 }
 
@@ -366,9 +516,26 @@ pub enum RepositoryEvent {
 }
 
 impl RepositoryEvent {
-    /// Convert to generic event.
+    /// Convert to generic event (see [`DomainEvent::to_event`]).
     pub fn to_event(&self) -> Event {
-        let event_type = match self {
+        DomainEvent::to_event(self)
+    }
+}
+
+impl DomainEvent for RepositoryEvent {
+    const EVENT_TYPES: &'static [&'static str] = &[
+        "repository.connected",
+        "repository.disconnected",
+        "repository.analysis_started",
+        "repository.analysis_completed",
+        "repository.finding_created",
+        "repository.finding_resolved",
+        "repository.pr_review_completed",
+    ];
+    const TOPIC_PATTERN: &'static str = "shipcheck.repository.*";
+
+    fn event_type(&self) -> &'static str {
+        match self {
             RepositoryEvent::Connected { .. } => "repository.connected",
             RepositoryEvent::Disconnected { .. } => "repository.disconnected",
             RepositoryEvent::AnalysisStarted { .. } => "repository.analysis_started",
@@ -376,12 +543,11 @@ impl RepositoryEvent {
             RepositoryEvent::FindingCreated { .. } => "repository.finding_created",
             RepositoryEvent::FindingResolved { .. } => "repository.finding_resolved",
             RepositoryEvent::PrReviewCompleted { .. } => "repository.pr_review_completed",
-        };
-        Event::new(
-            event_type,
-            App::ShipCheck,
-            serde_json::to_value(self).unwrap(),
-        )
+        }
+    }
+
+    fn source(&self) -> App {
+        App::ShipCheck
     }
 }
 
@@ -429,27 +595,46 @@ pub enum CrossAppEvent {
 }
 
 impl CrossAppEvent {
-    /// Convert to generic event.
+    /// Convert to generic event (see [`DomainEvent::to_event`]).
     pub fn to_event(&self) -> Event {
-        let (event_type, source) = match self {
+        DomainEvent::to_event(self)
+    }
+}
+
+impl DomainEvent for CrossAppEvent {
+    const EVENT_TYPES: &'static [&'static str] = &[
+        "cross_app.meeting_notes_for_verification",
+        "cross_app.meeting_notes_verified",
+        "cross_app.code_decision_tracked",
+        "cross_app.code_finding_for_discussion",
+        "cross_app.documentation_verification_request",
+    ];
+    const TOPIC_PATTERN: &'static str = "*.cross_app.*";
+
+    fn event_type(&self) -> &'static str {
+        match self {
             CrossAppEvent::MeetingNotesForVerification { .. } => {
-                ("cross_app.meeting_notes_for_verification", App::NoteMan)
-            }
-            CrossAppEvent::MeetingNotesVerified { .. } => {
-                ("cross_app.meeting_notes_verified", App::Verity)
-            }
-            CrossAppEvent::CodeDecisionTracked { .. } => {
-                ("cross_app.code_decision_tracked", App::NoteMan)
+                "cross_app.meeting_notes_for_verification"
             }
+            CrossAppEvent::MeetingNotesVerified { .. } => "cross_app.meeting_notes_verified",
+            CrossAppEvent::CodeDecisionTracked { .. } => "cross_app.code_decision_tracked",
             CrossAppEvent::CodeFindingForDiscussion { .. } => {
-                ("cross_app.code_finding_for_discussion", App::ShipCheck)
+                "cross_app.code_finding_for_discussion"
             }
-            CrossAppEvent::DocumentationVerificationRequest { .. } => (
-                "cross_app.documentation_verification_request",
-                App::ShipCheck,
-            ),
-        };
-        Event::new(event_type, source, serde_json::to_value(self).unwrap())
+            CrossAppEvent::DocumentationVerificationRequest { .. } => {
+                "cross_app.documentation_verification_request"
+            }
+        }
+    }
+
+    fn source(&self) -> App {
+        match self {
+            CrossAppEvent::MeetingNotesForVerification { .. }
+            | CrossAppEvent::CodeDecisionTracked { .. } => App::NoteMan,
+            CrossAppEvent::MeetingNotesVerified { .. } => App::Verity,
+            CrossAppEvent::CodeFindingForDiscussion { .. }
+            | CrossAppEvent::DocumentationVerificationRequest { .. } => App::ShipCheck,
+        }
     }
 }
 
@@ -510,6 +695,80 @@ mod tests {
         assert!(event.event_type.starts_with("cross_app."));
     }
 
+    #[test]
+    fn test_domain_event_round_trip() {
+        let events = vec![
+            RepositoryEvent::PrReviewCompleted {
+                repository_id: Uuid::now_v7(),
+                pr_number: 42,
+                approved: true,
+            },
+            RepositoryEvent::Disconnected {
+                repository_id: Uuid::now_v7(),
+            },
+        ];
+        for original in events {
+            let event = original.to_event();
+            assert_eq!(event.version, RepositoryEvent::VERSION);
+            let decoded = RepositoryEvent::try_from_event(&event).unwrap();
+            assert_eq!(decoded.event_type(), original.event_type());
+            assert_eq!(
+                serde_json::to_value(&decoded).unwrap(),
+                serde_json::to_value(&original).unwrap()
+            );
+        }
+
+        let cross_event = CrossAppEvent::CodeFindingForDiscussion {
+            finding_id: Uuid::now_v7(),
+            repository_id: Uuid::now_v7(),
+            meeting_id: None,
+            description: "Unbounded retry loop".to_string(),
+        };
+        let event = cross_event.to_event();
+        assert_eq!(event.source, App::ShipCheck);
+        assert!(CrossAppEvent::try_from_event(&event).is_ok());
+    }
+
+    #[test]
+    fn test_domain_event_decode_errors() {
+        let ended = MeetingEvent::Ended {
+            meeting_id: Uuid::now_v7(),
+            duration_seconds: 60,
+        }
+        .to_event();
+
+        let other = Event::new("meeting.archived", App::NoteMan, serde_json::json!({}));
+        assert!(matches!(
+            MeetingEvent::try_from_event(&other),
+            Err(DecodeError::UnknownEventType(_))
+        ));
+        assert!(matches!(
+            DocumentEvent::try_from_event(&ended),
+            Err(DecodeError::UnknownEventType(_))
+        ));
+
+        let mut newer = ended.clone();
+        newer.version = MeetingEvent::VERSION + 1;
+        assert!(matches!(
+            MeetingEvent::try_from_event(&newer),
+            Err(DecodeError::UnsupportedVersion { version: 2, .. })
+        ));
+
+        let mut retyped = ended.clone();
+        retyped.event_type = "meeting.started".to_string();
+        assert!(matches!(
+            MeetingEvent::try_from_event(&retyped),
+            Err(DecodeError::Mismatch { .. })
+        ));
+
+        let mut malformed = ended;
+        malformed.payload = serde_json::json!({"type": "ended"});
+        assert!(matches!(
+            MeetingEvent::try_from_event(&malformed),
+            Err(DecodeError::InvalidPayload(_))
+        ));
+    }
+
     #[test]
     fn test_event_category() {
         assert_eq!(
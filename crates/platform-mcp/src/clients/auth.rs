@@ -0,0 +1,368 @@
+//! Service-to-service authentication for cross-app clients.
+//!
+//! Clients authenticate to platform services in one of two ways:
+//!
+//! - **Static API keys** from [`ServiceEndpoint::api_key`], sent as a bearer token.
+//! - **Service tokens** minted by a [`ServiceTokenProvider`]. Each token is a
+//!   short-lived [`ServiceToken`] scoped to the target app, signed with the
+//!   platform JWT secret and cached until shortly before it expires.
+//!
+//! A client's [`ClientAuth`] selects between the two. [`ClientAuth::from_config`]
+//! builds one provider for a configuration, which all clients built from it
+//! share so they share its token cache. If service auth is configured but the
+//! provider cannot be built, requests fail instead of going out without the
+//! configured credentials.
+//!
+//! When a provider is attached it takes precedence over the static key. If the
+//! tool call carries an authenticated user, the provider also forwards that
+//! user's identity as a [`CrossAppToken`] in the `X-Cross-App-Token` header, so
+//! the target service can apply the user's own permissions instead of trusting
+//! the MCP server with blanket access.
+
+use super::config::{ServiceAuthConfig, ServiceConfig, ServiceEndpoint};
+use crate::server::ToolContext;
+use chrono::{DateTime, Duration, Utc};
+use platform_auth::{AppId, AuthError, AuthResult, CrossAppToken, JwtService, ServiceToken};
+use reqwest::RequestBuilder;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, error};
+
+/// Header carrying the forwarded end-user identity.
+pub const CROSS_APP_TOKEN_HEADER: &str = "X-Cross-App-Token";
+
+/// Default margin before expiry at which cached service tokens are re-minted.
+const DEFAULT_REFRESH_MARGIN_SECS: i64 = 60;
+
+/// A minted service token and its expiry.
+struct CachedToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Mints and caches platform service tokens for outgoing requests.
+///
+/// One provider is typically shared by all clients; tokens are cached per
+/// target app, so a Verity token is never presented to NoteMan.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use platform_auth::{AppId, JwtService};
+/// use platform_mcp::clients::auth::ServiceTokenProvider;
+///
+/// let jwt = JwtService::with_secret("shared-platform-secret").unwrap();
+/// let provider = ServiceTokenProvider::new(jwt, AppId::Verity)
+///     .with_capabilities(vec!["workflow.execute".to_string()]);
+///
+/// let token = provider.service_token(AppId::NoteMan).unwrap();
+/// ```
+pub struct ServiceTokenProvider {
+    /// JWT service used to sign tokens.
+    jwt: JwtService,
+
+    /// Identity the tokens are issued as.
+    issuer: AppId,
+
+    /// Capabilities granted to minted service tokens.
+    capabilities: Vec<String>,
+
+    /// How long before expiry a cached token is replaced.
+    refresh_margin: Duration,
+
+    /// Whether to forward the end user's identity.
+    forward_user: bool,
+
+    /// Cached service tokens keyed by target app.
+    cache: Mutex<HashMap<AppId, CachedToken>>,
+}
+
+impl std::fmt::Debug for ServiceTokenProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceTokenProvider")
+            .field("issuer", &self.issuer)
+            .field("capabilities", &self.capabilities)
+            .field("refresh_margin", &self.refresh_margin)
+            .field("forward_user", &self.forward_user)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ServiceTokenProvider {
+    /// Create a provider that issues tokens as `issuer`.
+    pub fn new(jwt: JwtService, issuer: AppId) -> Self {
+        Self {
+            jwt,
+            issuer,
+            capabilities: Vec::new(),
+            refresh_margin: Duration::seconds(DEFAULT_REFRESH_MARGIN_SECS),
+            forward_user: true,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a provider from service auth configuration.
+    pub fn from_config(config: &ServiceAuthConfig) -> AuthResult<Self> {
+        if config.signing_secret.is_empty() {
+            return Err(AuthError::ConfigError(
+                "Service token signing secret is empty".to_string(),
+            ));
+        }
+
+        let jwt = JwtService::with_secret(config.signing_secret.clone())?;
+        Ok(Self::new(jwt, config.issuer)
+            .with_capabilities(config.capabilities.clone())
+            .with_refresh_margin(Duration::seconds(config.refresh_margin_secs as i64))
+            .with_user_forwarding(config.forward_user))
+    }
+
+    /// Set the capabilities granted to minted service tokens.
+    pub fn with_capabilities(mut self, capabilities: Vec<String>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Set how long before expiry a cached token is re-minted.
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Enable or disable forwarding of the end user's identity.
+    pub fn with_user_forwarding(mut self, enabled: bool) -> Self {
+        self.forward_user = enabled;
+        self
+    }
+
+    /// Get the app the tokens are issued as.
+    pub fn issuer(&self) -> AppId {
+        self.issuer
+    }
+
+    /// Get a service token for `target`, minting a new one if the cached
+    /// token is missing or about to expire.
+    pub fn service_token(&self, target: AppId) -> AuthResult<String> {
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|_| AuthError::Internal("Service token cache poisoned".to_string()))?;
+
+        if let Some(cached) = cache.get(&target) {
+            if cached.expires_at - self.refresh_margin > Utc::now() {
+                return Ok(cached.token.clone());
+            }
+        }
+
+        let token = ServiceToken::new(self.issuer, target, self.capabilities.clone());
+        let expires_at = token.exp;
+        let encoded = self.jwt.encode_service_token(&token)?;
+        debug!(target = %target, jti = %token.jti, "Minted service token");
+
+        cache.insert(
+            target,
+            CachedToken {
+                token: encoded.clone(),
+                expires_at,
+            },
+        );
+
+        Ok(encoded)
+    }
+
+    /// Build a cross-app token forwarding the calling user to `target`.
+    ///
+    /// Returns `None` when forwarding is disabled or the context carries no user.
+    pub fn user_token(&self, target: AppId, context: &ToolContext) -> AuthResult<Option<String>> {
+        if !self.forward_user {
+            return Ok(None);
+        }
+
+        let Some(user_id) = context.user_id else {
+            return Ok(None);
+        };
+
+        let mut token = CrossAppToken::new(
+            user_id,
+            context.user_email.clone().unwrap_or_default(),
+            self.issuer,
+            vec![target],
+            context.permissions.clone(),
+        );
+        if let Some(org_id) = context.org_id {
+            token = token.with_org(org_id);
+        }
+
+        self.jwt.encode_cross_app_token(&token).map(Some)
+    }
+
+    /// Drop all cached service tokens.
+    pub fn clear_cache(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+}
+
+/// How a client authenticates its requests.
+#[derive(Debug, Clone, Default)]
+pub enum ClientAuth {
+    /// The endpoint's static API key, if it has one.
+    #[default]
+    ApiKey,
+
+    /// Service tokens minted by a (typically shared) provider.
+    ServiceTokens(Arc<ServiceTokenProvider>),
+
+    /// Service auth is configured but the provider could not be built;
+    /// every request fails with this message.
+    Misconfigured(String),
+}
+
+impl ClientAuth {
+    /// Build the authentication for clients of `config`.
+    ///
+    /// Call this once per configuration and pass the result to every
+    /// client, so that they share one provider and its token cache.
+    pub fn from_config(config: &ServiceConfig) -> Self {
+        match config.token_provider() {
+            Ok(Some(provider)) => ClientAuth::ServiceTokens(provider),
+            Ok(None) => ClientAuth::ApiKey,
+            Err(e) => {
+                error!("Service auth misconfigured, requests will fail: {}", e);
+                ClientAuth::Misconfigured(e.to_string())
+            }
+        }
+    }
+
+    /// Get the service token provider, if service tokens are used.
+    pub fn provider(&self) -> Option<&ServiceTokenProvider> {
+        match self {
+            ClientAuth::ServiceTokens(provider) => Some(provider),
+            _ => None,
+        }
+    }
+}
+
+/// Attach credentials for `target` to an outgoing request.
+///
+/// Uses the service token provider when present, falling back to the
+/// endpoint's static API key. Fails when service auth is misconfigured.
+pub(crate) fn authorize(
+    mut request: RequestBuilder,
+    endpoint: &ServiceEndpoint,
+    auth: &ClientAuth,
+    target: AppId,
+    context: Option<&ToolContext>,
+) -> AuthResult<RequestBuilder> {
+    match auth {
+        ClientAuth::ServiceTokens(provider) => {
+            request = request.bearer_auth(provider.service_token(target)?);
+            if let Some(context) = context {
+                if let Some(user_token) = provider.user_token(target, context)? {
+                    request = request.header(CROSS_APP_TOKEN_HEADER, user_token);
+                }
+            }
+        }
+        ClientAuth::ApiKey => {
+            if let Some(ref api_key) = endpoint.api_key {
+                request = request.header("Authorization", format!("Bearer {}", api_key));
+            }
+        }
+        ClientAuth::Misconfigured(message) => {
+            return Err(AuthError::ConfigError(message.clone()));
+        }
+    }
+
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> ServiceTokenProvider {
+        let jwt = JwtService::with_secret("test-secret").unwrap();
+        ServiceTokenProvider::new(jwt, AppId::Verity)
+            .with_capabilities(vec!["workflow.execute".to_string()])
+    }
+
+    #[test]
+    fn test_service_token_is_scoped_to_target() {
+        let provider = provider();
+        let token = provider.service_token(AppId::NoteMan).unwrap();
+
+        let jwt = JwtService::with_secret("test-secret").unwrap();
+        let decoded = jwt.decode_service_token(&token).unwrap();
+        assert!(decoded.is_valid_for(AppId::NoteMan));
+        assert!(!decoded.is_valid_for(AppId::ShipCheck));
+        assert!(decoded.has_capability("workflow.execute"));
+    }
+
+    #[test]
+    fn test_service_token_is_cached() {
+        let provider = provider();
+        let first = provider.service_token(AppId::ShipCheck).unwrap();
+        let second = provider.service_token(AppId::ShipCheck).unwrap();
+        assert_eq!(first, second);
+
+        let other = provider.service_token(AppId::NoteMan).unwrap();
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_service_token_refreshed_near_expiry() {
+        // A margin longer than the token lifetime forces a re-mint every time.
+        let provider = provider().with_refresh_margin(Duration::hours(1));
+        let first = provider.service_token(AppId::ShipCheck).unwrap();
+        let second = provider.service_token(AppId::ShipCheck).unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_user_token_forwards_context() {
+        let provider = provider();
+        let user_id = uuid::Uuid::now_v7();
+        let org_id = uuid::Uuid::now_v7();
+        let mut context = ToolContext::empty();
+        assert!(provider
+            .user_token(AppId::Verity, &context)
+            .unwrap()
+            .is_none());
+
+        context.user_id = Some(user_id);
+        context.org_id = Some(org_id);
+        context.user_email = Some("user@example.com".to_string());
+        context.permissions = vec!["verity:document:read".to_string()];
+
+        let token = provider
+            .user_token(AppId::Verity, &context)
+            .unwrap()
+            .unwrap();
+        let jwt = JwtService::with_secret("test-secret").unwrap();
+        let decoded = jwt.decode_cross_app_token(&token).unwrap();
+        assert_eq!(decoded.sub, user_id);
+        assert_eq!(decoded.org_id, Some(org_id));
+        assert!(decoded.is_valid_for(AppId::Verity));
+        assert!(decoded.has_permission("verity:document:read"));
+
+        let provider = provider.with_user_forwarding(false);
+        assert!(provider
+            .user_token(AppId::Verity, &context)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_misconfigured_auth_fails_requests() {
+        let mut config = ServiceConfig::default();
+        config.verity.api_key = Some("fallback-key".to_string());
+        config.service_auth = Some(ServiceAuthConfig::new(AppId::Verity, ""));
+
+        let auth = ClientAuth::from_config(&config);
+        assert!(matches!(auth, ClientAuth::Misconfigured(_)));
+
+        let request = reqwest::Client::new().get(config.verity.url("/health"));
+        let result = authorize(request, &config.verity, &auth, AppId::Verity, None);
+        assert!(matches!(result, Err(AuthError::ConfigError(_))));
+    }
+}
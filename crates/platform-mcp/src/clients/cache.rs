@@ -0,0 +1,496 @@
+//! Client-side response caching for read endpoints.
+//!
+//! Read calls such as `get_document` or `get_finding` can be served from a
+//! [`ResponseCache`] instead of hitting the network on every call. Entries are
+//! stored in a pluggable [`CacheBackend`] (an in-memory LRU by default) and
+//! expire after a per-operation TTL. Expired entries that carry an `ETag` are
+//! revalidated with `If-None-Match`; a `304 Not Modified` reply refreshes the
+//! entry without transferring the body again.
+//!
+//! Cache keys include the target app, the endpoint, the caller's organization
+//! and a fingerprint of the credentials used, so responses are never shared
+//! between tenants or identities.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::cache::ResponseCache;
+//! use platform_mcp::clients::{ServiceConfig, VerityClient};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let cache = ResponseCache::in_memory(500)
+//!     .with_default_ttl(Duration::from_secs(30))
+//!     .with_ttl("get_finding", Duration::from_secs(5));
+//!
+//! let client = VerityClient::from_config(&ServiceConfig::from_env())
+//!     .with_cache(Arc::new(cache));
+//! ```
+
+use super::auth::ServiceTokenProvider;
+use super::config::{CacheConfig, ServiceEndpoint};
+use crate::server::ToolContext;
+use chrono::{DateTime, Utc};
+use platform_auth::AppId;
+use reqwest::header::ETAG;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default number of entries kept by the in-memory cache.
+pub const DEFAULT_CACHE_CAPACITY: usize = 1000;
+
+/// Default time-to-live for cached responses, in seconds.
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 30;
+
+/// A cached response body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Decoded JSON response body.
+    pub body: serde_json::Value,
+
+    /// Entity tag returned by the service, used for revalidation.
+    pub etag: Option<String>,
+
+    /// When the entry stops being served without revalidation.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CacheEntry {
+    /// Check whether the entry can be served without contacting the service.
+    pub fn is_fresh(&self) -> bool {
+        Utc::now() < self.expires_at
+    }
+}
+
+/// Storage for cached responses.
+///
+/// Implement this to share a cache between processes, e.g. in Redis.
+/// Entries are serializable for that purpose.
+pub trait CacheBackend: Send + Sync {
+    /// Get an entry.
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    /// Insert or replace an entry.
+    fn put(&self, key: String, entry: CacheEntry);
+
+    /// Remove an entry.
+    fn remove(&self, key: &str);
+
+    /// Remove all entries.
+    fn clear(&self);
+}
+
+/// Least-recently-used map with a fixed capacity.
+pub(crate) struct Lru<K, V> {
+    /// Maximum number of entries.
+    capacity: usize,
+
+    /// Entries with the tick of their last use.
+    entries: HashMap<K, (u64, V)>,
+
+    /// Keys by last use, oldest first.
+    order: BTreeMap<u64, K>,
+
+    /// Monotonic use counter.
+    tick: u64,
+}
+
+impl<K: Eq + Hash + Clone, V> Lru<K, V> {
+    /// Create a map holding at most `capacity` entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Get an entry, marking it as most recently used.
+    pub(crate) fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.tick += 1;
+        let (used, value) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        *used = self.tick;
+        self.order.insert(self.tick, key.to_owned());
+        Some(value)
+    }
+
+    /// Insert or replace an entry, evicting the least recently used ones
+    /// beyond capacity.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        if let Some((used, _)) = self.entries.insert(key.clone(), (self.tick, value)) {
+            self.order.remove(&used);
+        }
+        self.order.insert(self.tick, key);
+
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// Remove an entry.
+    pub(crate) fn remove<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some((used, _)) = self.entries.remove(key) {
+            self.order.remove(&used);
+        }
+    }
+
+    /// Remove all entries.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Get the number of entries.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// In-memory least-recently-used cache backend.
+pub struct MemoryCache {
+    /// Entries and their recency order.
+    state: Mutex<Lru<String, CacheEntry>>,
+}
+
+impl MemoryCache {
+    /// Create a cache holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(Lru::new(capacity)),
+        }
+    }
+
+    /// Get the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.state().len()
+    }
+
+    /// Check whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, Lru<String, CacheEntry>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl CacheBackend for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.state().get(key).cloned()
+    }
+
+    fn put(&self, key: String, entry: CacheEntry) {
+        self.state().insert(key, entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.state().remove(key);
+    }
+
+    fn clear(&self) {
+        self.state().clear();
+    }
+}
+
+/// Result of looking up a request in the cache.
+pub(crate) enum CacheLookup {
+    /// A fresh body that can be returned as is.
+    Fresh(serde_json::Value),
+    /// An expired entry that can be revalidated with this ETag.
+    Stale(String),
+    /// Nothing usable is cached.
+    Miss,
+}
+
+/// Response cache shared by service clients.
+pub struct ResponseCache {
+    /// Entry storage.
+    backend: Arc<dyn CacheBackend>,
+
+    /// TTL for operations without an override.
+    default_ttl: Duration,
+
+    /// TTL overrides by operation name (e.g. `get_finding`).
+    ttls: HashMap<String, Duration>,
+}
+
+impl ResponseCache {
+    /// Create a cache on top of a custom backend.
+    pub fn new(backend: Arc<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
+            default_ttl: Duration::from_secs(DEFAULT_CACHE_TTL_SECS),
+            ttls: HashMap::new(),
+        }
+    }
+
+    /// Create an in-memory LRU cache holding at most `capacity` entries.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::new(Arc::new(MemoryCache::new(capacity)))
+    }
+
+    /// Create an in-memory cache from configuration.
+    pub fn from_config(config: &CacheConfig) -> Self {
+        let mut cache = Self::in_memory(config.capacity)
+            .with_default_ttl(Duration::from_secs(config.default_ttl_secs));
+        for (operation, secs) in &config.ttls {
+            cache = cache.with_ttl(operation.clone(), Duration::from_secs(*secs));
+        }
+        cache
+    }
+
+    /// Set the TTL for operations without an override.
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Set the TTL for one operation, such as `get_document`.
+    ///
+    /// A zero TTL revalidates on every call.
+    pub fn with_ttl(mut self, operation: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(operation.into(), ttl);
+        self
+    }
+
+    /// Get the TTL applied to an operation.
+    pub fn ttl(&self, operation: &str) -> Duration {
+        self.ttls
+            .get(operation)
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+
+    /// Remove all cached responses.
+    pub fn clear(&self) {
+        self.backend.clear();
+    }
+
+    /// Build the cache key for a request.
+    pub(crate) fn key(&self, app: AppId, operation: &str, scope: &str, resource: &str) -> String {
+        format!("{}:{}:{}:{}", app.as_str(), operation, scope, resource)
+    }
+
+    /// Look up a request.
+    pub(crate) fn lookup(&self, key: &str) -> CacheLookup {
+        match self.backend.get(key) {
+            Some(entry) if entry.is_fresh() => CacheLookup::Fresh(entry.body),
+            Some(CacheEntry {
+                etag: Some(etag), ..
+            }) => CacheLookup::Stale(etag),
+            Some(_) => {
+                self.backend.remove(key);
+                CacheLookup::Miss
+            }
+            None => CacheLookup::Miss,
+        }
+    }
+
+    /// Extend a stale entry after the service answered `304 Not Modified`.
+    pub(crate) fn revalidate(&self, key: &str, operation: &str) -> Option<serde_json::Value> {
+        let mut entry = self.backend.get(key)?;
+        entry.expires_at = self.expiry(operation);
+        let body = entry.body.clone();
+        self.backend.put(key.to_string(), entry);
+        Some(body)
+    }
+
+    /// Store a response body.
+    ///
+    /// Bodies that would expire immediately and cannot be revalidated are not
+    /// stored.
+    pub(crate) fn store(
+        &self,
+        key: &str,
+        operation: &str,
+        etag: Option<String>,
+        body: serde_json::Value,
+    ) {
+        if etag.is_none() && self.ttl(operation).is_zero() {
+            return;
+        }
+        self.backend.put(
+            key.to_string(),
+            CacheEntry {
+                body,
+                etag,
+                expires_at: self.expiry(operation),
+            },
+        );
+    }
+
+    fn expiry(&self, operation: &str) -> DateTime<Utc> {
+        let ttl = chrono::Duration::from_std(self.ttl(operation)).unwrap_or(chrono::Duration::MAX);
+        Utc::now()
+            .checked_add_signed(ttl)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("default_ttl", &self.default_ttl)
+            .field("ttls", &self.ttls)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Identify the tenant and credentials a request is made with.
+///
+/// Combines the endpoint, the caller's organization and user, and a hash of
+/// the credentials, so entries are never shared across tenants or identities.
+pub(crate) fn scope(
+    endpoint: &ServiceEndpoint,
+    provider: Option<&ServiceTokenProvider>,
+    context: Option<&ToolContext>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(endpoint.base_url.as_bytes());
+    match provider {
+        Some(provider) => hasher.update(format!("\0service:{}", provider.issuer().as_str())),
+        None => {
+            if let Some(ref api_key) = endpoint.api_key {
+                hasher.update(format!("\0key:{}", api_key));
+            }
+        }
+    }
+    if let Some(user_id) = context.and_then(|c| c.user_id) {
+        hasher.update(format!("\0user:{}", user_id));
+    }
+
+    let org = context
+        .and_then(|c| c.org_id)
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let digest = hasher.finalize();
+    let fingerprint: String = digest[..12].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}:{}", org, fingerprint)
+}
+
+/// Get the entity tag of a response, if any.
+pub(crate) fn etag(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(body: serde_json::Value, etag: Option<&str>, ttl_secs: i64) -> CacheEntry {
+        CacheEntry {
+            body,
+            etag: etag.map(String::from),
+            expires_at: Utc::now() + chrono::Duration::seconds(ttl_secs),
+        }
+    }
+
+    #[test]
+    fn test_memory_cache_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+        cache.put("a".to_string(), entry(serde_json::json!(1), None, 60));
+        cache.put("b".to_string(), entry(serde_json::json!(2), None, 60));
+
+        // Using "a" makes "b" the eviction candidate.
+        assert!(cache.get("a").is_some());
+        cache.put("c".to_string(), entry(serde_json::json!(3), None, 60));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn test_lookup_distinguishes_fresh_stale_and_expired() {
+        let backend = Arc::new(MemoryCache::new(10));
+        let cache = ResponseCache::new(backend.clone());
+
+        backend.put("fresh".to_string(), entry(serde_json::json!(1), None, 60));
+        backend.put(
+            "stale".to_string(),
+            entry(serde_json::json!(2), Some("\"v2\""), -1),
+        );
+        backend.put("expired".to_string(), entry(serde_json::json!(3), None, -1));
+
+        assert!(matches!(cache.lookup("fresh"), CacheLookup::Fresh(_)));
+        assert!(matches!(cache.lookup("stale"), CacheLookup::Stale(etag) if etag == "\"v2\""));
+        assert!(matches!(cache.lookup("expired"), CacheLookup::Miss));
+        assert!(backend.get("expired").is_none());
+
+        assert_eq!(
+            cache.revalidate("stale", "get_document"),
+            Some(serde_json::json!(2))
+        );
+        assert!(matches!(cache.lookup("stale"), CacheLookup::Fresh(_)));
+    }
+
+    #[test]
+    fn test_per_operation_ttl() {
+        let cache = ResponseCache::in_memory(10)
+            .with_default_ttl(Duration::from_secs(30))
+            .with_ttl("get_finding", Duration::ZERO);
+        assert_eq!(cache.ttl("get_document"), Duration::from_secs(30));
+        assert!(cache.ttl("get_finding").is_zero());
+
+        // Zero-TTL responses are only kept when they can be revalidated.
+        cache.store("k1", "get_finding", None, serde_json::json!(1));
+        assert!(matches!(cache.lookup("k1"), CacheLookup::Miss));
+        cache.store(
+            "k2",
+            "get_finding",
+            Some("\"e\"".into()),
+            serde_json::json!(1),
+        );
+        assert!(matches!(cache.lookup("k2"), CacheLookup::Stale(_)));
+    }
+
+    #[test]
+    fn test_scope_separates_orgs_and_credentials() {
+        let endpoint = ServiceEndpoint {
+            base_url: "http://localhost:3000".to_string(),
+            api_key: Some("key-a".to_string()),
+            webhook_secret: None,
+        };
+        let mut context = ToolContext::empty();
+        context.org_id = Some(uuid::Uuid::now_v7());
+        let org_a = scope(&endpoint, None, Some(&context));
+
+        context.org_id = Some(uuid::Uuid::now_v7());
+        assert_ne!(org_a, scope(&endpoint, None, Some(&context)));
+
+        let other_key = ServiceEndpoint {
+            api_key: Some("key-b".to_string()),
+            ..endpoint.clone()
+        };
+        assert_ne!(
+            scope(&endpoint, None, Some(&context)),
+            scope(&other_key, None, Some(&context))
+        );
+        assert!(!scope(&endpoint, None, None).contains("key-a"));
+    }
+}
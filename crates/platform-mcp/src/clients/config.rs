@@ -2,12 +2,44 @@
 //!
 //! Provides centralized configuration for all platform service endpoints,
 //! API keys, and timeout settings. Configuration is loaded from environment
-//! variables with sensible defaults for local development.
+//! variables with sensible defaults for local development, or layered from a
+//! TOML/YAML/JSON file:
+//!
+//! 1. Built-in defaults
+//! 2. Top-level settings from the file
+//! 3. The selected profile under `[profiles.<name>]`
+//! 4. Environment variable overrides (see [`ServiceConfig::from_env`])
+//!
+//! ```toml
+//! default_timeout_secs = 30
+//!
+//! [verity]
+//! base_url = "http://localhost:3000"
+//!
+//! [profiles.production]
+//! default_timeout_secs = 10
+//!
+//! [profiles.production.verity]
+//! base_url = "https://api.verity.relay.one"
+//! ```
+//!
+//! The `production` profile is checked with
+//! [`ServiceConfig::validate_for_production`].
 
+use super::auth::ServiceTokenProvider;
+use super::cache::{DEFAULT_CACHE_CAPACITY, DEFAULT_CACHE_TTL_SECS};
+use platform_auth::AppId;
+use platform_org::Tier;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 
+/// Profile names that require production validation.
+const PRODUCTION_PROFILES: &[&str] = &["production", "prod"];
+
 /// Configuration errors.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -23,6 +55,62 @@ pub enum ConfigError {
         /// Error message.
         message: String,
     },
+
+    /// Configuration file could not be read.
+    #[error("Failed to read config file {path}: {message}")]
+    Io {
+        /// File path.
+        path: String,
+        /// Error message.
+        message: String,
+    },
+
+    /// Configuration file could not be parsed.
+    #[error("Failed to parse config file: {0}")]
+    Parse(String),
+
+    /// Requested profile is not defined in the file.
+    #[error("Unknown config profile: {0}")]
+    UnknownProfile(String),
+}
+
+/// Supported configuration file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// TOML (`.toml`)
+    Toml,
+    /// YAML (`.yaml`, `.yml`)
+    Yaml,
+    /// JSON (`.json`)
+    Json,
+}
+
+impl ConfigFormat {
+    /// Detect the format from a file extension.
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("yaml") | Some("yml") => Ok(Self::Yaml),
+            Some("json") => Ok(Self::Json),
+            other => Err(ConfigError::Parse(format!(
+                "Unsupported config file extension: {}",
+                other.unwrap_or("<none>")
+            ))),
+        }
+    }
+
+    /// Parse file content into a generic value tree.
+    fn parse(self, content: &str) -> Result<serde_json::Value, ConfigError> {
+        match self {
+            Self::Toml => toml::from_str(content).map_err(|e| ConfigError::Parse(e.to_string())),
+            Self::Yaml => {
+                serde_yaml::from_str(content).map_err(|e| ConfigError::Parse(e.to_string()))
+            }
+            Self::Json => {
+                serde_json::from_str(content).map_err(|e| ConfigError::Parse(e.to_string()))
+            }
+        }
+    }
 }
 
 /// Service configuration for all platform services.
@@ -45,6 +133,18 @@ pub struct ServiceConfig {
 
     /// Whether to verify TLS certificates (disable only for testing).
     pub verify_tls: bool,
+
+    /// Signed service-token authentication (replaces static API keys when set).
+    #[serde(default)]
+    pub service_auth: Option<ServiceAuthConfig>,
+
+    /// Dedicated deployments for individual organizations, keyed by org ID.
+    #[serde(default)]
+    pub tenants: HashMap<uuid::Uuid, TenantConfig>,
+
+    /// Response caching for read endpoints (disabled when unset).
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
 }
 
 impl Default for ServiceConfig {
@@ -69,6 +169,9 @@ impl Default for ServiceConfig {
             default_timeout_secs: 30,
             max_retries: 3,
             verify_tls: true,
+            service_auth: None,
+            tenants: HashMap::new(),
+            cache: None,
         }
     }
 }
@@ -89,48 +192,194 @@ impl ServiceConfig {
     /// - `SERVICE_TIMEOUT_SECS`: Request timeout in seconds (default: 30)
     /// - `SERVICE_MAX_RETRIES`: Maximum retry attempts (default: 3)
     /// - `SERVICE_VERIFY_TLS`: Whether to verify TLS (default: true)
+    /// - `SERVICE_TOKEN_SECRET`: Enables service tokens signed with this secret
+    /// - `SERVICE_TOKEN_ISSUER`: App identity the tokens are issued as (verity, noteman, shipcheck)
+    /// - `SERVICE_TOKEN_CAPABILITIES`: Comma-separated capabilities granted to service tokens
+    /// - `SERVICE_CACHE_CAPACITY`: Enables response caching with this many entries
+    /// - `SERVICE_CACHE_TTL_SECS`: Default TTL for cached responses (default: 30)
     pub fn from_env() -> Self {
-        let default = Self::default();
+        let mut config = Self::default();
+        config.apply_env_overrides();
+        config
+    }
 
-        Self {
-            noteman: ServiceEndpoint {
-                base_url: std::env::var("NOTEMAN_API_URL").unwrap_or(default.noteman.base_url),
-                api_key: std::env::var("NOTEMAN_API_KEY").ok(),
-                webhook_secret: std::env::var("NOTEMAN_WEBHOOK_SECRET").ok(),
-            },
-            shipcheck: ServiceEndpoint {
-                base_url: std::env::var("SHIPCHECK_API_URL").unwrap_or(default.shipcheck.base_url),
-                api_key: std::env::var("SHIPCHECK_API_KEY").ok(),
-                webhook_secret: std::env::var("SHIPCHECK_WEBHOOK_SECRET").ok(),
-            },
-            verity: ServiceEndpoint {
-                base_url: std::env::var("VERITY_API_URL").unwrap_or(default.verity.base_url),
-                api_key: std::env::var("VERITY_API_KEY").ok(),
-                webhook_secret: std::env::var("VERITY_WEBHOOK_SECRET").ok(),
-            },
-            default_timeout_secs: std::env::var("SERVICE_TIMEOUT_SECS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(default.default_timeout_secs),
-            max_retries: std::env::var("SERVICE_MAX_RETRIES")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(default.max_retries),
-            verify_tls: std::env::var("SERVICE_VERIFY_TLS")
-                .map(|s| s != "false" && s != "0")
-                .unwrap_or(default.verify_tls),
+    /// Load configuration from `SERVICE_CONFIG_FILE` if set, otherwise from
+    /// environment variables.
+    ///
+    /// The profile is taken from `SERVICE_PROFILE`.
+    pub fn load() -> Result<Self, ConfigError> {
+        match std::env::var("SERVICE_CONFIG_FILE") {
+            Ok(path) => {
+                let profile = std::env::var("SERVICE_PROFILE").ok();
+                Self::from_file(path, profile.as_deref())
+            }
+            Err(_) => {
+                let config = Self::from_env();
+                config.validate()?;
+                Ok(config)
+            }
         }
     }
 
+    /// Load layered configuration from a TOML, YAML or JSON file.
+    ///
+    /// Settings are applied in order: defaults, file, `profiles.<profile>`,
+    /// then environment variable overrides. The result is validated, and the
+    /// `production` profile must also pass [`Self::validate_for_production`].
+    pub fn from_file(path: impl AsRef<Path>, profile: Option<&str>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        Self::from_str_with_format(&content, ConfigFormat::from_path(path)?, profile)
+    }
+
+    /// Load layered configuration from file content in the given format.
+    pub fn from_str_with_format(
+        content: &str,
+        format: ConfigFormat,
+        profile: Option<&str>,
+    ) -> Result<Self, ConfigError> {
+        let mut file = format.parse(content)?;
+        let profiles = file
+            .as_object_mut()
+            .and_then(|map| map.remove("profiles"))
+            .unwrap_or(serde_json::Value::Null);
+
+        let mut merged =
+            serde_json::to_value(Self::default()).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        merge_values(&mut merged, file);
+
+        if let Some(name) = profile {
+            let overrides = profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))?;
+            merge_values(&mut merged, overrides);
+        }
+
+        let mut config: Self =
+            serde_json::from_value(merged).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.apply_env_overrides();
+        config.validate()?;
+
+        if profile.is_some_and(|name| PRODUCTION_PROFILES.contains(&name)) {
+            config.validate_for_production()?;
+        }
+
+        Ok(config)
+    }
+
+    /// Apply any environment variables that are set on top of this configuration.
+    fn apply_env_overrides(&mut self) {
+        self.noteman.apply_env_overrides("NOTEMAN");
+        self.shipcheck.apply_env_overrides("SHIPCHECK");
+        self.verity.apply_env_overrides("VERITY");
+
+        if let Some(timeout) = env_parse("SERVICE_TIMEOUT_SECS") {
+            self.default_timeout_secs = timeout;
+        }
+        if let Some(retries) = env_parse("SERVICE_MAX_RETRIES") {
+            self.max_retries = retries;
+        }
+        if let Ok(verify) = std::env::var("SERVICE_VERIFY_TLS") {
+            self.verify_tls = verify != "false" && verify != "0";
+        }
+        if let Some(auth) = ServiceAuthConfig::from_env() {
+            self.service_auth = Some(auth);
+        }
+        if let Some(capacity) = env_parse("SERVICE_CACHE_CAPACITY") {
+            self.cache.get_or_insert_with(CacheConfig::default).capacity = capacity;
+        }
+        if let Some(ttl) = env_parse("SERVICE_CACHE_TTL_SECS") {
+            if let Some(ref mut cache) = self.cache {
+                cache.default_ttl_secs = ttl;
+            }
+        }
+    }
+
+    /// Validate that the configuration is usable.
+    ///
+    /// Checks that every endpoint has an HTTP(S) base URL, that the timeout
+    /// is non-zero, that service auth (when set) can mint tokens, and that
+    /// only enterprise tenants have dedicated endpoints.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        for (name, endpoint) in [
+            ("noteman", &self.noteman),
+            ("shipcheck", &self.shipcheck),
+            ("verity", &self.verity),
+        ] {
+            if !endpoint.base_url.starts_with("http://")
+                && !endpoint.base_url.starts_with("https://")
+            {
+                return Err(ConfigError::InvalidValue {
+                    key: format!("{}.base_url", name),
+                    message: format!("expected an http(s) URL, got '{}'", endpoint.base_url),
+                });
+            }
+        }
+        if self.default_timeout_secs == 0 {
+            return Err(ConfigError::InvalidValue {
+                key: "default_timeout_secs".to_string(),
+                message: "must be greater than zero".to_string(),
+            });
+        }
+        self.token_provider()?;
+        for (org_id, tenant) in &self.tenants {
+            if tenant.has_overrides() && !tenant.tier.is_enterprise() {
+                return Err(ConfigError::InvalidValue {
+                    key: format!("tenants.{}", org_id),
+                    message: format!(
+                        "dedicated deployments require an enterprise tier, got '{}'",
+                        tenant.tier.as_str()
+                    ),
+                });
+            }
+            tenant.apply(self).validate()?;
+        }
+        if self.cache.as_ref().is_some_and(|cache| cache.capacity == 0) {
+            return Err(ConfigError::InvalidValue {
+                key: "cache.capacity".to_string(),
+                message: "must be greater than zero".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// Get the default request timeout as a Duration.
     pub fn timeout(&self) -> Duration {
         Duration::from_secs(self.default_timeout_secs)
     }
 
+    /// Build the shared service token provider, if service auth is configured.
+    pub fn token_provider(&self) -> Result<Option<Arc<ServiceTokenProvider>>, ConfigError> {
+        self.service_auth
+            .as_ref()
+            .map(|auth| {
+                ServiceTokenProvider::from_config(auth)
+                    .map(Arc::new)
+                    .map_err(|e| ConfigError::InvalidValue {
+                        key: "service_auth".to_string(),
+                        message: e.to_string(),
+                    })
+            })
+            .transpose()
+    }
+
     /// Validate that all required configuration is present for production.
     ///
-    /// In production, API keys and webhook secrets should be configured.
+    /// In production, either service tokens or per-service API keys must be
+    /// configured.
     pub fn validate_for_production(&self) -> Result<(), ConfigError> {
+        if let Some(ref auth) = self.service_auth {
+            if auth.signing_secret.is_empty() {
+                return Err(ConfigError::MissingEnvVar(
+                    "SERVICE_TOKEN_SECRET".to_string(),
+                ));
+            }
+            return Ok(());
+        }
         if self.noteman.api_key.is_none() {
             return Err(ConfigError::MissingEnvVar("NOTEMAN_API_KEY".to_string()));
         }
@@ -144,6 +393,232 @@ impl ServiceConfig {
     }
 }
 
+/// Per-organization service overrides.
+///
+/// Only organizations on an enterprise tier may use dedicated deployments;
+/// any app left unset falls back to the shared endpoint.
+///
+/// ```toml
+/// [tenants.0190a3c2-7d1e-7000-8000-000000000001]
+/// tier = "enterprise"
+/// timeout_secs = 60
+///
+/// [tenants.0190a3c2-7d1e-7000-8000-000000000001.verity]
+/// base_url = "https://verity.acme.internal"
+/// api_key = "acme-verity-key"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// Subscription tier of the organization.
+    pub tier: Tier,
+
+    /// Dedicated NoteMan deployment.
+    #[serde(default)]
+    pub noteman: Option<ServiceEndpoint>,
+
+    /// Dedicated ShipCheck deployment.
+    #[serde(default)]
+    pub shipcheck: Option<ServiceEndpoint>,
+
+    /// Dedicated Verity deployment.
+    #[serde(default)]
+    pub verity: Option<ServiceEndpoint>,
+
+    /// Request timeout override in seconds.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl TenantConfig {
+    /// Create tenant overrides for an organization on `tier`.
+    pub fn new(tier: Tier) -> Self {
+        Self {
+            tier,
+            noteman: None,
+            shipcheck: None,
+            verity: None,
+            timeout_secs: None,
+        }
+    }
+
+    /// Check whether these overrides change anything.
+    pub fn has_overrides(&self) -> bool {
+        self.noteman.is_some()
+            || self.shipcheck.is_some()
+            || self.verity.is_some()
+            || self.timeout_secs.is_some()
+    }
+
+    /// Build the effective configuration for this tenant on top of `base`.
+    pub fn apply(&self, base: &ServiceConfig) -> ServiceConfig {
+        let mut config = base.clone();
+        config.tenants.clear();
+        if let Some(ref endpoint) = self.noteman {
+            config.noteman = endpoint.clone();
+        }
+        if let Some(ref endpoint) = self.shipcheck {
+            config.shipcheck = endpoint.clone();
+        }
+        if let Some(ref endpoint) = self.verity {
+            config.verity = endpoint.clone();
+        }
+        if let Some(timeout) = self.timeout_secs {
+            config.default_timeout_secs = timeout;
+        }
+        config
+    }
+}
+
+/// Response cache settings.
+///
+/// ```toml
+/// [cache]
+/// capacity = 1000
+/// default_ttl_secs = 30
+///
+/// [cache.ttls]
+/// get_finding = 5
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Maximum number of cached responses.
+    #[serde(default = "default_cache_capacity")]
+    pub capacity: usize,
+
+    /// TTL in seconds for operations without an override.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub default_ttl_secs: u64,
+
+    /// TTL overrides in seconds, keyed by client operation (e.g. `get_document`).
+    #[serde(default)]
+    pub ttls: HashMap<String, u64>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: default_cache_capacity(),
+            default_ttl_secs: default_cache_ttl_secs(),
+            ttls: HashMap::new(),
+        }
+    }
+}
+
+/// Service-token authentication settings.
+///
+/// When configured, clients present short-lived platform service tokens
+/// instead of static API keys.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ServiceAuthConfig {
+    /// App identity the tokens are issued as.
+    pub issuer: AppId,
+
+    /// Shared platform secret used to sign tokens.
+    pub signing_secret: String,
+
+    /// Capabilities granted to minted service tokens.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+
+    /// Seconds before expiry at which a cached token is re-minted.
+    #[serde(default = "default_refresh_margin_secs")]
+    pub refresh_margin_secs: u64,
+
+    /// Whether to forward the calling user's identity as a cross-app token.
+    #[serde(default = "default_true")]
+    pub forward_user: bool,
+}
+
+impl ServiceAuthConfig {
+    /// Create service auth settings with default capabilities and refresh margin.
+    pub fn new(issuer: AppId, signing_secret: impl Into<String>) -> Self {
+        Self {
+            issuer,
+            signing_secret: signing_secret.into(),
+            capabilities: Vec::new(),
+            refresh_margin_secs: default_refresh_margin_secs(),
+            forward_user: true,
+        }
+    }
+
+    /// Load service auth settings from the environment.
+    ///
+    /// Returns `None` unless `SERVICE_TOKEN_SECRET` is set.
+    fn from_env() -> Option<Self> {
+        let secret = std::env::var("SERVICE_TOKEN_SECRET").ok()?;
+        let issuer = std::env::var("SERVICE_TOKEN_ISSUER").unwrap_or_default();
+        let Some(issuer) = AppId::from_str(&issuer) else {
+            tracing::warn!(
+                issuer = %issuer,
+                "SERVICE_TOKEN_SECRET is set but SERVICE_TOKEN_ISSUER is not a platform app; service tokens disabled"
+            );
+            return None;
+        };
+
+        let mut config = Self::new(issuer, secret);
+        if let Ok(capabilities) = std::env::var("SERVICE_TOKEN_CAPABILITIES") {
+            config.capabilities = capabilities
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        Some(config)
+    }
+}
+
+impl std::fmt::Debug for ServiceAuthConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceAuthConfig")
+            .field("issuer", &self.issuer)
+            .field("signing_secret", &"[REDACTED]")
+            .field("capabilities", &self.capabilities)
+            .field("refresh_margin_secs", &self.refresh_margin_secs)
+            .field("forward_user", &self.forward_user)
+            .finish()
+    }
+}
+
+/// Parse an environment variable, ignoring unset or malformed values.
+fn env_parse<T: std::str::FromStr>(key: &str) -> Option<T> {
+    std::env::var(key).ok().and_then(|s| s.parse().ok())
+}
+
+/// Recursively merge `overlay` into `base`; objects merge key by key and
+/// everything else is replaced.
+fn merge_values(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn default_refresh_margin_secs() -> u64 {
+    60
+}
+
+fn default_cache_capacity() -> usize {
+    DEFAULT_CACHE_CAPACITY
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    DEFAULT_CACHE_TTL_SECS
+}
+
+fn default_true() -> bool {
+    true
+}
+
 /// Configuration for a single service endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceEndpoint {
@@ -158,6 +633,20 @@ pub struct ServiceEndpoint {
 }
 
 impl ServiceEndpoint {
+    /// Override fields from `{PREFIX}_API_URL`, `{PREFIX}_API_KEY` and
+    /// `{PREFIX}_WEBHOOK_SECRET` when set.
+    fn apply_env_overrides(&mut self, prefix: &str) {
+        if let Ok(url) = std::env::var(format!("{}_API_URL", prefix)) {
+            self.base_url = url;
+        }
+        if let Ok(key) = std::env::var(format!("{}_API_KEY", prefix)) {
+            self.api_key = Some(key);
+        }
+        if let Ok(secret) = std::env::var(format!("{}_WEBHOOK_SECRET", prefix)) {
+            self.webhook_secret = Some(secret);
+        }
+    }
+
     /// Build a full URL by appending a path to the base URL.
     pub fn url(&self, path: &str) -> String {
         let base = self.base_url.trim_end_matches('/');
@@ -225,4 +714,95 @@ mod tests {
         config.verity.api_key = Some("key3".to_string());
         assert!(config.validate_for_production().is_ok());
     }
+
+    #[test]
+    fn test_validate_for_production_with_service_auth() {
+        let mut config = ServiceConfig {
+            service_auth: Some(ServiceAuthConfig::new(AppId::Verity, "")),
+            ..Default::default()
+        };
+        assert!(config.validate_for_production().is_err());
+        assert!(config.token_provider().is_err());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidValue { ref key, .. }) if key == "service_auth"
+        ));
+
+        config.service_auth = Some(ServiceAuthConfig::new(AppId::Verity, "secret"));
+        assert!(config.validate_for_production().is_ok());
+        assert!(config.token_provider().unwrap().is_some());
+    }
+
+    const LAYERED_TOML: &str = r#"
+default_timeout_secs = 20
+
+[verity]
+base_url = "https://verity.staging.example.com"
+
+[profiles.production]
+default_timeout_secs = 10
+
+[profiles.production.verity]
+base_url = "https://verity.example.com"
+"#;
+
+    #[test]
+    fn test_from_toml_with_profile() {
+        let base =
+            ServiceConfig::from_str_with_format(LAYERED_TOML, ConfigFormat::Toml, None).unwrap();
+        assert_eq!(base.default_timeout_secs, 20);
+        assert_eq!(base.verity.base_url, "https://verity.staging.example.com");
+        // Untouched sections keep their defaults.
+        assert_eq!(base.noteman.base_url, "http://localhost:3001");
+
+        let mut toml = LAYERED_TOML.to_string();
+        toml.push_str(
+            "\n[profiles.staging]\nmax_retries = 5\n\n[profiles.staging.noteman]\nbase_url = \"https://noteman.example.com\"\n",
+        );
+        let staging =
+            ServiceConfig::from_str_with_format(&toml, ConfigFormat::Toml, Some("staging"))
+                .unwrap();
+        assert_eq!(staging.max_retries, 5);
+        assert_eq!(staging.noteman.base_url, "https://noteman.example.com");
+        assert_eq!(
+            staging.verity.base_url,
+            "https://verity.staging.example.com"
+        );
+    }
+
+    #[test]
+    fn test_production_profile_requires_credentials() {
+        let result = ServiceConfig::from_str_with_format(
+            LAYERED_TOML,
+            ConfigFormat::Toml,
+            Some("production"),
+        );
+        assert!(matches!(result, Err(ConfigError::MissingEnvVar(_))));
+
+        let result =
+            ServiceConfig::from_str_with_format(LAYERED_TOML, ConfigFormat::Toml, Some("missing"));
+        assert!(matches!(result, Err(ConfigError::UnknownProfile(_))));
+    }
+
+    #[test]
+    fn test_from_yaml() {
+        let yaml = r#"
+verify_tls: false
+shipcheck:
+  base_url: https://shipcheck.example.com
+  api_key: yaml-key
+"#;
+        let config = ServiceConfig::from_str_with_format(yaml, ConfigFormat::Yaml, None).unwrap();
+        assert!(!config.verify_tls);
+        assert_eq!(config.shipcheck.api_key.as_deref(), Some("yaml-key"));
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_values() {
+        let toml = "[noteman]\nbase_url = \"noteman.internal\"\n";
+        assert!(ServiceConfig::from_str_with_format(toml, ConfigFormat::Toml, None).is_err());
+
+        let toml = "default_timeout_secs = 0\n";
+        assert!(ServiceConfig::from_str_with_format(toml, ConfigFormat::Toml, None).is_err());
+    }
 }
@@ -6,14 +6,26 @@
 //! - Verity: Content verification service
 //!
 //! Each client handles authentication, request signing, and error handling for
-//! its respective service. The clients use shared configuration for service URLs.
+//! its respective service. Requests authenticate with a static API key or,
+//! when configured, with platform service tokens (see [`auth`]). The clients use shared configuration for service URLs.
+//! Read endpoints can be served from a response cache (see [`cache`]).
 
+pub mod auth;
+pub mod cache;
 pub mod config;
 pub mod noteman;
+pub mod registry;
+pub mod reload;
 pub mod shipcheck;
+pub mod tenants;
 pub mod verity;
 
-pub use config::ServiceConfig;
+pub use auth::{ClientAuth, ServiceTokenProvider};
+pub use cache::{CacheBackend, MemoryCache, ResponseCache};
+pub use config::{CacheConfig, ServiceAuthConfig, ServiceConfig, TenantConfig};
 pub use noteman::NoteManClient;
+pub use registry::ServiceRegistry;
+pub use reload::{ConfigWatcher, SharedServiceConfig};
 pub use shipcheck::ShipCheckClient;
+pub use tenants::{ConfiguredTenants, EndpointResolver};
 pub use verity::VerityClient;
@@ -4,10 +4,21 @@
 //! Provides methods for meeting transcription, summarization, action item extraction,
 //! and meeting search.
 
-use super::config::ServiceEndpoint;
-use reqwest::Client;
+use super::auth::{authorize, ClientAuth, ServiceTokenProvider};
+use super::cache::{self, CacheLookup, ResponseCache};
+use super::config::{ServiceConfig, ServiceEndpoint};
+use crate::health::MetricsCollector;
+use crate::idempotency;
+use crate::monitor::HealthMonitor;
+use crate::retry::{self, HedgePolicy, RetryConfig};
+use crate::server::ToolContext;
+use crate::trace;
+use platform_auth::AppId;
+use reqwest::header::IF_NONE_MATCH;
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error, instrument, warn};
 
@@ -38,6 +49,17 @@ pub enum NoteManError {
     /// Authentication failed.
     #[error("Authentication failed")]
     AuthenticationFailed,
+
+    /// Service credentials could not be produced.
+    #[error("Failed to mint service token: {0}")]
+    TokenError(String),
+
+    /// Health checks report the service as down.
+    #[error("NoteMan is unavailable (failing health checks since {since}); try again later")]
+    Unavailable {
+        /// When the service became unhealthy (RFC 3339).
+        since: String,
+    },
 }
 
 /// NoteMan service client.
@@ -53,6 +75,27 @@ pub struct NoteManClient {
 
     /// Request timeout.
     timeout: Duration,
+
+    /// Credentials for outgoing requests.
+    auth: ClientAuth,
+
+    /// Calling tool context, used to forward the user's identity.
+    context: Option<ToolContext>,
+
+    /// Response cache for read endpoints.
+    cache: Option<Arc<ResponseCache>>,
+
+    /// Collector for request counts and latencies.
+    metrics: Option<Arc<MetricsCollector>>,
+
+    /// Health monitor consulted before each request.
+    health: Option<Arc<HealthMonitor>>,
+
+    /// Hedging policy for search requests.
+    hedge: Option<Arc<HedgePolicy>>,
+
+    /// Retries of failed requests.
+    retry: Option<RetryConfig>,
 }
 
 impl NoteManClient {
@@ -67,9 +110,108 @@ impl NoteManClient {
             client,
             endpoint,
             timeout,
+            auth: ClientAuth::ApiKey,
+            context: None,
+            cache: None,
+            metrics: None,
+            health: None,
+            hedge: None,
+            retry: None,
+        }
+    }
+
+    /// Create a NoteMan client from shared service configuration.
+    ///
+    /// Attaches a service token provider when service auth is configured, and
+    /// an in-memory response cache when caching is configured.
+    pub fn from_config(config: &ServiceConfig) -> Self {
+        Self::from_config_with_auth(config, ClientAuth::from_config(config))
+    }
+
+    /// Create a NoteMan client from shared service configuration, with
+    /// authentication built once for all clients of that configuration.
+    pub fn from_config_with_auth(config: &ServiceConfig, auth: ClientAuth) -> Self {
+        let client = Self::new(config.noteman.clone(), config.timeout()).with_auth(auth);
+        match config.cache {
+            Some(ref cache) => client.with_cache(Arc::new(ResponseCache::from_config(cache))),
+            None => client,
         }
     }
 
+    /// Authenticate with platform service tokens instead of the static API key.
+    pub fn with_service_auth(self, provider: Arc<ServiceTokenProvider>) -> Self {
+        self.with_auth(ClientAuth::ServiceTokens(provider))
+    }
+
+    /// Set how requests are authenticated.
+    pub fn with_auth(mut self, auth: ClientAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Get how requests are authenticated.
+    pub fn auth(&self) -> &ClientAuth {
+        &self.auth
+    }
+
+    /// Serve read endpoints through a response cache.
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Record request counts and latencies in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Fail fast while `monitor` reports this client's base URL as down.
+    pub fn with_health_monitor(mut self, monitor: Arc<HealthMonitor>) -> Self {
+        self.health = Some(monitor);
+        self
+    }
+
+    /// Hedge search requests according to `policy`.
+    pub fn with_hedging(mut self, policy: Arc<HedgePolicy>) -> Self {
+        self.hedge = Some(policy);
+        self
+    }
+
+    /// Retry requests that fail to send or get a 429 or 5xx response
+    /// according to `config`.
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = Some(config);
+        self
+    }
+
+    /// Get a copy of this client that acts on behalf of the tool caller.
+    ///
+    /// The caller's identity is forwarded as a cross-app token when service
+    /// auth is enabled.
+    pub fn for_context(&self, context: &ToolContext) -> Self {
+        let mut client = self.clone();
+        client.context = Some(context.clone());
+        client
+    }
+
+    /// Build an authenticated request for a NoteMan API path.
+    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, NoteManError> {
+        let request = self
+            .client
+            .request(method, self.endpoint.url(path))
+            .timeout(self.timeout);
+        let request = trace::inject(request, self.context.as_ref());
+        authorize(
+            request,
+            &self.endpoint,
+            &self.auth,
+            AppId::NoteMan,
+            self.context.as_ref(),
+        )
+        .map_err(|e| NoteManError::TokenError(e.to_string()))
+    }
+
     /// Start transcription for a meeting.
     ///
     /// Initiates audio/video transcription for the specified meeting.
@@ -81,14 +223,11 @@ impl NoteManClient {
     ) -> Result<TranscribeMeetingResponse, NoteManError> {
         debug!("Starting transcription for meeting {}", params.meeting_id);
 
-        let url = self.endpoint.url("/api/v1/meetings/transcribe");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/meetings/transcribe")?
+            .json(&params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -102,14 +241,11 @@ impl NoteManClient {
     ) -> Result<SummarizeMeetingResponse, NoteManError> {
         debug!("Generating summary for meeting {}", params.meeting_id);
 
-        let url = self.endpoint.url("/api/v1/meetings/summarize");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/meetings/summarize")?
+            .json(&params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -123,14 +259,11 @@ impl NoteManClient {
     ) -> Result<ExtractActionItemsResponse, NoteManError> {
         debug!("Extracting action items from meeting {}", params.meeting_id);
 
-        let url = self.endpoint.url("/api/v1/meetings/action-items");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/meetings/action-items")?
+            .json(&params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -144,14 +277,11 @@ impl NoteManClient {
     ) -> Result<SearchMeetingsResponse, NoteManError> {
         debug!("Searching meetings with query: {}", params.query);
 
-        let url = self.endpoint.url("/api/v1/meetings/search");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/meetings/search")?
+            .json(&params);
 
-        let response = request.send().await?;
+        let response = self.send_hedged(request).await?;
         self.handle_response(response).await
     }
 
@@ -162,22 +292,17 @@ impl NoteManClient {
     pub async fn get_meeting(&self, meeting_id: &str) -> Result<Meeting, NoteManError> {
         debug!("Fetching meeting {}", meeting_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/meetings/{}", meeting_id));
-        let mut request = self.client.get(&url);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
-
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err(NoteManError::MeetingNotFound(meeting_id.to_string()));
-        }
+        let path = format!("/api/v1/meetings/{}", meeting_id);
+        let request = self.request(Method::GET, &path)?;
 
-        self.handle_response(response).await
+        self.send_cached("get_meeting", &path, request)
+            .await
+            .map_err(|e| match e {
+                NoteManError::ApiError { status: 404, .. } => {
+                    NoteManError::MeetingNotFound(meeting_id.to_string())
+                }
+                e => e,
+            })
     }
 
     /// Get meeting content for verification.
@@ -194,17 +319,15 @@ impl NoteManClient {
             content_type, meeting_id
         );
 
-        let url = self.endpoint.url(&format!(
-            "/api/v1/meetings/{}/content?type={}",
-            meeting_id, content_type
-        ));
-        let mut request = self.client.get(&url);
+        let request = self.request(
+            Method::GET,
+            &format!(
+                "/api/v1/meetings/{}/content?type={}",
+                meeting_id, content_type
+            ),
+        )?;
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -218,16 +341,12 @@ impl NoteManClient {
     ) -> Result<Vec<Decision>, NoteManError> {
         debug!("Fetching decisions for meeting {}", meeting_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/meetings/{}/decisions", meeting_id));
-        let mut request = self.client.get(&url);
+        let request = self.request(
+            Method::GET,
+            &format!("/api/v1/meetings/{}/decisions", meeting_id),
+        )?;
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: DecisionsResponse = self.handle_response(response).await?;
         Ok(result.decisions)
     }
@@ -242,15 +361,156 @@ impl NoteManClient {
     ) -> Result<CreateDiscussionResponse, NoteManError> {
         debug!("Creating discussion in workspace {}", params.workspace_id);
 
-        let url = self.endpoint.url("/api/v1/discussions");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/discussions")?
+            .json(&params);
+        let request =
+            idempotency::apply(request, self.context.as_ref(), "create_discussion", &params);
+
+        let response = self.send(request).await?;
+        self.handle_response(response).await
+    }
+
+    /// Delete a discussion.
+    ///
+    /// Used to roll back a discussion created by a workflow that failed later
+    /// on. A discussion that no longer exists counts as deleted.
+    #[instrument(skip(self), fields(discussion_id = %discussion_id))]
+    pub async fn delete_discussion(&self, discussion_id: &str) -> Result<(), NoteManError> {
+        debug!("Deleting discussion {}", discussion_id);
+
+        let path = format!("/api/v1/discussions/{}", discussion_id);
+        let request = self.request(Method::DELETE, &path)?;
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
+        let response = self.send(request).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(());
         }
+        self.check_response(response).await.map(|_| ())
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Send a request, retrying it if a retry configuration is attached.
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, NoteManError> {
+        match self.retry {
+            Some(ref config) => {
+                retry::retry_request(
+                    config,
+                    request,
+                    |request| self.send_once(request),
+                    |e| matches!(e, NoteManError::RequestFailed(_)),
+                )
+                .await
+            }
+            None => self.send_once(request).await,
+        }
+    }
+
+    /// Send a request once, recording its latency and outcome.
+    ///
+    /// Fails without sending while the health monitor reports this client's
+    /// NoteMan deployment as down.
+    async fn send_once(&self, request: RequestBuilder) -> Result<reqwest::Response, NoteManError> {
+        if let Some(since) = self
+            .health
+            .as_ref()
+            .and_then(|h| h.down_since_url(&self.endpoint.base_url))
+        {
+            return Err(NoteManError::Unavailable {
+                since: since.to_rfc3339(),
+            });
+        }
+
+        let start = Instant::now();
+        let result = request.send().await;
+        if let Some(ref metrics) = self.metrics {
+            let success = result.as_ref().is_ok_and(|response| {
+                response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED
+            });
+            metrics.record_request("noteman", start.elapsed().as_millis() as u64, success);
+        }
+        Ok(result?)
+    }
+
+    /// Send a read request, hedging it if a policy is attached.
+    async fn send_hedged(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<reqwest::Response, NoteManError> {
+        let Some(ref policy) = self.hedge else {
+            return self.send(request).await;
+        };
+        if request.try_clone().is_none() {
+            return self.send(request).await;
+        }
+
+        let (result, outcome) = retry::hedge(policy, retry::accept_response, || {
+            let request = request.try_clone().expect("request body is cloneable");
+            self.send(request)
+        })
+        .await;
+        if let (Some(ref metrics), Some(winner)) = (&self.metrics, outcome.winner()) {
+            metrics.record_hedge("noteman", winner);
+        }
+        result
+    }
+
+    /// Send a read request through the response cache, if one is attached.
+    ///
+    /// Fresh entries are returned without contacting NoteMan; expired entries
+    /// with an ETag are revalidated with `If-None-Match`.
+    async fn send_cached<T>(
+        &self,
+        operation: &str,
+        resource: &str,
+        request: RequestBuilder,
+    ) -> Result<T, NoteManError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some(cache) = self.cache.as_deref() else {
+            return self.handle_response(self.send(request).await?).await;
+        };
+
+        let scope = cache::scope(&self.endpoint, self.auth.provider(), self.context.as_ref());
+        let key = cache.key(AppId::NoteMan, operation, &scope, resource);
+        let decode = |body: serde_json::Value| {
+            serde_json::from_value(body).map_err(|e| NoteManError::InvalidResponse(e.to_string()))
+        };
+
+        let mut unconditional = None;
+        let request = match cache.lookup(&key) {
+            CacheLookup::Fresh(body) => {
+                debug!("Serving {} from cache", operation);
+                return decode(body);
+            }
+            CacheLookup::Stale(etag) => {
+                unconditional = request.try_clone();
+                request.header(IF_NONE_MATCH, etag)
+            }
+            CacheLookup::Miss => request,
+        };
+
+        let mut response = self.send(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(body) = cache.revalidate(&key, operation) {
+                debug!("Revalidated cached {}", operation);
+                return decode(body);
+            }
+            // The entry was evicted while revalidating; fetch the body again.
+            if let Some(request) = unconditional {
+                debug!(
+                    "Cached {} evicted during revalidation, refetching",
+                    operation
+                );
+                response = self.send(request).await?;
+            }
+        }
+
+        let etag = cache::etag(&response);
+        let body: serde_json::Value = self.handle_response(response).await?;
+        let value = decode(body.clone())?;
+        cache.store(&key, operation, etag, body);
+        Ok(value)
     }
 
     /// Handle API response and parse JSON.
@@ -258,6 +518,18 @@ impl NoteManClient {
     where
         T: for<'de> Deserialize<'de>,
     {
+        self.check_response(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| NoteManError::InvalidResponse(e.to_string()))
+    }
+
+    /// Turn an unsuccessful API response into an error.
+    async fn check_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, NoteManError> {
         let status = response.status();
 
         if status == reqwest::StatusCode::UNAUTHORIZED {
@@ -277,10 +549,7 @@ impl NoteManClient {
             });
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| NoteManError::InvalidResponse(e.to_string()))
+        Ok(response)
     }
 }
 
@@ -441,6 +710,14 @@ pub struct SearchMeetingsParams {
     #[serde(default)]
     pub participants: Vec<String>,
 
+    /// Only include meetings of this project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+
+    /// Only include meetings with all of these tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
     /// Maximum results.
     #[serde(default = "default_limit")]
     pub limit: u32,
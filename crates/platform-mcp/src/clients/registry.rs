@@ -0,0 +1,611 @@
+//! Service registry for tool dependencies.
+//!
+//! A [`ServiceRegistry`] owns the service configuration and the clients built
+//! from it. Tools receive the registry in their constructors instead of
+//! reaching for process-global clients, so each server instance (or test) can
+//! point at its own endpoints.
+//!
+//! Calls made for an organization with a dedicated deployment (see
+//! [`super::tenants`]) are routed to that organization's endpoints; the
+//! clients for each such organization are built once and cached. The cache
+//! keeps the most recently used organizations, up to
+//! [`ServiceRegistry::with_tenant_cache_capacity`]. A dedicated endpoint's
+//! own API key takes precedence over service tokens.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::{ServiceConfig, ServiceRegistry};
+//! use platform_mcp::tools::all_tools;
+//! use std::sync::Arc;
+//!
+//! let services = Arc::new(ServiceRegistry::new(ServiceConfig::from_env()));
+//! let tools = all_tools(&services);
+//! ```
+
+use super::auth::ClientAuth;
+use super::cache::{Lru, ResponseCache};
+use super::config::{ConfigError, ServiceConfig};
+use super::config::{ServiceEndpoint, TenantConfig};
+use super::noteman::NoteManClient;
+use super::reload::{ReloadableClient, SharedServiceConfig};
+use super::shipcheck::ShipCheckClient;
+use super::tenants::{resolve_tenant, ConfiguredTenants, EndpointResolver};
+use super::verity::VerityClient;
+use crate::health::MetricsCollector;
+use crate::idempotency::{IdempotencyStore, MemoryIdempotencyStore};
+use crate::monitor::HealthMonitor;
+use crate::retry::{HedgeConfig, HedgePolicy, Jitter, RetryBudgets, RetryConfig};
+use crate::server::ToolContext;
+use crate::workflow::runs::{MemoryRunStore, RunStore};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Clients built from one configuration, sharing one service token
+/// provider and therefore its token cache.
+#[derive(Clone)]
+struct Clients {
+    noteman: NoteManClient,
+    shipcheck: ShipCheckClient,
+    verity: VerityClient,
+
+    /// Authentication shared by the clients.
+    auth: ClientAuth,
+}
+
+impl Clients {
+    fn from_config(config: &ServiceConfig) -> Self {
+        let auth = ClientAuth::from_config(config);
+        Self {
+            noteman: NoteManClient::from_config_with_auth(config, auth.clone()),
+            shipcheck: ShipCheckClient::from_config_with_auth(config, auth.clone()),
+            verity: VerityClient::from_config_with_auth(config, auth.clone()),
+            auth,
+        }
+    }
+
+    /// Build the clients for an organization's dedicated deployments.
+    ///
+    /// A dedicated endpoint with its own API key authenticates with that
+    /// key; the others use `auth`, the shared service auth. Tenant overrides
+    /// never change the service auth configuration, so sharing its provider
+    /// also shares its token cache instead of minting tokens per tenant.
+    fn for_tenant(tenant: &TenantConfig, base: &ServiceConfig, auth: &ClientAuth) -> Self {
+        let config = tenant.apply(base);
+        let auth_for = |endpoint: &Option<ServiceEndpoint>| {
+            if endpoint.as_ref().is_some_and(|e| e.api_key.is_some()) {
+                ClientAuth::ApiKey
+            } else {
+                auth.clone()
+            }
+        };
+        Self {
+            noteman: NoteManClient::from_config_with_auth(&config, auth_for(&tenant.noteman)),
+            shipcheck: ShipCheckClient::from_config_with_auth(&config, auth_for(&tenant.shipcheck)),
+            verity: VerityClient::from_config_with_auth(&config, auth_for(&tenant.verity)),
+            auth: auth.clone(),
+        }
+    }
+}
+
+/// Default number of organizations whose routing is cached.
+const DEFAULT_TENANT_CACHE_CAPACITY: usize = 1024;
+
+/// Cached routing decisions by organization, least recently used evicted
+/// first.
+///
+/// Each decision records the configuration generation it was made for.
+struct TenantCache(Lru<Uuid, (u64, Option<Clients>)>);
+
+impl TenantCache {
+    fn new(capacity: usize) -> Self {
+        Self(Lru::new(capacity))
+    }
+
+    /// Get the decision for `org_id` if it was made for `generation`.
+    fn get(&mut self, org_id: Uuid, generation: u64) -> Option<Option<Clients>> {
+        match self.0.get(&org_id)? {
+            (built_for, clients) if *built_for == generation => Some(clients.clone()),
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, org_id: Uuid, generation: u64, clients: Option<Clients>) {
+        self.0.insert(org_id, (generation, clients));
+    }
+
+    fn remove(&mut self, org_id: Uuid) {
+        self.0.remove(&org_id);
+    }
+}
+
+/// Clients for the platform services, built from shared configuration.
+///
+/// Clients are rebuilt automatically when the shared configuration is
+/// replaced (see [`super::reload`]).
+pub struct ServiceRegistry {
+    /// Configuration the clients are built from.
+    config: SharedServiceConfig,
+
+    /// Clients for the shared deployments.
+    clients: ReloadableClient<Clients>,
+
+    /// Resolves dedicated endpoints per organization.
+    resolver: Arc<dyn EndpointResolver>,
+
+    /// Per-organization clients.
+    tenants: Mutex<TenantCache>,
+
+    /// Response cache shared by all clients, replacing configured caches.
+    cache: Option<Arc<ResponseCache>>,
+
+    /// Results of mutating workflow steps, by idempotency key.
+    idempotency: Arc<dyn IdempotencyStore>,
+
+    /// Recorded workflow runs.
+    runs: Arc<dyn RunStore>,
+
+    /// Collector for request metrics.
+    metrics: Option<Arc<MetricsCollector>>,
+
+    /// Health monitor for the shared deployments.
+    health: Option<Arc<HealthMonitor>>,
+
+    /// Retry budgets per service, shared by all callers.
+    retry_budgets: Arc<RetryBudgets>,
+
+    /// Hedging configuration for search requests, if enabled.
+    hedge_config: Option<HedgeConfig>,
+
+    /// Hedging policies for search requests, by service.
+    hedging: HashMap<&'static str, Arc<HedgePolicy>>,
+}
+
+impl ServiceRegistry {
+    /// Create a registry from a fixed configuration.
+    pub fn new(config: ServiceConfig) -> Self {
+        Self::from_shared(SharedServiceConfig::new(config))
+    }
+
+    /// Create a registry that follows a shared, reloadable configuration.
+    pub fn from_shared(config: SharedServiceConfig) -> Self {
+        Self {
+            clients: ReloadableClient::new(config.clone(), Clients::from_config),
+            config,
+            resolver: Arc::new(ConfiguredTenants),
+            tenants: Mutex::new(TenantCache::new(DEFAULT_TENANT_CACHE_CAPACITY)),
+            cache: None,
+            idempotency: Arc::new(MemoryIdempotencyStore::new()),
+            runs: Arc::new(MemoryRunStore::new()),
+            metrics: None,
+            health: None,
+            retry_budgets: Arc::new(RetryBudgets::default()),
+            hedge_config: None,
+            hedging: HashMap::new(),
+        }
+    }
+
+    /// Use a custom resolver for per-organization endpoints.
+    ///
+    /// Defaults to [`ConfiguredTenants`], which reads `ServiceConfig::tenants`.
+    pub fn with_resolver(mut self, resolver: Arc<dyn EndpointResolver>) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Cache the routing of at most `capacity` organizations (default 1024).
+    pub fn with_tenant_cache_capacity(self, capacity: usize) -> Self {
+        *self.tenant_cache() = TenantCache::new(capacity);
+        self
+    }
+
+    /// Share one response cache, e.g. with a custom backend, across all
+    /// clients instead of the in-memory caches built from configuration.
+    ///
+    /// Entries are keyed by app, organization and credentials, so sharing the
+    /// cache across tenants is safe.
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Record request counts and latencies of all clients in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Fail fast on calls to services that `monitor` reports as down.
+    ///
+    /// Applies to every deployment the monitor checks, including when an
+    /// organization's routing resolves to one; calls to dedicated deployments
+    /// the monitor does not check are always sent.
+    pub fn with_health_monitor(mut self, monitor: Arc<HealthMonitor>) -> Self {
+        self.health = Some(monitor);
+        self
+    }
+
+    /// Share `budgets` with other registries instead of the default
+    /// per-registry budgets.
+    pub fn with_retry_budgets(mut self, budgets: Arc<RetryBudgets>) -> Self {
+        self.retry_budgets = budgets;
+        self.build_hedging();
+        self
+    }
+
+    /// Hedge search requests to every service according to `config`.
+    ///
+    /// Each service tracks its own latencies, and hedges are drawn from the
+    /// service's retry budget, including budgets set later with
+    /// [`with_retry_budgets`](Self::with_retry_budgets).
+    pub fn with_hedging(mut self, config: HedgeConfig) -> Self {
+        self.hedge_config = Some(config);
+        self.build_hedging();
+        self
+    }
+
+    /// Build the hedging policies from the configuration and current budgets.
+    fn build_hedging(&mut self) {
+        let Some(ref config) = self.hedge_config else {
+            return;
+        };
+        self.hedging = ["noteman", "shipcheck", "verity"]
+            .into_iter()
+            .map(|service| {
+                let policy =
+                    HedgePolicy::new(config.clone()).with_budget(self.retry_budgets.get(service));
+                (service, Arc::new(policy))
+            })
+            .collect();
+    }
+
+    /// Get the retry configuration for calls to `service`.
+    ///
+    /// Allows `max_retries` retries from the configuration, with full
+    /// jitter, drawn from the service's shared retry budget. The clients
+    /// handed out by the registry retry their requests with it.
+    pub fn retry_config(&self, service: &str) -> RetryConfig {
+        RetryConfig {
+            max_attempts: self.config.current().max_retries + 1,
+            ..RetryConfig::standard()
+        }
+        .with_jitter(Jitter::Full)
+        .with_budget(self.retry_budgets.get(service))
+    }
+
+    /// Get the health monitor, if one is attached.
+    pub fn health_monitor(&self) -> Option<&Arc<HealthMonitor>> {
+        self.health.as_ref()
+    }
+
+    /// Record workflow step results in `store` instead of in memory.
+    pub fn with_idempotency_store(mut self, store: Arc<dyn IdempotencyStore>) -> Self {
+        self.idempotency = store;
+        self
+    }
+
+    /// Get the store recording workflow step results.
+    pub fn idempotency(&self) -> &dyn IdempotencyStore {
+        self.idempotency.as_ref()
+    }
+
+    /// Record workflow runs in `store` instead of in memory, e.g. a
+    /// [`FileRunStore`](crate::workflow::FileRunStore) so that interrupted
+    /// runs can be resumed after a restart.
+    pub fn with_run_store(mut self, store: Arc<dyn RunStore>) -> Self {
+        self.runs = store;
+        self
+    }
+
+    /// Get the store recording workflow runs.
+    pub fn runs(&self) -> &dyn RunStore {
+        self.runs.as_ref()
+    }
+
+    /// Drop cached clients for an organization so its endpoints are
+    /// resolved again on the next call.
+    pub fn invalidate_org(&self, org_id: Uuid) {
+        self.tenant_cache().remove(org_id);
+    }
+
+    /// Create a registry from environment variables.
+    pub fn from_env() -> Self {
+        Self::new(ServiceConfig::from_env())
+    }
+
+    /// Create a registry using [`ServiceConfig::load`].
+    pub fn load() -> Result<Self, ConfigError> {
+        ServiceConfig::load().map(Self::new)
+    }
+
+    /// Get the shared configuration, e.g. to attach a [`super::ConfigWatcher`].
+    pub fn config(&self) -> &SharedServiceConfig {
+        &self.config
+    }
+
+    /// Get a NoteMan client acting on behalf of the tool caller.
+    ///
+    /// Routed to the caller's organization deployment when it has one.
+    pub fn noteman(&self, context: &ToolContext) -> NoteManClient {
+        let client = match self.tenant(context) {
+            Some(tenant) => tenant.noteman,
+            None => self.clients.get().noteman,
+        };
+        let client = match self.health {
+            Some(ref monitor) => client.with_health_monitor(monitor.clone()),
+            None => client,
+        };
+        let client = match self.hedging.get("noteman") {
+            Some(policy) => client.with_hedging(policy.clone()),
+            None => client,
+        }
+        .with_retry(self.retry_config("noteman"));
+        self.instrument(
+            client,
+            |c, cache| c.with_cache(cache),
+            |c, m| c.with_metrics(m),
+        )
+        .for_context(context)
+    }
+
+    /// Get a ShipCheck client acting on behalf of the tool caller.
+    ///
+    /// Routed to the caller's organization deployment when it has one.
+    pub fn shipcheck(&self, context: &ToolContext) -> ShipCheckClient {
+        let client = match self.tenant(context) {
+            Some(tenant) => tenant.shipcheck,
+            None => self.clients.get().shipcheck,
+        };
+        let client = match self.health {
+            Some(ref monitor) => client.with_health_monitor(monitor.clone()),
+            None => client,
+        };
+        let client = match self.hedging.get("shipcheck") {
+            Some(policy) => client.with_hedging(policy.clone()),
+            None => client,
+        }
+        .with_retry(self.retry_config("shipcheck"));
+        self.instrument(
+            client,
+            |c, cache| c.with_cache(cache),
+            |c, m| c.with_metrics(m),
+        )
+        .for_context(context)
+    }
+
+    /// Get a Verity client acting on behalf of the tool caller.
+    ///
+    /// Routed to the caller's organization deployment when it has one.
+    pub fn verity(&self, context: &ToolContext) -> VerityClient {
+        let client = match self.tenant(context) {
+            Some(tenant) => tenant.verity,
+            None => self.clients.get().verity,
+        };
+        let client = match self.health {
+            Some(ref monitor) => client.with_health_monitor(monitor.clone()),
+            None => client,
+        };
+        let client = match self.hedging.get("verity") {
+            Some(policy) => client.with_hedging(policy.clone()),
+            None => client,
+        }
+        .with_retry(self.retry_config("verity"));
+        self.instrument(
+            client,
+            |c, cache| c.with_cache(cache),
+            |c, m| c.with_metrics(m),
+        )
+        .for_context(context)
+    }
+
+    /// Attach the shared response cache and metrics collector, if set.
+    fn instrument<T>(
+        &self,
+        mut client: T,
+        with_cache: fn(T, Arc<ResponseCache>) -> T,
+        with_metrics: fn(T, Arc<MetricsCollector>) -> T,
+    ) -> T {
+        if let Some(ref cache) = self.cache {
+            client = with_cache(client, cache.clone());
+        }
+        if let Some(ref metrics) = self.metrics {
+            client = with_metrics(client, metrics.clone());
+        }
+        client
+    }
+
+    /// Get the dedicated clients for the caller's organization, if any.
+    fn tenant(&self, context: &ToolContext) -> Option<Clients> {
+        let org_id = context.org_id?;
+        let generation = self.config.generation();
+        if let Some(clients) = self.tenant_cache().get(org_id, generation) {
+            return clients;
+        }
+
+        // Resolve without holding the lock: resolvers may be slow, and other
+        // organizations should not wait for this one.
+        let config = self.config.current();
+        let clients = resolve_tenant(self.resolver.as_ref(), org_id, &config)
+            .map(|tenant| Clients::for_tenant(&tenant, &config, &self.clients.get().auth));
+        self.tenant_cache()
+            .insert(org_id, generation, clients.clone());
+        clients
+    }
+
+    fn tenant_cache(&self) -> std::sync::MutexGuard<'_, TenantCache> {
+        self.tenants.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ServiceRegistry {
+    /// Registry using the local development defaults.
+    fn default() -> Self {
+        Self::new(ServiceConfig::default())
+    }
+}
+
+impl std::fmt::Debug for ServiceRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceRegistry")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use platform_org::Tier;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Resolver that counts lookups and gives every org a dedicated timeout.
+    #[derive(Default)]
+    struct CountingResolver {
+        calls: AtomicUsize,
+    }
+
+    impl EndpointResolver for CountingResolver {
+        fn resolve(&self, _org_id: Uuid, _config: &ServiceConfig) -> Option<TenantConfig> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Some(TenantConfig {
+                timeout_secs: Some(90),
+                ..TenantConfig::new(Tier::EnterprisePlus)
+            })
+        }
+    }
+
+    #[test]
+    fn test_tenant_clients_are_cached_per_generation() {
+        let resolver = Arc::new(CountingResolver::default());
+        let registry = ServiceRegistry::default().with_resolver(resolver.clone());
+
+        let mut context = ToolContext::empty();
+        registry.verity(&context);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 0);
+
+        context.org_id = Some(Uuid::now_v7());
+        registry.verity(&context);
+        registry.noteman(&context);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 1);
+
+        registry.config().replace(ServiceConfig::default());
+        registry.shipcheck(&context);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 2);
+
+        registry.invalidate_org(context.org_id.unwrap());
+        registry.shipcheck(&context);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_tenant_cache_evicts_least_recently_used() {
+        let resolver = Arc::new(CountingResolver::default());
+        let registry = ServiceRegistry::default()
+            .with_resolver(resolver.clone())
+            .with_tenant_cache_capacity(2);
+
+        let contexts: Vec<ToolContext> = (0..3)
+            .map(|_| {
+                let mut context = ToolContext::empty();
+                context.org_id = Some(Uuid::now_v7());
+                context
+            })
+            .collect();
+        registry.verity(&contexts[0]);
+        registry.verity(&contexts[1]);
+        // Using the first org makes the second the eviction candidate.
+        registry.verity(&contexts[0]);
+        registry.verity(&contexts[2]);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 3);
+
+        registry.verity(&contexts[0]);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 3);
+        registry.verity(&contexts[1]);
+        assert_eq!(resolver.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_tenant_api_key_takes_precedence_over_service_auth() {
+        let base = ServiceConfig {
+            service_auth: Some(crate::clients::ServiceAuthConfig::new(
+                platform_auth::AppId::Verity,
+                "secret",
+            )),
+            ..ServiceConfig::default()
+        };
+        let tenant = TenantConfig {
+            verity: Some(ServiceEndpoint {
+                base_url: "https://verity.acme.internal".to_string(),
+                api_key: Some("acme-key".to_string()),
+                webhook_secret: None,
+            }),
+            ..TenantConfig::new(Tier::Enterprise)
+        };
+
+        let clients = Clients::for_tenant(&tenant, &base, &ClientAuth::from_config(&base));
+        assert!(matches!(clients.verity.auth(), ClientAuth::ApiKey));
+        assert!(matches!(
+            clients.noteman.auth(),
+            ClientAuth::ServiceTokens(_)
+        ));
+    }
+
+    #[test]
+    fn test_tenant_clients_share_the_token_provider() {
+        let org_id = Uuid::now_v7();
+        let mut config = ServiceConfig {
+            service_auth: Some(crate::clients::ServiceAuthConfig::new(
+                platform_auth::AppId::Verity,
+                "secret",
+            )),
+            ..ServiceConfig::default()
+        };
+        config.tenants.insert(
+            org_id,
+            TenantConfig {
+                timeout_secs: Some(90),
+                ..TenantConfig::new(Tier::Enterprise)
+            },
+        );
+        let registry = ServiceRegistry::new(config);
+
+        let mut context = ToolContext::empty();
+        let shared = registry.noteman(&context);
+        context.org_id = Some(org_id);
+        let tenant = registry.noteman(&context);
+
+        let (ClientAuth::ServiceTokens(shared), ClientAuth::ServiceTokens(tenant)) =
+            (shared.auth(), tenant.auth())
+        else {
+            panic!("Expected service token auth");
+        };
+        assert!(Arc::ptr_eq(shared, tenant));
+    }
+
+    #[test]
+    fn test_retry_config_shares_service_budget() {
+        let services = ServiceRegistry::default();
+        let first = services.retry_config("verity");
+        let second = services.retry_config("verity");
+
+        assert_eq!(first.max_attempts, 4);
+        assert!(Arc::ptr_eq(
+            first.budget.as_ref().unwrap(),
+            second.budget.as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_hedging_uses_budgets_set_later() {
+        let budgets = Arc::new(RetryBudgets::default());
+        let services = ServiceRegistry::default()
+            .with_hedging(HedgeConfig::default())
+            .with_retry_budgets(budgets.clone());
+
+        for service in ["noteman", "shipcheck", "verity"] {
+            let policy = &services.hedging[service];
+            assert!(Arc::ptr_eq(policy.budget().unwrap(), &budgets.get(service)));
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Hot-reloadable service configuration.
+//!
+//! [`SharedServiceConfig`] holds the current [`ServiceConfig`] and bumps a
+//! generation counter each time it is replaced. [`ReloadableClient`] rebuilds
+//! its client whenever the generation changes, so endpoints, keys and timeouts
+//! swapped in at runtime reach the next tool call without a restart.
+//!
+//! [`ConfigWatcher`] polls a configuration file and swaps in the new settings
+//! when it changes. Invalid edits are logged and the previous configuration
+//! stays active.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::reload::{ConfigWatcher, SharedServiceConfig};
+//! use platform_mcp::clients::ServiceConfig;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = ServiceConfig::from_file("relay.toml", Some("production"))?;
+//! let shared = SharedServiceConfig::new(config);
+//!
+//! // Reload whenever relay.toml changes
+//! let _watcher = ConfigWatcher::new("relay.toml")
+//!     .with_profile("production")
+//!     .spawn(shared.clone())
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::config::{ConfigError, ConfigFormat, ServiceConfig};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Default interval between config file checks.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shared, swappable service configuration.
+///
+/// Cloning is cheap; all clones observe the same configuration.
+#[derive(Clone)]
+pub struct SharedServiceConfig {
+    inner: Arc<SharedInner>,
+}
+
+struct SharedInner {
+    sender: watch::Sender<Arc<ServiceConfig>>,
+    generation: AtomicU64,
+}
+
+impl SharedServiceConfig {
+    /// Create a shared configuration holding `config`.
+    pub fn new(config: ServiceConfig) -> Self {
+        let (sender, _) = watch::channel(Arc::new(config));
+        Self {
+            inner: Arc::new(SharedInner {
+                sender,
+                generation: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Get the current configuration.
+    pub fn current(&self) -> Arc<ServiceConfig> {
+        self.inner.sender.borrow().clone()
+    }
+
+    /// Get the current generation; it increases on every replacement.
+    pub fn generation(&self) -> u64 {
+        self.inner.generation.load(Ordering::Acquire)
+    }
+
+    /// Replace the configuration, returning the new generation.
+    pub fn replace(&self, config: ServiceConfig) -> u64 {
+        self.inner.sender.send_replace(Arc::new(config));
+        self.inner.generation.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Subscribe to configuration changes.
+    pub fn subscribe(&self) -> watch::Receiver<Arc<ServiceConfig>> {
+        self.inner.sender.subscribe()
+    }
+}
+
+impl std::fmt::Debug for SharedServiceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedServiceConfig")
+            .field("generation", &self.generation())
+            .field("config", &self.current())
+            .finish()
+    }
+}
+
+/// A client that is rebuilt whenever the shared configuration changes.
+pub struct ReloadableClient<T> {
+    /// Configuration source.
+    config: SharedServiceConfig,
+
+    /// Builds a client from a configuration snapshot.
+    build: fn(&ServiceConfig) -> T,
+
+    /// Client built for a given generation.
+    cached: Mutex<Option<(u64, T)>>,
+}
+
+impl<T: Clone> ReloadableClient<T> {
+    /// Create a reloadable client built with `build`.
+    pub fn new(config: SharedServiceConfig, build: fn(&ServiceConfig) -> T) -> Self {
+        Self {
+            config,
+            build,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Get a client for the current configuration.
+    pub fn get(&self) -> T {
+        let generation = self.config.generation();
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+
+        match cached.as_ref() {
+            Some((built_for, client)) if *built_for == generation => client.clone(),
+            _ => {
+                let client = (self.build)(&self.config.current());
+                *cached = Some((generation, client.clone()));
+                client
+            }
+        }
+    }
+}
+
+/// Polls a configuration file and applies changes to a [`SharedServiceConfig`].
+#[derive(Debug, Clone)]
+pub struct ConfigWatcher {
+    /// File to watch.
+    path: PathBuf,
+
+    /// Profile to apply on reload.
+    profile: Option<String>,
+
+    /// Interval between checks.
+    interval: Duration,
+}
+
+impl ConfigWatcher {
+    /// Create a watcher for `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            profile: None,
+            interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Set the profile applied when reloading.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Set the polling interval.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Reload the file once and apply it if valid.
+    pub async fn reload(&self, shared: &SharedServiceConfig) -> Result<u64, ConfigError> {
+        let content = tokio::fs::read(&self.path)
+            .await
+            .map_err(|e| self.io_error(e))?;
+        self.apply(&content, shared)
+    }
+
+    /// Parse file content and apply it if valid.
+    fn apply(&self, content: &[u8], shared: &SharedServiceConfig) -> Result<u64, ConfigError> {
+        let content = std::str::from_utf8(content).map_err(|e| self.io_error(e))?;
+        let config = ServiceConfig::from_str_with_format(
+            content,
+            ConfigFormat::from_path(&self.path)?,
+            self.profile.as_deref(),
+        )?;
+        let generation = shared.replace(config);
+        info!(
+            path = %self.path.display(),
+            generation,
+            "Service configuration reloaded"
+        );
+        Ok(generation)
+    }
+
+    fn io_error(&self, e: impl std::fmt::Display) -> ConfigError {
+        ConfigError::Io {
+            path: self.path.display().to_string(),
+            message: e.to_string(),
+        }
+    }
+
+    /// Spawn a background task that reloads the file whenever its content changes.
+    ///
+    /// The current content is read before this returns, so only later edits
+    /// trigger a reload. Abort the returned handle to stop watching.
+    pub async fn spawn(self, shared: SharedServiceConfig) -> JoinHandle<()> {
+        let mut last = tokio::fs::read(&self.path).await.ok();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let current = match tokio::fs::read(&self.path).await {
+                    Ok(content) => content,
+                    Err(e) => {
+                        warn!(path = %self.path.display(), error = %e, "Config file unreadable");
+                        continue;
+                    }
+                };
+                if last.as_ref() == Some(&current) {
+                    continue;
+                }
+
+                if let Err(e) = self.apply(&current, &shared) {
+                    warn!(
+                        path = %self.path.display(),
+                        error = %e,
+                        "Ignoring invalid config change; keeping previous configuration"
+                    );
+                }
+                last = Some(current);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_bumps_generation() {
+        let shared = SharedServiceConfig::new(ServiceConfig::default());
+        let mut rx = shared.subscribe();
+        assert_eq!(shared.generation(), 0);
+
+        let config = ServiceConfig {
+            default_timeout_secs: 5,
+            ..Default::default()
+        };
+        assert_eq!(shared.replace(config), 1);
+        assert_eq!(shared.current().default_timeout_secs, 5);
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(rx.borrow_and_update().default_timeout_secs, 5);
+    }
+
+    #[test]
+    fn test_reloadable_client_rebuilds_on_change() {
+        let shared = SharedServiceConfig::new(ServiceConfig::default());
+        let client = ReloadableClient::new(shared.clone(), |config| config.verity.base_url.clone());
+        assert_eq!(client.get(), "http://localhost:3000");
+
+        let mut config = ServiceConfig::default();
+        config.verity.base_url = "https://verity.example.com".to_string();
+        shared.replace(config);
+        assert_eq!(client.get(), "https://verity.example.com");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_applies_valid_changes_only() {
+        let path = std::env::temp_dir().join(format!("relay-{}.toml", uuid::Uuid::now_v7()));
+        std::fs::write(&path, "default_timeout_secs = 20\n").unwrap();
+
+        let shared = SharedServiceConfig::new(ServiceConfig::from_file(&path, None).unwrap());
+        let handle = ConfigWatcher::new(&path)
+            .with_interval(Duration::from_millis(10))
+            .spawn(shared.clone())
+            .await;
+        let mut rx = shared.subscribe();
+
+        std::fs::write(&path, "default_timeout_secs = 7\n").unwrap();
+        tokio::time::timeout(Duration::from_secs(5), rx.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(shared.current().default_timeout_secs, 7);
+
+        // An invalid edit keeps the previous configuration.
+        std::fs::write(&path, "default_timeout_secs = 0\n").unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(shared.current().default_timeout_secs, 7);
+        assert_eq!(shared.generation(), 1);
+
+        handle.abort();
+        let _ = std::fs::remove_file(&path);
+    }
+}
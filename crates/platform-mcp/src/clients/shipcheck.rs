@@ -4,10 +4,21 @@
 //! Provides methods for code analysis, PR verification, finding search,
 //! and pipeline execution.
 
-use super::config::ServiceEndpoint;
-use reqwest::Client;
+use super::auth::{authorize, ClientAuth, ServiceTokenProvider};
+use super::cache::{self, CacheLookup, ResponseCache};
+use super::config::{ServiceConfig, ServiceEndpoint};
+use crate::health::MetricsCollector;
+use crate::idempotency;
+use crate::monitor::HealthMonitor;
+use crate::retry::{self, HedgePolicy, RetryConfig};
+use crate::server::ToolContext;
+use crate::trace;
+use platform_auth::AppId;
+use reqwest::header::IF_NONE_MATCH;
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error, instrument, warn};
 
@@ -42,6 +53,17 @@ pub enum ShipCheckError {
     /// Authentication failed.
     #[error("Authentication failed")]
     AuthenticationFailed,
+
+    /// Service credentials could not be produced.
+    #[error("Failed to mint service token: {0}")]
+    TokenError(String),
+
+    /// Health checks report the service as down.
+    #[error("ShipCheck is unavailable (failing health checks since {since}); try again later")]
+    Unavailable {
+        /// When the service became unhealthy (RFC 3339).
+        since: String,
+    },
 }
 
 /// ShipCheck service client.
@@ -57,6 +79,27 @@ pub struct ShipCheckClient {
 
     /// Request timeout.
     timeout: Duration,
+
+    /// Credentials for outgoing requests.
+    auth: ClientAuth,
+
+    /// Calling tool context, used to forward the user's identity.
+    context: Option<ToolContext>,
+
+    /// Response cache for read endpoints.
+    cache: Option<Arc<ResponseCache>>,
+
+    /// Collector for request counts and latencies.
+    metrics: Option<Arc<MetricsCollector>>,
+
+    /// Health monitor consulted before each request.
+    health: Option<Arc<HealthMonitor>>,
+
+    /// Hedging policy for search requests.
+    hedge: Option<Arc<HedgePolicy>>,
+
+    /// Retries of failed requests.
+    retry: Option<RetryConfig>,
 }
 
 impl ShipCheckClient {
@@ -71,9 +114,108 @@ impl ShipCheckClient {
             client,
             endpoint,
             timeout,
+            auth: ClientAuth::ApiKey,
+            context: None,
+            cache: None,
+            metrics: None,
+            health: None,
+            hedge: None,
+            retry: None,
+        }
+    }
+
+    /// Create a ShipCheck client from shared service configuration.
+    ///
+    /// Attaches a service token provider when service auth is configured, and
+    /// an in-memory response cache when caching is configured.
+    pub fn from_config(config: &ServiceConfig) -> Self {
+        Self::from_config_with_auth(config, ClientAuth::from_config(config))
+    }
+
+    /// Create a ShipCheck client from shared service configuration, with
+    /// authentication built once for all clients of that configuration.
+    pub fn from_config_with_auth(config: &ServiceConfig, auth: ClientAuth) -> Self {
+        let client = Self::new(config.shipcheck.clone(), config.timeout()).with_auth(auth);
+        match config.cache {
+            Some(ref cache) => client.with_cache(Arc::new(ResponseCache::from_config(cache))),
+            None => client,
         }
     }
 
+    /// Authenticate with platform service tokens instead of the static API key.
+    pub fn with_service_auth(self, provider: Arc<ServiceTokenProvider>) -> Self {
+        self.with_auth(ClientAuth::ServiceTokens(provider))
+    }
+
+    /// Set how requests are authenticated.
+    pub fn with_auth(mut self, auth: ClientAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Get how requests are authenticated.
+    pub fn auth(&self) -> &ClientAuth {
+        &self.auth
+    }
+
+    /// Serve read endpoints through a response cache.
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Record request counts and latencies in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Fail fast while `monitor` reports this client's base URL as down.
+    pub fn with_health_monitor(mut self, monitor: Arc<HealthMonitor>) -> Self {
+        self.health = Some(monitor);
+        self
+    }
+
+    /// Hedge search requests according to `policy`.
+    pub fn with_hedging(mut self, policy: Arc<HedgePolicy>) -> Self {
+        self.hedge = Some(policy);
+        self
+    }
+
+    /// Retry requests that fail to send or get a 429 or 5xx response
+    /// according to `config`.
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = Some(config);
+        self
+    }
+
+    /// Get a copy of this client that acts on behalf of the tool caller.
+    ///
+    /// The caller's identity is forwarded as a cross-app token when service
+    /// auth is enabled.
+    pub fn for_context(&self, context: &ToolContext) -> Self {
+        let mut client = self.clone();
+        client.context = Some(context.clone());
+        client
+    }
+
+    /// Build an authenticated request for a ShipCheck API path.
+    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, ShipCheckError> {
+        let request = self
+            .client
+            .request(method, self.endpoint.url(path))
+            .timeout(self.timeout);
+        let request = trace::inject(request, self.context.as_ref());
+        authorize(
+            request,
+            &self.endpoint,
+            &self.auth,
+            AppId::ShipCheck,
+            self.context.as_ref(),
+        )
+        .map_err(|e| ShipCheckError::TokenError(e.to_string()))
+    }
+
     /// Analyze code in a repository.
     ///
     /// Performs static analysis on code to find bugs, security issues, and style problems.
@@ -87,14 +229,9 @@ impl ShipCheckClient {
             params.repository_id
         );
 
-        let url = self.endpoint.url("/api/v1/analyze");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self.request(Method::POST, "/api/v1/analyze")?.json(&params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -111,14 +248,11 @@ impl ShipCheckClient {
             params.pr_number, params.repository_id
         );
 
-        let url = self.endpoint.url("/api/v1/verify-pr");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/verify-pr")?
+            .json(&params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -132,14 +266,11 @@ impl ShipCheckClient {
     ) -> Result<SearchFindingsResponse, ShipCheckError> {
         debug!("Searching findings with query: {}", params.query);
 
-        let url = self.endpoint.url("/api/v1/findings/search");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/findings/search")?
+            .json(&params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send_hedged(request).await?;
         self.handle_response(response).await
     }
 
@@ -153,14 +284,11 @@ impl ShipCheckClient {
     ) -> Result<RunPipelineResponse, ShipCheckError> {
         debug!("Running pipeline for repository {}", params.repository_id);
 
-        let url = self.endpoint.url("/api/v1/pipelines/run");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/pipelines/run")?
+            .json(&params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -171,24 +299,31 @@ impl ShipCheckClient {
     pub async fn get_repository(&self, repository_id: &str) -> Result<Repository, ShipCheckError> {
         debug!("Fetching repository {}", repository_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/repositories/{}", repository_id));
-        let mut request = self.client.get(&url);
+        let path = format!("/api/v1/repositories/{}", repository_id);
+        let request = self.request(Method::GET, &path)?;
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        self.send_cached("get_repository", &path, request)
+            .await
+            .map_err(|e| match e {
+                ShipCheckError::ApiError { status: 404, .. } => {
+                    ShipCheckError::RepositoryNotFound(repository_id.to_string())
+                }
+                e => e,
+            })
+    }
 
-        let response = request.send().await?;
+    /// List the repositories in a project.
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn list_repositories(
+        &self,
+        project_id: &str,
+    ) -> Result<ProjectRepositories, ShipCheckError> {
+        debug!("Listing repositories of project {}", project_id);
 
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err(ShipCheckError::RepositoryNotFound(
-                repository_id.to_string(),
-            ));
-        }
+        let path = format!("/api/v1/projects/{}/repositories", project_id);
+        let request = self.request(Method::GET, &path)?;
 
-        self.handle_response(response).await
+        self.send_cached("list_repositories", &path, request).await
     }
 
     /// Get finding details.
@@ -198,22 +333,17 @@ impl ShipCheckClient {
     pub async fn get_finding(&self, finding_id: &str) -> Result<Finding, ShipCheckError> {
         debug!("Fetching finding {}", finding_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/findings/{}", finding_id));
-        let mut request = self.client.get(&url);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
-
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err(ShipCheckError::FindingNotFound(finding_id.to_string()));
-        }
+        let path = format!("/api/v1/findings/{}", finding_id);
+        let request = self.request(Method::GET, &path)?;
 
-        self.handle_response(response).await
+        self.send_cached("get_finding", &path, request)
+            .await
+            .map_err(|e| match e {
+                ShipCheckError::ApiError { status: 404, .. } => {
+                    ShipCheckError::FindingNotFound(finding_id.to_string())
+                }
+                e => e,
+            })
     }
 
     /// Get repository documentation.
@@ -227,19 +357,16 @@ impl ShipCheckClient {
     ) -> Result<RepositoryDocs, ShipCheckError> {
         debug!("Fetching documentation for repository {}", repository_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/repositories/{}/docs", repository_id));
-        let mut request = self.client.post(&url).json(&serde_json::json!({
+        let path = format!("/api/v1/repositories/{}/docs", repository_id);
+        let body = serde_json::json!({
             "paths": paths
-        }));
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        });
+        let request = self.request(Method::POST, &path)?.json(&body);
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+        // The requested paths are part of the cache key.
+        let resource = format!("{}?{}", path, body);
+        self.send_cached("get_repository_docs", &resource, request)
+            .await
     }
 
     /// Link a decision to a repository.
@@ -252,17 +379,33 @@ impl ShipCheckClient {
     ) -> Result<LinkDecisionResponse, ShipCheckError> {
         debug!("Linking decision to repository {}", params.repository_id);
 
-        let url = self.endpoint.url("/api/v1/decisions/link");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/decisions/link")?
+            .json(&params);
+        let request = idempotency::apply(request, self.context.as_ref(), "link_decision", &params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
+    /// Remove a decision link.
+    ///
+    /// Used to retract a decision linked by a workflow that failed later on.
+    /// A link that no longer exists counts as removed.
+    #[instrument(skip(self), fields(link_id = %link_id))]
+    pub async fn unlink_decision(&self, link_id: &str) -> Result<(), ShipCheckError> {
+        debug!("Removing decision link {}", link_id);
+
+        let path = format!("/api/v1/decisions/link/{}", link_id);
+        let request = self.request(Method::DELETE, &path)?;
+
+        let response = self.send(request).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        self.check_response(response).await.map(|_| ())
+    }
+
     /// Sync action items to repository tasks.
     ///
     /// Creates tasks in the repository from action items.
@@ -273,15 +416,140 @@ impl ShipCheckClient {
     ) -> Result<SyncTasksResponse, ShipCheckError> {
         debug!("Syncing tasks to repository {}", params.repository_id);
 
-        let url = self.endpoint.url("/api/v1/tasks/sync");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/tasks/sync")?
+            .json(&params);
+        let request = idempotency::apply(request, self.context.as_ref(), "sync_tasks", &params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
+        let response = self.send(request).await?;
+        self.handle_response(response).await
+    }
+
+    /// Send a request, retrying it if a retry configuration is attached.
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, ShipCheckError> {
+        match self.retry {
+            Some(ref config) => {
+                retry::retry_request(
+                    config,
+                    request,
+                    |request| self.send_once(request),
+                    |e| matches!(e, ShipCheckError::RequestFailed(_)),
+                )
+                .await
+            }
+            None => self.send_once(request).await,
         }
+    }
 
-        let response = request.send().await?;
-        self.handle_response(response).await
+    /// Send a request once, recording its latency and outcome.
+    ///
+    /// Fails without sending while the health monitor reports this client's
+    /// ShipCheck deployment as down.
+    async fn send_once(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<reqwest::Response, ShipCheckError> {
+        if let Some(since) = self
+            .health
+            .as_ref()
+            .and_then(|h| h.down_since_url(&self.endpoint.base_url))
+        {
+            return Err(ShipCheckError::Unavailable {
+                since: since.to_rfc3339(),
+            });
+        }
+
+        let start = Instant::now();
+        let result = request.send().await;
+        if let Some(ref metrics) = self.metrics {
+            let success = result.as_ref().is_ok_and(|response| {
+                response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED
+            });
+            metrics.record_request("shipcheck", start.elapsed().as_millis() as u64, success);
+        }
+        Ok(result?)
+    }
+
+    /// Send a read request, hedging it if a policy is attached.
+    async fn send_hedged(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<reqwest::Response, ShipCheckError> {
+        let Some(ref policy) = self.hedge else {
+            return self.send(request).await;
+        };
+        if request.try_clone().is_none() {
+            return self.send(request).await;
+        }
+
+        let (result, outcome) = retry::hedge(policy, retry::accept_response, || {
+            let request = request.try_clone().expect("request body is cloneable");
+            self.send(request)
+        })
+        .await;
+        if let (Some(ref metrics), Some(winner)) = (&self.metrics, outcome.winner()) {
+            metrics.record_hedge("shipcheck", winner);
+        }
+        result
+    }
+
+    /// Send a read request through the response cache, if one is attached.
+    ///
+    /// Fresh entries are returned without contacting ShipCheck; expired entries
+    /// with an ETag are revalidated with `If-None-Match`.
+    async fn send_cached<T>(
+        &self,
+        operation: &str,
+        resource: &str,
+        request: RequestBuilder,
+    ) -> Result<T, ShipCheckError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some(cache) = self.cache.as_deref() else {
+            return self.handle_response(self.send(request).await?).await;
+        };
+
+        let scope = cache::scope(&self.endpoint, self.auth.provider(), self.context.as_ref());
+        let key = cache.key(AppId::ShipCheck, operation, &scope, resource);
+        let decode = |body: serde_json::Value| {
+            serde_json::from_value(body).map_err(|e| ShipCheckError::InvalidResponse(e.to_string()))
+        };
+
+        let mut unconditional = None;
+        let request = match cache.lookup(&key) {
+            CacheLookup::Fresh(body) => {
+                debug!("Serving {} from cache", operation);
+                return decode(body);
+            }
+            CacheLookup::Stale(etag) => {
+                unconditional = request.try_clone();
+                request.header(IF_NONE_MATCH, etag)
+            }
+            CacheLookup::Miss => request,
+        };
+
+        let mut response = self.send(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(body) = cache.revalidate(&key, operation) {
+                debug!("Revalidated cached {}", operation);
+                return decode(body);
+            }
+            // The entry was evicted while revalidating; fetch the body again.
+            if let Some(request) = unconditional {
+                debug!(
+                    "Cached {} evicted during revalidation, refetching",
+                    operation
+                );
+                response = self.send(request).await?;
+            }
+        }
+
+        let etag = cache::etag(&response);
+        let body: serde_json::Value = self.handle_response(response).await?;
+        let value = decode(body.clone())?;
+        cache.store(&key, operation, etag, body);
+        Ok(value)
     }
 
     /// Handle API response and parse JSON.
@@ -289,6 +557,18 @@ impl ShipCheckClient {
     where
         T: for<'de> Deserialize<'de>,
     {
+        self.check_response(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| ShipCheckError::InvalidResponse(e.to_string()))
+    }
+
+    /// Turn an unsuccessful API response into an error.
+    async fn check_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, ShipCheckError> {
         let status = response.status();
 
         if status == reqwest::StatusCode::UNAUTHORIZED {
@@ -308,10 +588,7 @@ impl ShipCheckClient {
             });
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| ShipCheckError::InvalidResponse(e.to_string()))
+        Ok(response)
     }
 }
 
@@ -417,11 +694,33 @@ pub struct SearchFindingsParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<String>,
 
+    /// Only include findings reported in this date range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_range: Option<DateRange>,
+
+    /// Only include findings in repositories of this project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+
+    /// Only include findings with all of these tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
     /// Maximum results.
     #[serde(default = "default_limit")]
     pub limit: u32,
 }
 
+/// Date range for filtering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateRange {
+    /// Start date (ISO 8601).
+    pub start: String,
+
+    /// End date (ISO 8601).
+    pub end: String,
+}
+
 /// Response from findings search.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchFindingsResponse {
@@ -580,6 +879,17 @@ pub struct Repository {
     pub status: String,
 }
 
+/// Repositories in a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRepositories {
+    /// Project ID.
+    pub project_id: String,
+
+    /// Repositories in the project.
+    #[serde(default)]
+    pub repositories: Vec<Repository>,
+}
+
 /// Repository documentation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepositoryDocs {
@@ -0,0 +1,100 @@
+//! Per-organization endpoint routing.
+//!
+//! Enterprise organizations may run dedicated Verity, NoteMan or ShipCheck
+//! deployments. An [`EndpointResolver`] maps an org ID to its
+//! [`TenantConfig`], and the [`super::ServiceRegistry`] builds (and caches)
+//! clients from the resulting configuration. Organizations without overrides
+//! use the shared endpoints.
+
+use super::config::{ServiceConfig, TenantConfig};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Resolves service overrides for an organization.
+///
+/// Implement this to source tenant endpoints from somewhere other than the
+/// service configuration, such as an organization database.
+pub trait EndpointResolver: Send + Sync {
+    /// Get the overrides for `org_id`, or `None` to use the shared endpoints.
+    fn resolve(&self, org_id: Uuid, config: &ServiceConfig) -> Option<TenantConfig>;
+}
+
+/// Resolves tenants from [`ServiceConfig::tenants`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfiguredTenants;
+
+impl EndpointResolver for ConfiguredTenants {
+    fn resolve(&self, org_id: Uuid, config: &ServiceConfig) -> Option<TenantConfig> {
+        config.tenants.get(&org_id).cloned()
+    }
+}
+
+/// Resolve the overrides that apply to an organization.
+///
+/// Returns `None` when the organization uses the shared endpoints, including
+/// when a non-enterprise organization has overrides configured.
+pub(crate) fn resolve_tenant(
+    resolver: &dyn EndpointResolver,
+    org_id: Uuid,
+    config: &ServiceConfig,
+) -> Option<TenantConfig> {
+    let tenant = resolver.resolve(org_id, config)?;
+    if !tenant.has_overrides() {
+        return None;
+    }
+    if !tenant.tier.is_enterprise() {
+        warn!(
+            org_id = %org_id,
+            tier = tenant.tier.as_str(),
+            "Ignoring dedicated endpoints for non-enterprise organization"
+        );
+        return None;
+    }
+    Some(tenant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::config::ServiceEndpoint;
+    use platform_org::Tier;
+
+    fn dedicated(tier: Tier) -> TenantConfig {
+        TenantConfig {
+            verity: Some(ServiceEndpoint {
+                base_url: "https://verity.acme.internal".to_string(),
+                api_key: Some("acme-key".to_string()),
+                webhook_secret: None,
+            }),
+            timeout_secs: Some(60),
+            ..TenantConfig::new(tier)
+        }
+    }
+
+    #[test]
+    fn test_enterprise_tenant_overrides_endpoints() {
+        let org_id = Uuid::now_v7();
+        let mut config = ServiceConfig::default();
+        config.tenants.insert(org_id, dedicated(Tier::Enterprise));
+
+        let resolved = resolve_tenant(&ConfiguredTenants, org_id, &config)
+            .unwrap()
+            .apply(&config);
+        assert_eq!(resolved.verity.base_url, "https://verity.acme.internal");
+        assert_eq!(resolved.verity.api_key.as_deref(), Some("acme-key"));
+        assert_eq!(resolved.default_timeout_secs, 60);
+        // Apps without overrides keep the shared endpoint.
+        assert_eq!(resolved.noteman.base_url, config.noteman.base_url);
+    }
+
+    #[test]
+    fn test_unknown_or_non_enterprise_org_uses_shared() {
+        let org_id = Uuid::now_v7();
+        let mut config = ServiceConfig::default();
+        assert!(resolve_tenant(&ConfiguredTenants, org_id, &config).is_none());
+
+        config.tenants.insert(org_id, dedicated(Tier::TeamBusiness));
+        assert!(resolve_tenant(&ConfiguredTenants, org_id, &config).is_none());
+        assert!(config.validate().is_err());
+    }
+}
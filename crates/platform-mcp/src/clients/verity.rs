@@ -4,10 +4,21 @@
 //! Provides methods for document verification, assertion extraction,
 //! knowledge base search, and propagation analysis.
 
-use super::config::ServiceEndpoint;
-use reqwest::Client;
+use super::auth::{authorize, ClientAuth, ServiceTokenProvider};
+use super::cache::{self, CacheLookup, ResponseCache};
+use super::config::{ServiceConfig, ServiceEndpoint};
+use crate::health::MetricsCollector;
+use crate::idempotency;
+use crate::monitor::HealthMonitor;
+use crate::retry::{self, HedgePolicy, RetryConfig};
+use crate::server::ToolContext;
+use crate::trace;
+use platform_auth::AppId;
+use reqwest::header::IF_NONE_MATCH;
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error, instrument, warn};
 
@@ -42,6 +53,17 @@ pub enum VerityError {
     /// Authentication failed.
     #[error("Authentication failed")]
     AuthenticationFailed,
+
+    /// Service credentials could not be produced.
+    #[error("Failed to mint service token: {0}")]
+    TokenError(String),
+
+    /// Health checks report the service as down.
+    #[error("Verity is unavailable (failing health checks since {since}); try again later")]
+    Unavailable {
+        /// When the service became unhealthy (RFC 3339).
+        since: String,
+    },
 }
 
 /// Verity service client.
@@ -57,6 +79,27 @@ pub struct VerityClient {
 
     /// Request timeout.
     timeout: Duration,
+
+    /// Credentials for outgoing requests.
+    auth: ClientAuth,
+
+    /// Calling tool context, used to forward the user's identity.
+    context: Option<ToolContext>,
+
+    /// Response cache for read endpoints.
+    cache: Option<Arc<ResponseCache>>,
+
+    /// Collector for request counts and latencies.
+    metrics: Option<Arc<MetricsCollector>>,
+
+    /// Health monitor consulted before each request.
+    health: Option<Arc<HealthMonitor>>,
+
+    /// Hedging policy for search requests.
+    hedge: Option<Arc<HedgePolicy>>,
+
+    /// Retries of failed requests.
+    retry: Option<RetryConfig>,
 }
 
 impl VerityClient {
@@ -71,9 +114,108 @@ impl VerityClient {
             client,
             endpoint,
             timeout,
+            auth: ClientAuth::ApiKey,
+            context: None,
+            cache: None,
+            metrics: None,
+            health: None,
+            hedge: None,
+            retry: None,
         }
     }
 
+    /// Create a Verity client from shared service configuration.
+    ///
+    /// Attaches a service token provider when service auth is configured, and
+    /// an in-memory response cache when caching is configured.
+    pub fn from_config(config: &ServiceConfig) -> Self {
+        Self::from_config_with_auth(config, ClientAuth::from_config(config))
+    }
+
+    /// Create a Verity client from shared service configuration, with
+    /// authentication built once for all clients of that configuration.
+    pub fn from_config_with_auth(config: &ServiceConfig, auth: ClientAuth) -> Self {
+        let client = Self::new(config.verity.clone(), config.timeout()).with_auth(auth);
+        match config.cache {
+            Some(ref cache) => client.with_cache(Arc::new(ResponseCache::from_config(cache))),
+            None => client,
+        }
+    }
+
+    /// Authenticate with platform service tokens instead of the static API key.
+    pub fn with_service_auth(self, provider: Arc<ServiceTokenProvider>) -> Self {
+        self.with_auth(ClientAuth::ServiceTokens(provider))
+    }
+
+    /// Set how requests are authenticated.
+    pub fn with_auth(mut self, auth: ClientAuth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Get how requests are authenticated.
+    pub fn auth(&self) -> &ClientAuth {
+        &self.auth
+    }
+
+    /// Serve read endpoints through a response cache.
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Record request counts and latencies in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Fail fast while `monitor` reports this client's base URL as down.
+    pub fn with_health_monitor(mut self, monitor: Arc<HealthMonitor>) -> Self {
+        self.health = Some(monitor);
+        self
+    }
+
+    /// Hedge search requests according to `policy`.
+    pub fn with_hedging(mut self, policy: Arc<HedgePolicy>) -> Self {
+        self.hedge = Some(policy);
+        self
+    }
+
+    /// Retry requests that fail to send or get a 429 or 5xx response
+    /// according to `config`.
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = Some(config);
+        self
+    }
+
+    /// Get a copy of this client that acts on behalf of the tool caller.
+    ///
+    /// The caller's identity is forwarded as a cross-app token when service
+    /// auth is enabled.
+    pub fn for_context(&self, context: &ToolContext) -> Self {
+        let mut client = self.clone();
+        client.context = Some(context.clone());
+        client
+    }
+
+    /// Build an authenticated request for a Verity API path.
+    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, VerityError> {
+        let request = self
+            .client
+            .request(method, self.endpoint.url(path))
+            .timeout(self.timeout);
+        let request = trace::inject(request, self.context.as_ref());
+        authorize(
+            request,
+            &self.endpoint,
+            &self.auth,
+            AppId::Verity,
+            self.context.as_ref(),
+        )
+        .map_err(|e| VerityError::TokenError(e.to_string()))
+    }
+
     /// Verify a document.
     ///
     /// Analyzes a document and verifies all factual claims against trusted sources.
@@ -84,14 +226,11 @@ impl VerityClient {
     ) -> Result<VerifyDocumentResponse, VerityError> {
         debug!("Starting verification for document {}", params.document_id);
 
-        let url = self.endpoint.url("/api/v1/documents/verify");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/documents/verify")?
+            .json(&params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -105,14 +244,11 @@ impl VerityClient {
     ) -> Result<ExtractAssertionsResponse, VerityError> {
         debug!("Extracting assertions from content");
 
-        let url = self.endpoint.url("/api/v1/assertions/extract");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/assertions/extract")?
+            .json(&params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -126,14 +262,11 @@ impl VerityClient {
     ) -> Result<SearchKnowledgeResponse, VerityError> {
         debug!("Searching knowledge base with query: {}", params.query);
 
-        let url = self.endpoint.url("/api/v1/knowledge/search");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/knowledge/search")?
+            .json(&params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send_hedged(request).await?;
         self.handle_response(response).await
     }
 
@@ -147,14 +280,11 @@ impl VerityClient {
     ) -> Result<CheckPropagationResponse, VerityError> {
         debug!("Checking propagation for assertion {}", params.assertion_id);
 
-        let url = self.endpoint.url("/api/v1/propagation/check");
-        let mut request = self.client.post(&url).json(&params);
+        let request = self
+            .request(Method::POST, "/api/v1/propagation/check")?
+            .json(&params);
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -165,22 +295,17 @@ impl VerityClient {
     pub async fn get_document(&self, document_id: &str) -> Result<Document, VerityError> {
         debug!("Fetching document {}", document_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/documents/{}", document_id));
-        let mut request = self.client.get(&url);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
-
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err(VerityError::DocumentNotFound(document_id.to_string()));
-        }
+        let path = format!("/api/v1/documents/{}", document_id);
+        let request = self.request(Method::GET, &path)?;
 
-        self.handle_response(response).await
+        self.send_cached("get_document", &path, request)
+            .await
+            .map_err(|e| match e {
+                VerityError::ApiError { status: 404, .. } => {
+                    VerityError::DocumentNotFound(document_id.to_string())
+                }
+                e => e,
+            })
     }
 
     /// Create a document from external content.
@@ -193,17 +318,34 @@ impl VerityClient {
     ) -> Result<CreateDocumentResponse, VerityError> {
         debug!("Creating document: {}", params.title);
 
-        let url = self.endpoint.url("/api/v1/documents");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self
+            .request(Method::POST, "/api/v1/documents")?
+            .json(&params);
+        let request =
+            idempotency::apply(request, self.context.as_ref(), "create_document", &params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
+    /// Delete a document.
+    ///
+    /// Used to roll back a document created by a workflow that failed later
+    /// on. A document that no longer exists counts as deleted.
+    #[instrument(skip(self), fields(document_id = %document_id))]
+    pub async fn delete_document(&self, document_id: &str) -> Result<(), VerityError> {
+        debug!("Deleting document {}", document_id);
+
+        let path = format!("/api/v1/documents/{}", document_id);
+        let request = self.request(Method::DELETE, &path)?;
+
+        let response = self.send(request).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        self.check_response(response).await.map(|_| ())
+    }
+
     /// Verify content directly.
     ///
     /// Verifies content without creating a permanent document.
@@ -217,14 +359,9 @@ impl VerityClient {
             params.verification_level
         );
 
-        let url = self.endpoint.url("/api/v1/verify");
-        let mut request = self.client.post(&url).json(&params);
-
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+        let request = self.request(Method::POST, "/api/v1/verify")?.json(&params);
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -235,16 +372,9 @@ impl VerityClient {
     pub async fn get_assertion(&self, assertion_id: &str) -> Result<Assertion, VerityError> {
         debug!("Fetching assertion {}", assertion_id);
 
-        let url = self
-            .endpoint
-            .url(&format!("/api/v1/assertions/{}", assertion_id));
-        let mut request = self.client.get(&url);
+        let request = self.request(Method::GET, &format!("/api/v1/assertions/{}", assertion_id))?;
 
-        if let Some(ref api_key) = self.endpoint.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self.send(request).await?;
 
         if response.status() == reqwest::StatusCode::NOT_FOUND {
             return Err(VerityError::AssertionNotFound(assertion_id.to_string()));
@@ -253,11 +383,144 @@ impl VerityClient {
         self.handle_response(response).await
     }
 
+    /// Send a request, retrying it if a retry configuration is attached.
+    async fn send(&self, request: RequestBuilder) -> Result<reqwest::Response, VerityError> {
+        match self.retry {
+            Some(ref config) => {
+                retry::retry_request(
+                    config,
+                    request,
+                    |request| self.send_once(request),
+                    |e| matches!(e, VerityError::RequestFailed(_)),
+                )
+                .await
+            }
+            None => self.send_once(request).await,
+        }
+    }
+
+    /// Send a request once, recording its latency and outcome.
+    ///
+    /// Fails without sending while the health monitor reports this client's
+    /// Verity deployment as down.
+    async fn send_once(&self, request: RequestBuilder) -> Result<reqwest::Response, VerityError> {
+        if let Some(since) = self
+            .health
+            .as_ref()
+            .and_then(|h| h.down_since_url(&self.endpoint.base_url))
+        {
+            return Err(VerityError::Unavailable {
+                since: since.to_rfc3339(),
+            });
+        }
+
+        let start = Instant::now();
+        let result = request.send().await;
+        if let Some(ref metrics) = self.metrics {
+            let success = result.as_ref().is_ok_and(|response| {
+                response.status().is_success() || response.status() == StatusCode::NOT_MODIFIED
+            });
+            metrics.record_request("verity", start.elapsed().as_millis() as u64, success);
+        }
+        Ok(result?)
+    }
+
+    /// Send a read request, hedging it if a policy is attached.
+    async fn send_hedged(&self, request: RequestBuilder) -> Result<reqwest::Response, VerityError> {
+        let Some(ref policy) = self.hedge else {
+            return self.send(request).await;
+        };
+        if request.try_clone().is_none() {
+            return self.send(request).await;
+        }
+
+        let (result, outcome) = retry::hedge(policy, retry::accept_response, || {
+            let request = request.try_clone().expect("request body is cloneable");
+            self.send(request)
+        })
+        .await;
+        if let (Some(ref metrics), Some(winner)) = (&self.metrics, outcome.winner()) {
+            metrics.record_hedge("verity", winner);
+        }
+        result
+    }
+
+    /// Send a read request through the response cache, if one is attached.
+    ///
+    /// Fresh entries are returned without contacting Verity; expired entries
+    /// with an ETag are revalidated with `If-None-Match`.
+    async fn send_cached<T>(
+        &self,
+        operation: &str,
+        resource: &str,
+        request: RequestBuilder,
+    ) -> Result<T, VerityError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let Some(cache) = self.cache.as_deref() else {
+            return self.handle_response(self.send(request).await?).await;
+        };
+
+        let scope = cache::scope(&self.endpoint, self.auth.provider(), self.context.as_ref());
+        let key = cache.key(AppId::Verity, operation, &scope, resource);
+        let decode = |body: serde_json::Value| {
+            serde_json::from_value(body).map_err(|e| VerityError::InvalidResponse(e.to_string()))
+        };
+
+        let mut unconditional = None;
+        let request = match cache.lookup(&key) {
+            CacheLookup::Fresh(body) => {
+                debug!("Serving {} from cache", operation);
+                return decode(body);
+            }
+            CacheLookup::Stale(etag) => {
+                unconditional = request.try_clone();
+                request.header(IF_NONE_MATCH, etag)
+            }
+            CacheLookup::Miss => request,
+        };
+
+        let mut response = self.send(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(body) = cache.revalidate(&key, operation) {
+                debug!("Revalidated cached {}", operation);
+                return decode(body);
+            }
+            // The entry was evicted while revalidating; fetch the body again.
+            if let Some(request) = unconditional {
+                debug!(
+                    "Cached {} evicted during revalidation, refetching",
+                    operation
+                );
+                response = self.send(request).await?;
+            }
+        }
+
+        let etag = cache::etag(&response);
+        let body: serde_json::Value = self.handle_response(response).await?;
+        let value = decode(body.clone())?;
+        cache.store(&key, operation, etag, body);
+        Ok(value)
+    }
+
     /// Handle API response and parse JSON.
     async fn handle_response<T>(&self, response: reqwest::Response) -> Result<T, VerityError>
     where
         T: for<'de> Deserialize<'de>,
     {
+        self.check_response(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| VerityError::InvalidResponse(e.to_string()))
+    }
+
+    /// Turn an unsuccessful API response into an error.
+    async fn check_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, VerityError> {
         let status = response.status();
 
         if status == reqwest::StatusCode::UNAUTHORIZED {
@@ -277,10 +540,7 @@ impl VerityClient {
             });
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| VerityError::InvalidResponse(e.to_string()))
+        Ok(response)
     }
 }
 
@@ -404,6 +664,14 @@ pub struct SearchFilters {
     /// Date range.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date_range: Option<DateRange>,
+
+    /// Only include knowledge from this project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+
+    /// Only include knowledge with all of these tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 /// Date range for filtering.
@@ -451,6 +719,14 @@ pub struct KnowledgeResult {
     /// Last verified timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_verified: Option<String>,
+
+    /// App the fact was taken from, e.g. `noteman`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_app: Option<String>,
+
+    /// ID of the item in the source app, e.g. a meeting ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
 }
 
 /// Parameters for propagation check.
@@ -691,6 +967,8 @@ mod tests {
             source_types: Some(vec!["academic".to_string()]),
             min_confidence: Some(0.8),
             date_range: None,
+            project_id: None,
+            tags: Vec::new(),
         };
         assert_eq!(filters.min_confidence, Some(0.8));
     }
@@ -7,6 +7,7 @@
 //! - Cross-app connectivity verification
 //! - Latency monitoring and metrics
 //! - Dependency status aggregation
+//! - Request, tool-call and health metrics with OpenMetrics export
 //!
 //! ## Health Check Types
 //!
@@ -37,8 +38,13 @@
 //! ```
 
 use crate::clients::config::{ServiceConfig, ServiceEndpoint};
+use crate::metrics::{
+    format_value, percentile, LatencyHistogram, OpenMetricsWriter, ToolOutcome, LATENCY_QUANTILES,
+};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tracing::{debug, error, info, instrument, warn};
@@ -228,6 +234,9 @@ pub struct HealthChecker {
 
     /// Start time for uptime calculation.
     start_time: Instant,
+
+    /// Collector updated with the results of full health checks.
+    metrics: Option<Arc<MetricsCollector>>,
 }
 
 impl HealthChecker {
@@ -243,9 +252,21 @@ impl HealthChecker {
             config,
             health_config,
             start_time: Instant::now(),
+            metrics: None,
         }
     }
 
+    /// Record the results of full health checks in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the collector health checks are recorded in, if any.
+    pub fn metrics(&self) -> Option<&Arc<MetricsCollector>> {
+        self.metrics.as_ref()
+    }
+
     /// Create a health checker from environment variables.
     pub fn from_env() -> Self {
         Self::new(ServiceConfig::from_env(), HealthCheckConfig::default())
@@ -360,14 +381,18 @@ impl HealthChecker {
             "Health check complete"
         );
 
-        HealthReport {
+        let report = HealthReport {
             status,
             timestamp: chrono::Utc::now().to_rfc3339(),
             services,
             check_duration_ms: duration.as_millis() as u64,
             platform_version: env!("CARGO_PKG_VERSION").to_string(),
             message,
+        };
+        if let Some(ref metrics) = self.metrics {
+            metrics.record_health(&report);
         }
+        report
     }
 
     /// Check a specific service's health.
@@ -515,6 +540,9 @@ pub struct IntegrationMetrics {
     /// Average latency in milliseconds.
     pub avg_latency_ms: f64,
 
+    /// Median latency in milliseconds.
+    pub p50_latency_ms: f64,
+
     /// 95th percentile latency in milliseconds.
     pub p95_latency_ms: f64,
 
@@ -528,20 +556,45 @@ pub struct IntegrationMetrics {
     pub requests_per_second: f64,
 }
 
+/// Maximum number of latencies kept per window for percentile calculation.
+const LATENCY_WINDOW: usize = 10000;
+
 /// Metrics collector for integration monitoring.
 ///
-/// Collects and aggregates metrics for cross-app communication.
+/// Collects and aggregates metrics for cross-app communication. Use
+/// [`Self::render_openmetrics`] to export them (see [`crate::metrics`]).
 pub struct MetricsCollector {
-    /// Request latencies (stored for percentile calculation).
-    latencies: std::sync::Mutex<Vec<u64>>,
-
-    /// Request counts by service.
-    request_counts: std::sync::Mutex<RequestCounts>,
+    /// Collected metrics.
+    state: std::sync::Mutex<MetricsState>,
 
     /// Start time.
     start_time: Instant,
 }
 
+#[derive(Debug, Default)]
+struct MetricsState {
+    /// Recent latencies across all services.
+    latencies: VecDeque<u64>,
+
+    /// Request counts for the known services.
+    counts: RequestCounts,
+
+    /// Per-service request metrics.
+    services: BTreeMap<String, ServiceMetrics>,
+
+    /// Tool calls by tool and outcome.
+    tool_calls: BTreeMap<(String, ToolOutcome), u64>,
+
+    /// Latest health check result by service.
+    health: BTreeMap<String, (ServiceStatus, u64)>,
+
+    /// Circuit breaker state by service (true when open).
+    circuits: BTreeMap<String, bool>,
+
+    /// Hedged requests by service and whether the hedge answered first.
+    hedges: BTreeMap<(String, &'static str), u64>,
+}
+
 #[derive(Debug, Default)]
 struct RequestCounts {
     noteman: u64,
@@ -550,70 +603,115 @@ struct RequestCounts {
     failed: u64,
 }
 
+#[derive(Debug, Default)]
+struct ServiceMetrics {
+    success: u64,
+    failure: u64,
+    histogram: LatencyHistogram,
+    latencies: VecDeque<u64>,
+}
+
+/// Append a latency to a bounded window.
+fn push_latency(window: &mut VecDeque<u64>, latency_ms: u64) {
+    window.push_back(latency_ms);
+    if window.len() > LATENCY_WINDOW {
+        window.pop_front();
+    }
+}
+
+fn sorted(window: &VecDeque<u64>) -> Vec<u64> {
+    let mut sorted: Vec<_> = window.iter().copied().collect();
+    sorted.sort_unstable();
+    sorted
+}
+
 impl MetricsCollector {
     /// Create a new metrics collector.
     pub fn new() -> Self {
         Self {
-            latencies: std::sync::Mutex::new(Vec::new()),
-            request_counts: std::sync::Mutex::new(RequestCounts::default()),
+            state: std::sync::Mutex::new(MetricsState::default()),
             start_time: Instant::now(),
         }
     }
 
+    fn state(&self) -> std::sync::MutexGuard<'_, MetricsState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Record a request.
     pub fn record_request(&self, service: &str, latency_ms: u64, success: bool) {
-        {
-            let mut latencies = self.latencies.lock().unwrap();
-            latencies.push(latency_ms);
+        let mut state = self.state();
+        push_latency(&mut state.latencies, latency_ms);
+
+        match service {
+            "noteman" => state.counts.noteman += 1,
+            "shipcheck" => state.counts.shipcheck += 1,
+            "verity" => state.counts.verity += 1,
+            _ => {}
+        }
+        if !success {
+            state.counts.failed += 1;
+        }
 
-            // Keep last 10000 latencies for percentile calculation
-            if latencies.len() > 10000 {
-                latencies.remove(0);
-            }
+        let metrics = state.services.entry(service.to_string()).or_default();
+        if success {
+            metrics.success += 1;
+        } else {
+            metrics.failure += 1;
         }
+        metrics.histogram.observe(latency_ms);
+        push_latency(&mut metrics.latencies, latency_ms);
+    }
 
-        {
-            let mut counts = self.request_counts.lock().unwrap();
-            match service {
-                "noteman" => counts.noteman += 1,
-                "shipcheck" => counts.shipcheck += 1,
-                "verity" => counts.verity += 1,
-                _ => {}
-            }
-            if !success {
-                counts.failed += 1;
-            }
+    /// Record a tool call.
+    pub fn record_tool_call(&self, tool: &str, outcome: ToolOutcome) {
+        *self
+            .state()
+            .tool_calls
+            .entry((tool.to_string(), outcome))
+            .or_default() += 1;
+    }
+
+    /// Record a hedged request and which request answered first
+    /// (`"primary"`, `"hedge"`, or `"none"` if both failed).
+    pub fn record_hedge(&self, service: &str, winner: &'static str) {
+        *self
+            .state()
+            .hedges
+            .entry((service.to_string(), winner))
+            .or_default() += 1;
+    }
+
+    /// Record the services' state from a health check.
+    pub fn record_health(&self, report: &HealthReport) {
+        let mut state = self.state();
+        for service in &report.services {
+            state.health.insert(
+                service.name.to_lowercase(),
+                (service.status, service.latency_ms),
+            );
         }
     }
 
+    /// Record whether the circuit breaker for a service is open, i.e. its
+    /// calls fail fast (see [`HealthMonitor`](crate::monitor::HealthMonitor)).
+    pub fn set_circuit_open(&self, service: &str, open: bool) {
+        self.state().circuits.insert(service.to_string(), open);
+    }
+
     /// Get current metrics.
     pub fn get_metrics(&self) -> IntegrationMetrics {
-        let latencies = self.latencies.lock().unwrap();
-        let counts = self.request_counts.lock().unwrap();
+        let state = self.state();
+        let counts = &state.counts;
 
         let total_requests = counts.noteman + counts.shipcheck + counts.verity;
         let elapsed_secs = self.start_time.elapsed().as_secs_f64();
 
-        let (avg, p95, p99) = if !latencies.is_empty() {
-            let mut sorted: Vec<_> = latencies.clone();
-            sorted.sort_unstable();
-
-            let avg = sorted.iter().sum::<u64>() as f64 / sorted.len() as f64;
-            let p95_idx = (sorted.len() as f64 * 0.95) as usize;
-            let p99_idx = (sorted.len() as f64 * 0.99) as usize;
-
-            let p95 = sorted
-                .get(p95_idx.min(sorted.len() - 1))
-                .copied()
-                .unwrap_or(0) as f64;
-            let p99 = sorted
-                .get(p99_idx.min(sorted.len() - 1))
-                .copied()
-                .unwrap_or(0) as f64;
-
-            (avg, p95, p99)
+        let sorted = sorted(&state.latencies);
+        let avg = if sorted.is_empty() {
+            0.0
         } else {
-            (0.0, 0.0, 0.0)
+            sorted.iter().sum::<u64>() as f64 / sorted.len() as f64
         };
 
         let success_rate = if total_requests > 0 {
@@ -628,8 +726,9 @@ impl MetricsCollector {
             verity_requests: counts.verity,
             failed_requests: counts.failed,
             avg_latency_ms: avg,
-            p95_latency_ms: p95,
-            p99_latency_ms: p99,
+            p50_latency_ms: percentile(&sorted, 0.5),
+            p95_latency_ms: percentile(&sorted, 0.95),
+            p99_latency_ms: percentile(&sorted, 0.99),
             success_rate,
             requests_per_second: if elapsed_secs > 0.0 {
                 total_requests as f64 / elapsed_secs
@@ -639,10 +738,163 @@ impl MetricsCollector {
         }
     }
 
+    /// Render all metrics in the OpenMetrics text format.
+    pub fn render_openmetrics(&self) -> String {
+        let state = self.state();
+        let mut out = OpenMetricsWriter::new();
+
+        out.family(
+            "platform_mcp_service_requests",
+            "counter",
+            "Requests made to platform services.",
+        );
+        for (service, metrics) in &state.services {
+            for (outcome, count) in [("success", metrics.success), ("failure", metrics.failure)] {
+                out.sample(
+                    "platform_mcp_service_requests_total",
+                    &[("service", service), ("outcome", outcome)],
+                    count as f64,
+                );
+            }
+        }
+
+        out.family(
+            "platform_mcp_service_request_duration_seconds",
+            "histogram",
+            "Latency of requests to platform services.",
+        );
+        for (service, metrics) in &state.services {
+            for (bound, count) in metrics.histogram.cumulative() {
+                let le = bound.map_or(f64::INFINITY, |ms| ms as f64 / 1000.0);
+                out.sample(
+                    "platform_mcp_service_request_duration_seconds_bucket",
+                    &[("service", service), ("le", &format_value(le))],
+                    count as f64,
+                );
+            }
+            out.sample(
+                "platform_mcp_service_request_duration_seconds_count",
+                &[("service", service)],
+                metrics.histogram.count() as f64,
+            );
+            out.sample(
+                "platform_mcp_service_request_duration_seconds_sum",
+                &[("service", service)],
+                metrics.histogram.sum_ms() as f64 / 1000.0,
+            );
+        }
+
+        out.family(
+            "platform_mcp_service_request_latency_seconds",
+            "summary",
+            "Latency quantiles over recent requests to platform services.",
+        );
+        for (service, metrics) in &state.services {
+            let sorted = sorted(&metrics.latencies);
+            for &quantile in LATENCY_QUANTILES {
+                out.sample(
+                    "platform_mcp_service_request_latency_seconds",
+                    &[("service", service), ("quantile", &format_value(quantile))],
+                    percentile(&sorted, quantile) / 1000.0,
+                );
+            }
+            // Count and sum must be monotonic, so they come from the
+            // cumulative histogram rather than the window.
+            out.sample(
+                "platform_mcp_service_request_latency_seconds_count",
+                &[("service", service)],
+                metrics.histogram.count() as f64,
+            );
+            out.sample(
+                "platform_mcp_service_request_latency_seconds_sum",
+                &[("service", service)],
+                metrics.histogram.sum_ms() as f64 / 1000.0,
+            );
+        }
+
+        out.family(
+            "platform_mcp_tool_calls",
+            "counter",
+            "MCP tool calls by tool and outcome.",
+        );
+        for ((tool, outcome), count) in &state.tool_calls {
+            out.sample(
+                "platform_mcp_tool_calls_total",
+                &[("tool", tool), ("outcome", outcome.as_str())],
+                *count as f64,
+            );
+        }
+
+        out.family(
+            "platform_mcp_hedged_requests",
+            "counter",
+            "Hedged requests by service and which request answered first.",
+        );
+        for ((service, winner), count) in &state.hedges {
+            out.sample(
+                "platform_mcp_hedged_requests_total",
+                &[("service", service), ("winner", winner)],
+                *count as f64,
+            );
+        }
+
+        out.family(
+            "platform_mcp_service_up",
+            "gauge",
+            "Whether the service passed its last health check (degraded counts as up).",
+        );
+        for (service, (status, _)) in &state.health {
+            let up = matches!(status, ServiceStatus::Up | ServiceStatus::Degraded);
+            out.sample(
+                "platform_mcp_service_up",
+                &[("service", service)],
+                f64::from(u8::from(up)),
+            );
+        }
+
+        out.family(
+            "platform_mcp_service_health_latency_seconds",
+            "gauge",
+            "Latency of the last health check.",
+        );
+        for (service, (_, latency_ms)) in &state.health {
+            out.sample(
+                "platform_mcp_service_health_latency_seconds",
+                &[("service", service)],
+                *latency_ms as f64 / 1000.0,
+            );
+        }
+
+        out.family(
+            "platform_mcp_circuit_open",
+            "gauge",
+            "Whether the circuit breaker for the service is open.",
+        );
+        for (service, open) in &state.circuits {
+            out.sample(
+                "platform_mcp_circuit_open",
+                &[("service", service)],
+                f64::from(u8::from(*open)),
+            );
+        }
+
+        out.family(
+            "platform_mcp_uptime_seconds",
+            "gauge",
+            "Seconds since the collector was created.",
+        );
+        out.sample(
+            "platform_mcp_uptime_seconds",
+            &[],
+            self.start_time.elapsed().as_secs_f64().floor(),
+        );
+
+        out.finish()
+    }
+
     /// Reset all metrics.
     pub fn reset(&self) {
-        *self.latencies.lock().unwrap() = Vec::new();
-        *self.request_counts.lock().unwrap() = RequestCounts::default();
+        *self.state() = MetricsState::default();
     }
 }
 
@@ -652,6 +904,14 @@ impl Default for MetricsCollector {
     }
 }
 
+impl std::fmt::Debug for MetricsCollector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsCollector")
+            .field("uptime", &self.start_time.elapsed())
+            .finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -750,6 +1010,56 @@ mod tests {
         assert!(metrics.avg_latency_ms > 0.0);
     }
 
+    #[test]
+    fn test_render_openmetrics() {
+        let collector = MetricsCollector::new();
+        collector.record_request("verity", 40, true);
+        collector.record_request("verity", 700, false);
+        collector.record_tool_call("verify_document", ToolOutcome::Denied);
+        collector.set_circuit_open("noteman", true);
+        collector.record_hedge("verity", "hedge");
+
+        let out = collector.render_openmetrics();
+        assert!(out.contains(
+            "platform_mcp_service_requests_total{service=\"verity\",outcome=\"failure\"} 1.0\n"
+        ));
+        assert!(out.contains(
+            "platform_mcp_service_request_duration_seconds_bucket{service=\"verity\",le=\"0.05\"} 1.0\n"
+        ));
+        assert!(out.contains(
+            "platform_mcp_service_request_duration_seconds_bucket{service=\"verity\",le=\"+Inf\"} 2.0\n"
+        ));
+        assert!(out.contains(
+            "platform_mcp_service_request_latency_seconds{service=\"verity\",quantile=\"0.99\"} 0.7\n"
+        ));
+        assert!(out.contains(
+            "platform_mcp_tool_calls_total{tool=\"verify_document\",outcome=\"denied\"} 1.0\n"
+        ));
+        assert!(out.contains("platform_mcp_circuit_open{service=\"noteman\"} 1.0\n"));
+        assert!(out.contains(
+            "platform_mcp_hedged_requests_total{service=\"verity\",winner=\"hedge\"} 1.0\n"
+        ));
+        assert!(out.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn test_latency_summary_count_is_cumulative() {
+        let collector = MetricsCollector::new();
+        for _ in 0..LATENCY_WINDOW + 5 {
+            collector.record_request("verity", 2, true);
+        }
+
+        // The window drops old samples; count and sum keep every request.
+        let out = collector.render_openmetrics();
+        assert!(out.contains(&format!(
+            "platform_mcp_service_request_latency_seconds_count{{service=\"verity\"}} {:.1}\n",
+            (LATENCY_WINDOW + 5) as f64
+        )));
+        assert!(out.contains(
+            "platform_mcp_service_request_latency_seconds_sum{service=\"verity\"} 20.01\n"
+        ));
+    }
+
     #[test]
     fn test_health_check_config_defaults() {
         let config = HealthCheckConfig::default();
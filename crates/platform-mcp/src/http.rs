@@ -0,0 +1,154 @@
+//! HTTP endpoints for operating the MCP server.
+//!
+//! Requires the `http` feature. Mount the routers next to the MCP transport:
+//!
+//! ```rust,ignore
+//! use platform_mcp::health::{HealthChecker, MetricsCollector};
+//! use platform_mcp::http::{health_router, metrics_router};
+//! use std::sync::Arc;
+//!
+//! let metrics = Arc::new(MetricsCollector::new());
+//! let checker = Arc::new(HealthChecker::from_env().with_metrics(metrics.clone()));
+//! let app = metrics_router(metrics).merge(health_router(checker));
+//!
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:9090").await?;
+//! axum::serve(listener, app).await?;
+//! ```
+//!
+//! | Path | Response |
+//! |------|----------|
+//! | `/healthz` | [`LivenessResult`](crate::health::LivenessResult), always `200` |
+//! | `/readyz` | [`ReadinessResult`](crate::health::ReadinessResult), `503` unless all services respond |
+//! | `/health` | [`HealthReport`](crate::health::HealthReport), `503` when unhealthy |
+//! | `/metrics` | OpenMetrics text |
+
+use crate::health::{HealthChecker, HealthStatus, MetricsCollector};
+use crate::metrics::OPENMETRICS_CONTENT_TYPE;
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use std::sync::Arc;
+
+/// Build a router serving `GET /metrics` in the OpenMetrics text format.
+pub fn metrics_router(metrics: Arc<MetricsCollector>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(metrics)
+}
+
+/// Render the collected metrics.
+async fn metrics_handler(State(metrics): State<Arc<MetricsCollector>>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE)],
+        metrics.render_openmetrics(),
+    )
+}
+
+/// Build a router serving `GET /healthz`, `GET /readyz` and `GET /health`.
+pub fn health_router(checker: Arc<HealthChecker>) -> Router {
+    Router::new()
+        .route("/healthz", get(liveness_handler))
+        .route("/readyz", get(readiness_handler))
+        .route("/health", get(health_handler))
+        .with_state(checker)
+}
+
+/// Liveness probe.
+async fn liveness_handler(State(checker): State<Arc<HealthChecker>>) -> impl IntoResponse {
+    Json(checker.check_liveness().await)
+}
+
+/// Readiness probe.
+async fn readiness_handler(State(checker): State<Arc<HealthChecker>>) -> impl IntoResponse {
+    let result = checker.check_readiness().await;
+    let status = if result.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(result))
+}
+
+/// Full health report of all services.
+async fn health_handler(State(checker): State<Arc<HealthChecker>>) -> impl IntoResponse {
+    let report = checker.check_all().await;
+    let status = match report.status {
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::ToolOutcome;
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let metrics = Arc::new(MetricsCollector::new());
+        metrics.record_tool_call("verify_document", ToolOutcome::Success);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, metrics_router(metrics))
+                .await
+                .unwrap();
+        });
+
+        let response = reqwest::get(format!("http://{}/metrics", addr))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.headers()["content-type"].to_str().unwrap(),
+            OPENMETRICS_CONTENT_TYPE
+        );
+        let body = response.text().await.unwrap();
+        assert!(body.contains(
+            "platform_mcp_tool_calls_total{tool=\"verify_document\",outcome=\"success\"} 1.0"
+        ));
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoints() {
+        // Nothing listens on the discard port.
+        let mut config = crate::clients::ServiceConfig::default();
+        for endpoint in [
+            &mut config.noteman,
+            &mut config.shipcheck,
+            &mut config.verity,
+        ] {
+            endpoint.base_url = "http://127.0.0.1:9".to_string();
+        }
+        let checker = Arc::new(HealthChecker::new(
+            config,
+            crate::health::HealthCheckConfig {
+                check_timeout: std::time::Duration::from_millis(200),
+                ..Default::default()
+            },
+        ));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, health_router(checker)).await.unwrap();
+        });
+
+        let get = |path: &str| reqwest::get(format!("http://{}{}", addr, path));
+        assert_eq!(get("/healthz").await.unwrap().status(), 200);
+        assert_eq!(get("/readyz").await.unwrap().status(), 503);
+
+        let response = get("/health").await.unwrap();
+        assert_eq!(response.status(), 503);
+        let report: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(report["status"], "unhealthy");
+
+        server.abort();
+    }
+}
@@ -0,0 +1,230 @@
+//! Idempotency keys for mutating cross-app calls.
+//!
+//! Creating calls such as `create_document` or `sync_tasks` are sent with an
+//! `Idempotency-Key` header derived from the tool call (its request ID, or
+//! its correlation ID when there is none), the calling user and organization,
+//! and the request arguments. A retry of the same call therefore carries the
+//! same key, and the target service can discard the duplicate. The server
+//! takes the request ID from the caller's idempotency key, or the JSON-RPC ID
+//! when there is none, so a client retrying a call after a timeout reuses its
+//! keys, while calls from different users or organizations never share one.
+//!
+//! Workflow tools additionally record the result of each mutating step in an
+//! [`IdempotencyStore`]. Re-running a step with the same key returns the
+//! recorded result instead of calling the service again.
+
+use crate::server::ToolContext;
+use reqwest::RequestBuilder;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Idempotency key header.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Default time a recorded step result is kept.
+pub const DEFAULT_RECORD_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Derive the idempotency key for an operation performed by a tool call.
+///
+/// Returns `None` when the call has neither a request ID nor a correlation
+/// ID, since retries could not be recognized.
+pub fn idempotency_key(
+    context: &ToolContext,
+    operation: &str,
+    args: &impl Serialize,
+) -> Option<String> {
+    let call_id = context
+        .request_id
+        .as_deref()
+        .or(context.correlation_id.as_deref())?;
+    // serde_json sorts object keys, so equal arguments always hash the same.
+    let args = serde_json::to_value(args).ok()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(operation.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(call_id.as_bytes());
+    hasher.update(b"\0");
+    if let Some(org_id) = context.org_id {
+        hasher.update(org_id.as_bytes());
+    }
+    hasher.update(b"\0");
+    if let Some(user_id) = context.user_id {
+        hasher.update(user_id.as_bytes());
+    }
+    hasher.update(b"\0");
+    hasher.update(args.to_string().as_bytes());
+
+    Some(
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect(),
+    )
+}
+
+/// Add an `Idempotency-Key` header for a mutating request.
+pub(crate) fn apply(
+    request: RequestBuilder,
+    context: Option<&ToolContext>,
+    operation: &str,
+    args: &impl Serialize,
+) -> RequestBuilder {
+    match context.and_then(|c| idempotency_key(c, operation, args)) {
+        Some(key) => request.header(IDEMPOTENCY_KEY_HEADER, key),
+        None => request,
+    }
+}
+
+/// Records the results of mutating workflow steps by idempotency key.
+pub trait IdempotencyStore: Send + Sync {
+    /// Get the recorded result for `key`.
+    fn get(&self, key: &str) -> Option<serde_json::Value>;
+
+    /// Record the result for `key`.
+    fn put(&self, key: String, result: serde_json::Value);
+
+    /// Forget the result for `key`, e.g. after the step was rolled back.
+    fn remove(&self, key: &str);
+}
+
+/// In-memory idempotency store with expiring records.
+pub struct MemoryIdempotencyStore {
+    /// How long a record is kept.
+    ttl: Duration,
+
+    /// Records and when they were written.
+    records: Mutex<HashMap<String, (Instant, serde_json::Value)>>,
+}
+
+impl MemoryIdempotencyStore {
+    /// Create a store keeping records for [`DEFAULT_RECORD_TTL`].
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_RECORD_TTL)
+    }
+
+    /// Create a store keeping records for `ttl`.
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            records: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the number of live records.
+    pub fn len(&self) -> usize {
+        let records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        records
+            .values()
+            .filter(|(written, _)| written.elapsed() < self.ttl)
+            .count()
+    }
+
+    /// Check whether the store has no live records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdempotencyStore for MemoryIdempotencyStore {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        records
+            .get(key)
+            .filter(|(written, _)| written.elapsed() < self.ttl)
+            .map(|(_, result)| result.clone())
+    }
+
+    fn put(&self, key: String, result: serde_json::Value) {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        records.retain(|_, (written, _)| written.elapsed() < self.ttl);
+        records.insert(key, (Instant::now(), result));
+    }
+
+    fn remove(&self, key: &str) {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        records.remove(key);
+    }
+}
+
+impl std::fmt::Debug for MemoryIdempotencyStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryIdempotencyStore")
+            .field("ttl", &self.ttl)
+            .field("records", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(request_id: &str) -> ToolContext {
+        ToolContext {
+            request_id: Some(request_id.to_string()),
+            ..ToolContext::empty()
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_call_operation_and_args() {
+        let args = serde_json::json!({ "title": "Notes", "tags": ["a"] });
+        let key = idempotency_key(&context("1"), "create_document", &args).unwrap();
+
+        assert_eq!(
+            idempotency_key(&context("1"), "create_document", &args),
+            Some(key.clone())
+        );
+        assert_ne!(
+            idempotency_key(&context("2"), "create_document", &args),
+            Some(key.clone())
+        );
+        assert_ne!(
+            idempotency_key(&context("1"), "create_discussion", &args),
+            Some(key.clone())
+        );
+        assert_ne!(
+            idempotency_key(
+                &context("1"),
+                "create_document",
+                &serde_json::json!({ "title": "Other" })
+            ),
+            Some(key)
+        );
+
+        // The same call ID from another user is a different call.
+        let other_user = ToolContext {
+            user_id: Some(uuid::Uuid::now_v7()),
+            ..context("1")
+        };
+        assert_ne!(
+            idempotency_key(&other_user, "create_document", &args),
+            idempotency_key(&context("1"), "create_document", &args)
+        );
+
+        // Without any call identity there is nothing to derive a key from.
+        assert!(idempotency_key(&ToolContext::empty(), "create_document", &args).is_none());
+    }
+
+    #[test]
+    fn test_memory_store_expires_records() {
+        let store = MemoryIdempotencyStore::with_ttl(Duration::from_millis(20));
+        store.put("k".to_string(), serde_json::json!({ "id": 1 }));
+        assert_eq!(store.get("k"), Some(serde_json::json!({ "id": 1 })));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(store.get("k").is_none());
+        assert!(store.is_empty());
+    }
+}
@@ -50,6 +50,17 @@
 //! - `verify_documentation`: Verify repo documentation (ShipCheck→Verity)
 //! - `create_finding_discussion`: Create discussion from finding (ShipCheck→NoteMan)
 //! - `sync_action_items`: Sync action items to tasks (NoteMan→ShipCheck)
+//! - `workflow_verify_meeting_notes_batch`: Verify the notes of many meetings,
+//!   e.g. all meetings in a date range
+//! - `workflow_verify_documentation_batch`: Verify the documentation of many
+//!   repositories, e.g. all repositories in a project
+//! - `workflow_list_runs`: List recent workflow runs
+//! - `workflow_get_run`: Inspect a workflow run step by step
+//!
+//! ### Platform Tools
+//! - `platform_health`: Check the health of all platform services
+//! - `platform_search`: Search knowledge, meetings and findings at once (see
+//!   [`search`])
 //!
 //! ## Service Clients
 //!
@@ -58,6 +69,23 @@
 //! - `ShipCheckClient`: Code analysis and verification
 //! - `VerityClient`: Content verification
 //!
+//! Client requests carry W3C trace context and the caller's correlation ID
+//! (see [`trace`]). Enable the `otel` feature to export spans over OTLP.
+//! Mutating calls carry an `Idempotency-Key` (see [`idempotency`]). With a
+//! [`monitor::HealthMonitor`] attached, calls to a service that is down fail
+//! fast.
+//!
+//! ## Workflows
+//!
+//! The cross-app workflow tools run declarative workflow definitions (see
+//! [`workflow`]). Steps call client operations, pass outputs to later steps,
+//! branch on conditions and fan out in parallel. New workflows can be written
+//! in YAML or JSON and registered as tools with [`workflow::WorkflowTool`].
+//! Runs are recorded step by step and can be resumed after a restart (see
+//! [Resuming Workflow Runs](#resuming-workflow-runs)).
+//! [`workflow::Automations`] run workflows in response to platform events,
+//! and the [`scheduler`] runs them on cron schedules.
+//!
 //! ## Usage
 //!
 //! ### Creating an MCP Server
@@ -107,6 +135,28 @@
 //! }
 //! ```
 //!
+//! ### Resuming Workflow Runs
+//!
+//! The crate does not start a server process, so the embedding application
+//! resumes interrupted workflow runs itself, once at startup and before
+//! serving requests:
+//!
+//! ```rust,no_run
+//! use platform_mcp::tools::{resume_workflow_runs, workflow_tools};
+//! use platform_mcp::workflow::FileRunStore;
+//! use platform_mcp::{McpServer, ServiceRegistry};
+//! use std::sync::Arc;
+//!
+//! async fn start(server: &McpServer) {
+//!     let store = FileRunStore::open("/var/lib/platform-mcp/runs").await.unwrap();
+//!     let services = Arc::new(ServiceRegistry::from_env().with_run_store(Arc::new(store)));
+//!     for (run_id, result) in resume_workflow_runs(&services).await.unwrap() {
+//!         println!("Resumed run {}: {}", run_id, result.is_ok());
+//!     }
+//!     server.register_tools(workflow_tools(&services)).await;
+//! }
+//! ```
+//!
 //! ## Tool Categories
 //!
 //! Tools are organized into categories:
@@ -116,17 +166,31 @@
 //! - `search`: Search tools (knowledge, meetings, findings)
 //! - `workflow`: Cross-app workflow tools
 //! - `pipeline`: Automated pipeline tools
+//! - `monitoring`: Platform health tools
 
 pub mod clients;
 pub mod health;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod idempotency;
+pub mod metrics;
+pub mod monitor;
 pub mod retry;
+pub mod scheduler;
+pub mod search;
 pub mod server;
 pub mod tools;
+pub mod trace;
 pub mod types;
+pub mod workflow;
 
 // Re-export main types
-pub use retry::{with_retry, with_retry_if, RetryConfig};
+pub use retry::{
+    with_hedge, with_retry, with_retry_if, HedgeConfig, HedgePolicy, Jitter, RetryBudget,
+    RetryBudgets, RetryConfig,
+};
 pub use server::{FunctionTool, McpServer, McpServerError, McpServerResult, Tool, ToolContext};
+pub use trace::TraceContext;
 pub use types::{
     ContentBlock, McpError, McpRequest, McpResponse, PromptCapabilities, RequestId,
     ResourceCapabilities, ResourceDefinition, ServerCapabilities, ServerInfo, ToolCall,
@@ -134,10 +198,16 @@ pub use types::{
 };
 
 // Re-export tool collections
-pub use tools::{noteman_tools, shipcheck_tools, verity_tools, workflow_tools};
+pub use tools::{
+    batch_workflow_tools, noteman_tools, platform_tools, shipcheck_tools, verity_tools,
+    workflow_run_tools, workflow_tools,
+};
 
 // Re-export service clients
-pub use clients::{NoteManClient, ServiceConfig, ShipCheckClient, VerityClient};
+pub use clients::{
+    NoteManClient, ServiceAuthConfig, ServiceConfig, ServiceRegistry, ServiceTokenProvider,
+    ShipCheckClient, VerityClient,
+};
 
 // Re-export health check types
 pub use health::{
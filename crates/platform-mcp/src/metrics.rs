@@ -0,0 +1,224 @@
+//! OpenMetrics exposition for integration metrics.
+//!
+//! [`MetricsCollector`](crate::health::MetricsCollector) records per-service
+//! request counts and latencies, tool-call outcomes, and the latest health and
+//! circuit state of each service. [`MetricsCollector::render_openmetrics`]
+//! formats them in the OpenMetrics text format for Prometheus to scrape:
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `platform_mcp_service_requests_total` | counter | `service`, `outcome` |
+//! | `platform_mcp_service_request_duration_seconds` | histogram | `service` |
+//! | `platform_mcp_service_request_latency_seconds` | summary (p50/p95/p99) | `service` |
+//! | `platform_mcp_tool_calls_total` | counter | `tool`, `outcome` |
+//! | `platform_mcp_hedged_requests_total` | counter | `service`, `winner` |
+//! | `platform_mcp_service_up` | gauge | `service` |
+//! | `platform_mcp_service_health_latency_seconds` | gauge | `service` |
+//! | `platform_mcp_circuit_open` | gauge | `service` |
+//! | `platform_mcp_uptime_seconds` | gauge | |
+//!
+//! With the `http` feature, [`crate::http`] serves the output at `/metrics`.
+//!
+//! [`MetricsCollector::render_openmetrics`]: crate::health::MetricsCollector::render_openmetrics
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Content type of the OpenMetrics text format.
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Upper bounds of the latency histogram buckets, in milliseconds.
+pub const LATENCY_BUCKETS_MS: &[u64] = &[5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Quantiles reported for request latency.
+pub const LATENCY_QUANTILES: &[f64] = &[0.5, 0.95, 0.99];
+
+/// Outcome of a tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolOutcome {
+    /// The tool returned a successful result.
+    Success,
+    /// The tool returned an error result.
+    Error,
+    /// The tool failed to execute (invalid parameters, internal error).
+    Failed,
+    /// The caller lacked a required permission.
+    Denied,
+}
+
+impl ToolOutcome {
+    /// Get the outcome label value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Error => "error",
+            Self::Failed => "failed",
+            Self::Denied => "denied",
+        }
+    }
+}
+
+/// Cumulative latency histogram with fixed buckets ([`LATENCY_BUCKETS_MS`]).
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    /// Observations per bucket; the last bucket is `+Inf`.
+    buckets: Vec<u64>,
+
+    /// Sum of observed latencies in milliseconds.
+    sum_ms: u64,
+
+    /// Number of observations.
+    count: u64,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram.
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; LATENCY_BUCKETS_MS.len() + 1],
+            sum_ms: 0,
+            count: 0,
+        }
+    }
+
+    /// Record one latency.
+    pub fn observe(&mut self, latency_ms: u64) {
+        let index = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[index] += 1;
+        self.sum_ms += latency_ms;
+        self.count += 1;
+    }
+
+    /// Get the number of observations.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Get the sum of observed latencies in milliseconds.
+    pub fn sum_ms(&self) -> u64 {
+        self.sum_ms
+    }
+
+    /// Get cumulative counts per bucket upper bound; `None` is `+Inf`.
+    pub fn cumulative(&self) -> Vec<(Option<u64>, u64)> {
+        let mut total = 0;
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                total += count;
+                (LATENCY_BUCKETS_MS.get(i).copied(), total)
+            })
+            .collect()
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get a quantile of sorted latencies.
+pub(crate) fn percentile(sorted: &[u64], quantile: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = (sorted.len() as f64 * quantile) as usize;
+    sorted[index.min(sorted.len() - 1)] as f64
+}
+
+/// Incremental OpenMetrics text writer.
+pub(crate) struct OpenMetricsWriter {
+    out: String,
+}
+
+impl OpenMetricsWriter {
+    pub(crate) fn new() -> Self {
+        Self { out: String::new() }
+    }
+
+    /// Start a metric family.
+    pub(crate) fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+    }
+
+    /// Write one sample.
+    pub(crate) fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{}=\"{}\"", key, escape_label(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+    }
+
+    /// Finish the exposition.
+    pub(crate) fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+/// Format a bucket bound or quantile as a label value.
+pub(crate) fn format_value(value: f64) -> String {
+    if value.is_infinite() {
+        return if value > 0.0 { "+Inf" } else { "-Inf" }.to_string();
+    }
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.1}", value)
+    } else {
+        format!("{}", value)
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let mut histogram = LatencyHistogram::new();
+        for latency in [3, 7, 80, 20000] {
+            histogram.observe(latency);
+        }
+
+        let buckets = histogram.cumulative();
+        assert_eq!(buckets[0], (Some(5), 1));
+        assert_eq!(buckets[1], (Some(10), 2));
+        assert_eq!(buckets[4], (Some(100), 3));
+        assert_eq!(buckets.last(), Some(&(None, 4)));
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum_ms(), 20090);
+    }
+
+    #[test]
+    fn test_writer_escapes_labels() {
+        let mut writer = OpenMetricsWriter::new();
+        writer.family("demo", "gauge", "Demo metric.");
+        writer.sample("demo", &[("tool", "a\"b\\c")], 1.0);
+        let out = writer.finish();
+
+        assert!(out.contains("demo{tool=\"a\\\"b\\\\c\"} 1.0\n"));
+        assert!(out.ends_with("# EOF\n"));
+    }
+}
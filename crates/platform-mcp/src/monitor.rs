@@ -0,0 +1,518 @@
+//! Background health monitoring.
+//!
+//! A [`HealthMonitor`] runs [`HealthChecker::check_all`] on an interval (with
+//! jitter, so replicas do not probe in lockstep) and keeps a bounded history of
+//! results per service. When a service moves between `Healthy`, `Degraded` and
+//! `Unhealthy`, the monitor publishes a `system.health.changed` event.
+//!
+//! Clients obtained from a [`ServiceRegistry`](crate::clients::ServiceRegistry)
+//! with a monitor attached fail fast while the deployment they call is
+//! unhealthy, instead of waiting for the request to time out. Health is keyed
+//! by base URL, so organizations routed to a dedicated deployment are not
+//! failed by an outage of the shared one, and vice versa. A service counts as
+//! down only after [`HealthMonitorConfig::failure_threshold`] consecutive
+//! failed checks, so a single lost probe does not fail calls for a whole
+//! interval. When the checker records metrics,
+//! the monitor reports this as the service's open circuit
+//! (`platform_mcp_circuit_open`).
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::{ServiceConfig, ServiceRegistry};
+//! use platform_mcp::health::{HealthCheckConfig, HealthChecker};
+//! use platform_mcp::monitor::{HealthMonitor, HealthMonitorConfig};
+//! use platform_events::MemoryEventBus;
+//! use std::sync::Arc;
+//!
+//! async fn start() {
+//!     let config = ServiceConfig::from_env();
+//!     let checker = HealthChecker::new(config.clone(), HealthCheckConfig::default());
+//!     let monitor = Arc::new(
+//!         HealthMonitor::new(checker, HealthMonitorConfig::default())
+//!             .with_event_bus(Arc::new(MemoryEventBus::new())),
+//!     );
+//!     let _task = monitor.clone().spawn();
+//!
+//!     let services = ServiceRegistry::new(config).with_health_monitor(monitor);
+//! }
+//! ```
+
+use crate::health::{HealthChecker, HealthReport, HealthStatus, ServiceStatus};
+use crate::retry::random_unit;
+use crate::server::ToolContext;
+use chrono::{DateTime, Utc};
+use platform_events::{Event, EventBus};
+use platform_rbac::App;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Event type published when a service's health status changes.
+pub const HEALTH_CHANGED_EVENT: &str = "system.health.changed";
+
+/// Health monitor configuration.
+#[derive(Debug, Clone)]
+pub struct HealthMonitorConfig {
+    /// Time between checks.
+    pub interval: Duration,
+
+    /// Maximum random delay added to each interval.
+    pub jitter: Duration,
+
+    /// Number of results kept per service.
+    pub history_size: usize,
+
+    /// Consecutive failed checks before calls to a service fail fast.
+    pub failure_threshold: u32,
+}
+
+impl Default for HealthMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            jitter: Duration::from_secs(5),
+            history_size: 20,
+            failure_threshold: 3,
+        }
+    }
+}
+
+/// Result of one health check of a service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthSample {
+    /// Service status.
+    pub status: HealthStatus,
+
+    /// Response latency in milliseconds.
+    pub latency_ms: u64,
+
+    /// When the check ran.
+    pub checked_at: DateTime<Utc>,
+
+    /// Error message if the check failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A change in a service's health status.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthChange {
+    /// Service name (lowercase, e.g. `verity`).
+    pub service: String,
+
+    /// Previous status, `None` on the first check.
+    pub previous: Option<HealthStatus>,
+
+    /// New status.
+    pub status: HealthStatus,
+
+    /// When the change was observed.
+    pub changed_at: DateTime<Utc>,
+
+    /// Error message if the service is unhealthy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HealthChange {
+    /// Convert to a platform event.
+    pub fn to_event(&self) -> Event {
+        Event::new(
+            HEALTH_CHANGED_EVENT,
+            App::Shared,
+            serde_json::to_value(self).unwrap_or_default(),
+        )
+    }
+}
+
+/// Tracked state of one service.
+#[derive(Debug, Default)]
+struct ServiceState {
+    /// Recent results, oldest first.
+    history: VecDeque<HealthSample>,
+
+    /// When the service entered its current status.
+    since: Option<DateTime<Utc>>,
+
+    /// Base URL of the checked deployment.
+    url: String,
+
+    /// Number of consecutive failed checks.
+    failures: u32,
+}
+
+/// Periodically checks service health and publishes status changes.
+pub struct HealthMonitor {
+    /// Checker used for each round.
+    checker: HealthChecker,
+
+    /// Monitor configuration.
+    config: HealthMonitorConfig,
+
+    /// Bus receiving `system.health.changed` events.
+    bus: Option<Arc<dyn EventBus>>,
+
+    /// State per service.
+    services: Mutex<HashMap<String, ServiceState>>,
+}
+
+impl HealthMonitor {
+    /// Create a monitor.
+    pub fn new(checker: HealthChecker, config: HealthMonitorConfig) -> Self {
+        Self {
+            checker,
+            config,
+            bus: None,
+            services: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Publish status changes on `bus`.
+    pub fn with_event_bus(mut self, bus: Arc<dyn EventBus>) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Start checking in the background.
+    ///
+    /// The first check runs immediately. Abort the returned handle to stop.
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                self.check_now().await;
+                tokio::time::sleep(jittered(self.config.interval, self.config.jitter)).await;
+            }
+        })
+    }
+
+    /// Check all services once, record the results and publish changes.
+    ///
+    /// The events of one round share a correlation ID.
+    pub async fn check_now(&self) -> Vec<HealthChange> {
+        let report = self.checker.check_all().await;
+        let changes = self.record(&report);
+        if let Some(metrics) = self.checker.metrics() {
+            for health in &report.services {
+                let service = health.name.to_lowercase();
+                metrics.set_circuit_open(&service, self.down_since(&service).is_some());
+            }
+        }
+        let context = ToolContext::empty().traced();
+
+        for change in &changes {
+            info!(
+                service = %change.service,
+                previous = ?change.previous,
+                status = ?change.status,
+                "Service health changed"
+            );
+            if let Some(ref bus) = self.bus {
+                if let Err(e) = bus.publish(context.stamp_event(change.to_event())).await {
+                    warn!(error = %e, "Failed to publish health change");
+                }
+            }
+        }
+        changes
+    }
+
+    /// Get the current status of a service, if it has been checked.
+    pub fn status(&self, service: &str) -> Option<HealthStatus> {
+        let services = self.services();
+        services
+            .get(&service.to_lowercase())
+            .and_then(|state| state.history.back())
+            .map(|sample| sample.status)
+    }
+
+    /// Get recent results for a service, oldest first.
+    pub fn history(&self, service: &str) -> Vec<HealthSample> {
+        let services = self.services();
+        services
+            .get(&service.to_lowercase())
+            .map(|state| state.history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get when a service became unhealthy, if it is considered down.
+    ///
+    /// A service is down once it has failed
+    /// [`HealthMonitorConfig::failure_threshold`] checks in a row.
+    pub fn down_since(&self, service: &str) -> Option<DateTime<Utc>> {
+        let services = self.services();
+        self.down(services.get(&service.to_lowercase())?)
+    }
+
+    /// Get when the deployment at `base_url` became unhealthy, if it is
+    /// considered down.
+    ///
+    /// Returns `None` for deployments the monitor does not check, such as an
+    /// organization's dedicated endpoints.
+    pub fn down_since_url(&self, base_url: &str) -> Option<DateTime<Utc>> {
+        let base_url = base_url.trim_end_matches('/');
+        let services = self.services();
+        services
+            .values()
+            .filter(|state| state.url.trim_end_matches('/') == base_url)
+            .find_map(|state| self.down(state))
+    }
+
+    fn down(&self, state: &ServiceState) -> Option<DateTime<Utc>> {
+        if state.failures >= self.config.failure_threshold.max(1) {
+            state.since
+        } else {
+            None
+        }
+    }
+
+    /// Record a health report and return the resulting status changes.
+    ///
+    /// The first result for a service counts as a change only when the
+    /// service is not healthy.
+    fn record(&self, report: &HealthReport) -> Vec<HealthChange> {
+        let now = Utc::now();
+        let mut services = self.services();
+        let mut changes = Vec::new();
+
+        for health in &report.services {
+            let Some(status) = health_status(health.status) else {
+                continue;
+            };
+            let name = health.name.to_lowercase();
+            let state = services.entry(name.clone()).or_default();
+            let previous = state.history.back().map(|sample| sample.status);
+            state.url.clone_from(&health.url);
+            state.failures = match status {
+                HealthStatus::Unhealthy => state.failures.saturating_add(1),
+                _ => 0,
+            };
+
+            if previous != Some(status) {
+                state.since = Some(now);
+                if previous.is_some() || status != HealthStatus::Healthy {
+                    changes.push(HealthChange {
+                        service: name,
+                        previous,
+                        status,
+                        changed_at: now,
+                        error: health.error.clone(),
+                    });
+                }
+            }
+
+            state.history.push_back(HealthSample {
+                status,
+                latency_ms: health.latency_ms,
+                checked_at: now,
+                error: health.error.clone(),
+            });
+            while state.history.len() > self.config.history_size.max(1) {
+                state.history.pop_front();
+            }
+        }
+        changes
+    }
+
+    fn services(&self) -> std::sync::MutexGuard<'_, HashMap<String, ServiceState>> {
+        self.services.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl std::fmt::Debug for HealthMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthMonitor")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Map a service check result onto the health status scale.
+fn health_status(status: ServiceStatus) -> Option<HealthStatus> {
+    match status {
+        ServiceStatus::Up => Some(HealthStatus::Healthy),
+        ServiceStatus::Degraded => Some(HealthStatus::Degraded),
+        ServiceStatus::Down => Some(HealthStatus::Unhealthy),
+        ServiceStatus::Unknown => None,
+    }
+}
+
+/// Add a random delay of up to `jitter` to `interval`.
+fn jittered(interval: Duration, jitter: Duration) -> Duration {
+    let jitter_ms = jitter.as_millis() as u64;
+    if jitter_ms == 0 {
+        return interval;
+    }
+    interval + Duration::from_millis((random_unit() * (jitter_ms + 1) as f64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::ServiceConfig;
+    use crate::health::{HealthCheckConfig, ServiceHealth};
+
+    fn monitor(history_size: usize, failure_threshold: u32) -> HealthMonitor {
+        HealthMonitor::new(
+            HealthChecker::new(ServiceConfig::default(), HealthCheckConfig::default()),
+            HealthMonitorConfig {
+                history_size,
+                failure_threshold,
+                ..HealthMonitorConfig::default()
+            },
+        )
+    }
+
+    fn report(statuses: &[(&str, ServiceStatus)]) -> HealthReport {
+        HealthReport {
+            status: HealthStatus::Healthy,
+            timestamp: Utc::now().to_rfc3339(),
+            services: statuses
+                .iter()
+                .map(|(name, status)| ServiceHealth {
+                    name: name.to_string(),
+                    status: *status,
+                    latency_ms: 10,
+                    url: format!("http://{}.test", name.to_lowercase()),
+                    error: (*status == ServiceStatus::Down).then(|| "HTTP 503".to_string()),
+                    last_success: None,
+                    version: None,
+                })
+                .collect(),
+            check_duration_ms: 10,
+            platform_version: String::new(),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_reports_transitions() {
+        let monitor = monitor(2, 1);
+
+        // A healthy first result is not a change.
+        let changes = monitor.record(&report(&[
+            ("Verity", ServiceStatus::Up),
+            ("NoteMan", ServiceStatus::Down),
+        ]));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].service, "noteman");
+        assert_eq!(changes[0].previous, None);
+        assert!(monitor.down_since("noteman").is_some());
+
+        let changes = monitor.record(&report(&[
+            ("Verity", ServiceStatus::Degraded),
+            ("NoteMan", ServiceStatus::Down),
+        ]));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].previous, Some(HealthStatus::Healthy));
+        assert_eq!(changes[0].status, HealthStatus::Degraded);
+
+        monitor.record(&report(&[("NoteMan", ServiceStatus::Up)]));
+        assert_eq!(monitor.status("NoteMan"), Some(HealthStatus::Healthy));
+        assert!(monitor.down_since("noteman").is_none());
+
+        // History is bounded.
+        let history = monitor.history("noteman");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status, HealthStatus::Unhealthy);
+    }
+
+    #[test]
+    fn test_service_is_down_after_consecutive_failures() {
+        let monitor = monitor(20, 2);
+
+        monitor.record(&report(&[("Verity", ServiceStatus::Down)]));
+        assert_eq!(monitor.status("verity"), Some(HealthStatus::Unhealthy));
+        assert!(monitor.down_since("verity").is_none());
+
+        // A success resets the count.
+        monitor.record(&report(&[("Verity", ServiceStatus::Up)]));
+        monitor.record(&report(&[("Verity", ServiceStatus::Down)]));
+        assert!(monitor.down_since("verity").is_none());
+
+        monitor.record(&report(&[("Verity", ServiceStatus::Down)]));
+        assert!(monitor.down_since("verity").is_some());
+    }
+
+    #[test]
+    fn test_down_since_url_matches_checked_deployment() {
+        let monitor = monitor(20, 1);
+        monitor.record(&report(&[("Verity", ServiceStatus::Down)]));
+
+        assert!(monitor.down_since_url("http://verity.test").is_some());
+        assert!(monitor.down_since_url("http://verity.test/").is_some());
+        assert!(monitor
+            .down_since_url("https://verity.acme.internal")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_check_reports_open_circuits() {
+        let unreachable = crate::clients::config::ServiceEndpoint {
+            base_url: "http://127.0.0.1:9".to_string(),
+            api_key: None,
+            webhook_secret: None,
+        };
+        let config = ServiceConfig {
+            noteman: unreachable,
+            ..ServiceConfig::default()
+        };
+        let metrics = Arc::new(crate::health::MetricsCollector::new());
+        let monitor = HealthMonitor::new(
+            HealthChecker::new(config, HealthCheckConfig::default()).with_metrics(metrics.clone()),
+            HealthMonitorConfig {
+                failure_threshold: 2,
+                ..HealthMonitorConfig::default()
+            },
+        );
+
+        monitor.check_now().await;
+        let out = metrics.render_openmetrics();
+        assert!(out.contains("platform_mcp_circuit_open{service=\"noteman\"} 0.0\n"));
+
+        monitor.check_now().await;
+        let out = metrics.render_openmetrics();
+        assert!(out.contains("platform_mcp_circuit_open{service=\"noteman\"} 1.0\n"));
+    }
+
+    #[tokio::test]
+    async fn test_published_changes_carry_correlation_id() {
+        let unreachable = crate::clients::config::ServiceEndpoint {
+            base_url: "http://127.0.0.1:9".to_string(),
+            api_key: None,
+            webhook_secret: None,
+        };
+        let config = ServiceConfig {
+            noteman: unreachable.clone(),
+            shipcheck: unreachable.clone(),
+            verity: unreachable,
+            ..ServiceConfig::default()
+        };
+        let bus = Arc::new(platform_events::MemoryEventBus::new());
+        let mut events = bus.subscribe("shared.system.health.changed").await.unwrap();
+        let monitor = HealthMonitor::new(
+            HealthChecker::new(config, HealthCheckConfig::default()),
+            HealthMonitorConfig::default(),
+        )
+        .with_event_bus(bus.clone());
+
+        let changes = monitor.check_now().await;
+        assert_eq!(changes.len(), 3);
+        let first = events.recv().await.unwrap();
+        assert!(first.correlation_id.is_some());
+        for _ in 1..changes.len() {
+            let next = events.recv().await.unwrap();
+            assert_eq!(next.correlation_id, first.correlation_id);
+        }
+    }
+
+    #[test]
+    fn test_jitter_stays_in_range() {
+        let interval = Duration::from_secs(30);
+        for _ in 0..100 {
+            let delay = jittered(interval, Duration::from_secs(5));
+            assert!(delay >= interval && delay <= Duration::from_secs(35));
+        }
+        assert_eq!(jittered(interval, Duration::ZERO), interval);
+    }
+}
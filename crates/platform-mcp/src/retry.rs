@@ -3,6 +3,21 @@
 //! This module provides utilities for retrying operations that may fail
 //! transiently, using exponential backoff to avoid overwhelming services.
 //!
+//! Three further safeguards keep retries from piling onto a recovering
+//! service:
+//!
+//! - **Jitter** ([`Jitter`], opt in with [`RetryConfig::with_jitter`])
+//!   randomizes delays so that many sessions failing at once do not retry in
+//!   lockstep.
+//! - **Retry budgets** ([`RetryBudget`]) cap the rate of retries against a
+//!   service across all callers sharing the budget.
+//! - **Deadlines** ([`RetryConfig::deadline`]) stop retrying when the
+//!   remaining time cannot fit another attempt.
+//!
+//! For latency-sensitive reads, [`with_hedge`] sends a second request when the
+//! first has not answered within a percentile of recent latencies, and uses
+//! whichever answers first. Hedges draw from the same retry budgets.
+//!
 //! # Example
 //!
 //! ```rust,no_run
@@ -15,7 +30,9 @@
 //!         initial_delay: Duration::from_millis(100),
 //!         max_delay: Duration::from_secs(10),
 //!         exponential_base: 2.0,
-//!     };
+//!         ..RetryConfig::default()
+//!     }
+//!     .with_deadline(Duration::from_secs(5));
 //!
 //!     with_retry(&config, || async {
 //!         // Your operation here
@@ -24,9 +41,24 @@
 //! }
 //! ```
 
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
+/// How retry delays are randomized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Jitter {
+    /// Plain exponential backoff.
+    #[default]
+    None,
+    /// A random delay between zero and the exponential backoff.
+    Full,
+    /// A random delay between `initial_delay` and three times the previous
+    /// delay, capped at `max_delay`.
+    Decorrelated,
+}
+
 /// Configuration for retry behavior.
 ///
 /// Controls how many times to retry an operation and how long to wait
@@ -44,6 +76,15 @@ pub struct RetryConfig {
 
     /// Base for exponential backoff (typically 2.0)
     pub exponential_base: f64,
+
+    /// Randomization of delays
+    pub jitter: Jitter,
+
+    /// Total time allowed for all attempts, including delays
+    pub deadline: Option<Duration>,
+
+    /// Budget retries are drawn from, shared with other callers
+    pub budget: Option<Arc<RetryBudget>>,
 }
 
 impl Default for RetryConfig {
@@ -53,6 +94,9 @@ impl Default for RetryConfig {
             initial_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(10),
             exponential_base: 2.0,
+            jitter: Jitter::default(),
+            deadline: None,
+            budget: None,
         }
     }
 }
@@ -72,6 +116,9 @@ impl RetryConfig {
             initial_delay: Duration::from_millis(50),
             max_delay: Duration::from_secs(1),
             exponential_base: 2.0,
+            jitter: Jitter::default(),
+            deadline: None,
+            budget: None,
         }
     }
 
@@ -100,6 +147,9 @@ impl RetryConfig {
             initial_delay: Duration::from_millis(500),
             max_delay: Duration::from_secs(30),
             exponential_base: 2.0,
+            jitter: Jitter::default(),
+            deadline: None,
+            budget: None,
         }
     }
 
@@ -116,14 +166,476 @@ impl RetryConfig {
             initial_delay: Duration::from_millis(0),
             max_delay: Duration::from_millis(0),
             exponential_base: 1.0,
+            jitter: Jitter::None,
+            deadline: None,
+            budget: None,
+        }
+    }
+
+    /// Set how delays are randomized.
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Stop retrying once another attempt would not finish within
+    /// `deadline` of the first attempt.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Draw each retry from `budget`, giving up when it is empty.
+    pub fn with_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Get the exponential backoff before retry number `retry` (from 1),
+    /// without jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = self.exponential_base.powi(retry.saturating_sub(1) as i32);
+        Duration::from_secs_f64(
+            (self.initial_delay.as_secs_f64() * factor).min(self.max_delay.as_secs_f64()),
+        )
+    }
+
+    /// Get the delay before retry number `retry`, given the previous delay.
+    fn delay(&self, retry: u32, previous: Duration) -> Duration {
+        match self.jitter {
+            Jitter::None => self.backoff(retry),
+            Jitter::Full => self.backoff(retry).mul_f64(random_unit()),
+            Jitter::Decorrelated => {
+                let low = self.initial_delay.as_secs_f64();
+                let high = (previous.as_secs_f64() * 3.0).max(low);
+                Duration::from_secs_f64(
+                    (low + (high - low) * random_unit()).min(self.max_delay.as_secs_f64()),
+                )
+            }
+        }
+    }
+}
+
+/// Token bucket limiting how often callers may retry.
+///
+/// Each retry takes one token; tokens refill at a fixed rate up to the
+/// bucket's capacity. Share one budget per service (see [`RetryBudgets`]) so
+/// that a failing service sees a bounded retry rate no matter how many
+/// sessions call it.
+pub struct RetryBudget {
+    /// Maximum number of tokens.
+    capacity: f64,
+
+    /// Tokens added per second.
+    refill_per_sec: f64,
+
+    /// Available tokens and when they were last refilled.
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RetryBudget {
+    /// Create a full budget of `capacity` retries, refilled at
+    /// `refill_per_sec` retries per second.
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity: f64::from(capacity),
+            refill_per_sec,
+            state: Mutex::new((f64::from(capacity), Instant::now())),
+        }
+    }
+
+    /// Take a token for one retry, returning `false` if none is available.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        self.refill(&mut state);
+        if state.0 >= 1.0 {
+            state.0 -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Get the number of whole retries currently available.
+    pub fn available(&self) -> u32 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        self.refill(&mut state);
+        state.0 as u32
+    }
+
+    fn refill(&self, state: &mut (f64, Instant)) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.1).as_secs_f64();
+        state.0 = (state.0 + elapsed * self.refill_per_sec).min(self.capacity);
+        state.1 = now;
+    }
+}
+
+impl std::fmt::Debug for RetryBudget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryBudget")
+            .field("capacity", &self.capacity)
+            .field("refill_per_sec", &self.refill_per_sec)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// Retry budgets keyed by service name.
+#[derive(Debug)]
+pub struct RetryBudgets {
+    /// Capacity of each budget.
+    capacity: u32,
+
+    /// Refill rate of each budget.
+    refill_per_sec: f64,
+
+    /// Budgets created so far.
+    budgets: Mutex<HashMap<String, Arc<RetryBudget>>>,
+}
+
+impl RetryBudgets {
+    /// Create budgets of `capacity` retries refilled at `refill_per_sec`.
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+            budgets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the budget for `service`, creating it on first use.
+    pub fn get(&self, service: &str) -> Arc<RetryBudget> {
+        let mut budgets = self.budgets.lock().unwrap_or_else(|e| e.into_inner());
+        budgets
+            .entry(service.to_string())
+            .or_insert_with(|| Arc::new(RetryBudget::new(self.capacity, self.refill_per_sec)))
+            .clone()
+    }
+}
+
+impl Default for RetryBudgets {
+    /// Budgets of 10 retries refilled at one retry per second.
+    fn default() -> Self {
+        Self::new(10, 1.0)
+    }
+}
+
+/// Configuration for hedged requests.
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    /// Latency percentile after which a hedge is sent (e.g. 0.95)
+    pub percentile: f64,
+
+    /// Shortest delay before hedging
+    pub min_delay: Duration,
+
+    /// Longest delay before hedging, also used until enough latencies are known
+    pub max_delay: Duration,
+
+    /// Number of recent latencies the percentile is computed over
+    pub window: usize,
+
+    /// Minimum number of latencies before the percentile is used
+    pub min_samples: usize,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 0.95,
+            min_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(2),
+            window: 100,
+            min_samples: 20,
         }
     }
 }
 
+/// Hedging policy for one service.
+///
+/// Tracks recent latencies of the service to derive the hedge delay.
+pub struct HedgePolicy {
+    /// Hedge configuration.
+    config: HedgeConfig,
+
+    /// Budget hedges are drawn from.
+    budget: Option<Arc<RetryBudget>>,
+
+    /// Recent latencies in milliseconds.
+    latencies: Mutex<VecDeque<u64>>,
+}
+
+impl HedgePolicy {
+    /// Create a policy with no latency history.
+    pub fn new(config: HedgeConfig) -> Self {
+        Self {
+            config,
+            budget: None,
+            latencies: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Draw each hedge from `budget`, sending none when it is empty.
+    pub fn with_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Get the budget hedges are drawn from, if any.
+    pub fn budget(&self) -> Option<&Arc<RetryBudget>> {
+        self.budget.as_ref()
+    }
+
+    /// Get the current delay before a hedge is sent.
+    pub fn delay(&self) -> Duration {
+        let latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        if latencies.len() < self.config.min_samples.max(1) {
+            return self.config.max_delay;
+        }
+        let mut sorted: Vec<u64> = latencies.iter().copied().collect();
+        sorted.sort_unstable();
+        let delay = Duration::from_millis(crate::metrics::percentile(
+            &sorted,
+            self.config.percentile,
+        ) as u64);
+        delay.clamp(self.config.min_delay, self.config.max_delay)
+    }
+
+    /// Record the latency of a successful request.
+    pub fn observe(&self, latency: Duration) {
+        let mut latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        latencies.push_back(latency.as_millis() as u64);
+        while latencies.len() > self.config.window.max(1) {
+            latencies.pop_front();
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        self.budget
+            .as_ref()
+            .is_none_or(|budget| budget.try_acquire())
+    }
+}
+
+impl std::fmt::Debug for HedgePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HedgePolicy")
+            .field("config", &self.config)
+            .field("budget", &self.budget)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Result of a hedged call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HedgeOutcome {
+    /// The first request answered before the hedge delay, or the budget
+    /// allowed no hedge.
+    NotHedged,
+    /// A hedge was sent and the first request still answered first.
+    PrimaryWon,
+    /// A hedge was sent and answered first.
+    HedgeWon,
+    /// A hedge was sent and both requests failed.
+    BothFailed,
+}
+
+impl HedgeOutcome {
+    /// Get which request answered first (`"primary"`, `"hedge"`, or
+    /// `"none"` if both failed), or `None` if no hedge was sent.
+    pub fn winner(&self) -> Option<&'static str> {
+        match self {
+            Self::NotHedged => None,
+            Self::PrimaryWon => Some("primary"),
+            Self::HedgeWon => Some("hedge"),
+            Self::BothFailed => Some("none"),
+        }
+    }
+}
+
+/// Execute a read with hedging.
+///
+/// Calls `f` once; if it has not completed within the policy's delay, calls
+/// it again and returns whichever call succeeds first. If one call fails, the
+/// other is awaited. `f` must be safe to call twice, so only hedge reads.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use platform_mcp::retry::{with_hedge, HedgeConfig, HedgePolicy};
+///
+/// async fn example() -> Result<String, String> {
+///     let policy = HedgePolicy::new(HedgeConfig::default());
+///
+///     with_hedge(&policy, || async {
+///         // A read that is sometimes slow
+///         Ok("results".to_string())
+///     }).await
+/// }
+/// ```
+pub async fn with_hedge<F, Fut, T, E>(policy: &HedgePolicy, f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    hedge(policy, Result::is_ok, f).await.0
+}
+
+/// Execute a read with hedging, reporting whether a hedge was sent and won.
+///
+/// Only a result for which `accept` holds can win; otherwise the other call
+/// is awaited, so a hedge that fails fast does not hide a slow success.
+pub(crate) async fn hedge<F, Fut, T, E>(
+    policy: &HedgePolicy,
+    accept: fn(&Result<T, E>) -> bool,
+    mut f: F,
+) -> (Result<T, E>, HedgeOutcome)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let observe = |result: &Result<T, E>, start: Instant| {
+        if accept(result) {
+            policy.observe(start.elapsed());
+        }
+    };
+
+    let primary_start = Instant::now();
+    let primary = f();
+    tokio::pin!(primary);
+
+    tokio::select! {
+        result = &mut primary => {
+            observe(&result, primary_start);
+            return (result, HedgeOutcome::NotHedged);
+        }
+        _ = sleep(policy.delay()) => {}
+    }
+
+    if !policy.try_acquire() {
+        let result = primary.await;
+        observe(&result, primary_start);
+        return (result, HedgeOutcome::NotHedged);
+    }
+
+    tracing::debug!("Request exceeded hedge delay, sending hedge");
+    let hedge_start = Instant::now();
+    let hedge = f();
+    tokio::pin!(hedge);
+
+    tokio::select! {
+        result = &mut primary => {
+            if accept(&result) {
+                observe(&result, primary_start);
+                return (result, HedgeOutcome::PrimaryWon);
+            }
+            let result = hedge.await;
+            observe(&result, hedge_start);
+            let outcome = if accept(&result) {
+                HedgeOutcome::HedgeWon
+            } else {
+                HedgeOutcome::BothFailed
+            };
+            (result, outcome)
+        },
+        result = &mut hedge => {
+            if accept(&result) {
+                observe(&result, hedge_start);
+                return (result, HedgeOutcome::HedgeWon);
+            }
+            let result = primary.await;
+            observe(&result, primary_start);
+            let outcome = if accept(&result) {
+                HedgeOutcome::PrimaryWon
+            } else {
+                HedgeOutcome::BothFailed
+            };
+            (result, outcome)
+        },
+    }
+}
+
+/// Check whether a hedged HTTP request can win: it got a response whose
+/// status is not worth retrying.
+pub(crate) fn accept_response<E>(result: &Result<reqwest::Response, E>) -> bool {
+    result
+        .as_ref()
+        .is_ok_and(|response| !retryable_status(response.status()))
+}
+
+/// Check whether a response status is worth retrying: rate limiting or a
+/// server error.
+pub(crate) fn retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Outcome of one attempt of an HTTP request.
+#[derive(Debug)]
+enum Attempt<E> {
+    /// The request failed.
+    Failed(E),
+
+    /// The service answered with a retryable status.
+    Status(reqwest::Response),
+}
+
+/// Send an HTTP request with `send`, retrying according to `config` on
+/// errors `is_retryable` accepts and on retryable statuses.
+///
+/// When retries run out, the last response or error is returned. Requests
+/// whose body cannot be cloned are sent once.
+pub(crate) async fn retry_request<F, Fut, E>(
+    config: &RetryConfig,
+    request: reqwest::RequestBuilder,
+    mut send: F,
+    is_retryable: fn(&E) -> bool,
+) -> Result<reqwest::Response, E>
+where
+    F: FnMut(reqwest::RequestBuilder) -> Fut,
+    Fut: std::future::Future<Output = Result<reqwest::Response, E>>,
+    E: std::fmt::Debug,
+{
+    if request.try_clone().is_none() {
+        return send(request).await;
+    }
+
+    let result = with_retry_if(
+        config,
+        || {
+            let attempt = send(request.try_clone().expect("request body is cloneable"));
+            async move {
+                match attempt.await {
+                    Ok(response) if retryable_status(response.status()) => {
+                        Err(Attempt::Status(response))
+                    }
+                    Ok(response) => Ok(response),
+                    Err(e) => Err(Attempt::Failed(e)),
+                }
+            }
+        },
+        |attempt| match attempt {
+            Attempt::Failed(e) => is_retryable(e),
+            Attempt::Status(_) => true,
+        },
+    )
+    .await;
+    match result {
+        Ok(response) | Err(Attempt::Status(response)) => Ok(response),
+        Err(Attempt::Failed(e)) => Err(e),
+    }
+}
+
+/// Get a uniformly distributed random number in `[0, 1)`.
+pub(crate) fn random_unit() -> f64 {
+    rand::random()
+}
+
 /// Execute a function with retries.
 ///
 /// The function will be called up to `max_attempts` times. If it fails,
-/// the call will wait with exponential backoff before retrying.
+/// the call will wait with exponential backoff before retrying. Retrying
+/// also stops when the configured deadline or retry budget runs out.
 ///
 /// # Arguments
 ///
@@ -148,52 +660,13 @@ impl RetryConfig {
 ///     }).await
 /// }
 /// ```
-pub async fn with_retry<F, Fut, T, E>(config: &RetryConfig, mut f: F) -> Result<T, E>
+pub async fn with_retry<F, Fut, T, E>(config: &RetryConfig, f: F) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
     E: std::fmt::Debug,
 {
-    let mut attempt = 0;
-    let mut delay = config.initial_delay;
-
-    loop {
-        attempt += 1;
-
-        match f().await {
-            Ok(result) => {
-                if attempt > 1 {
-                    tracing::info!(attempts = attempt, "Operation succeeded after retry");
-                }
-                return Ok(result);
-            }
-            Err(e) if attempt >= config.max_attempts => {
-                tracing::error!(
-                    attempts = attempt,
-                    error = ?e,
-                    "All retry attempts exhausted"
-                );
-                return Err(e);
-            }
-            Err(e) => {
-                tracing::warn!(
-                    attempt = attempt,
-                    max_attempts = config.max_attempts,
-                    delay_ms = delay.as_millis(),
-                    error = ?e,
-                    "Attempt failed, retrying"
-                );
-
-                sleep(delay).await;
-
-                // Calculate next delay with exponential backoff
-                delay = Duration::from_secs_f64(
-                    (delay.as_secs_f64() * config.exponential_base)
-                        .min(config.max_delay.as_secs_f64()),
-                );
-            }
-        }
-    }
+    with_retry_if(config, f, |_| true).await
 }
 
 /// Execute a function with retries and a custom predicate for retryable errors.
@@ -247,13 +720,19 @@ where
     E: std::fmt::Debug,
     P: FnMut(&E) -> bool,
 {
+    let start = Instant::now();
     let mut attempt = 0;
     let mut delay = config.initial_delay;
+    let mut longest_attempt = Duration::ZERO;
 
     loop {
         attempt += 1;
 
-        match f().await {
+        let attempt_start = Instant::now();
+        let result = f().await;
+        longest_attempt = longest_attempt.max(attempt_start.elapsed());
+
+        match result {
             Ok(result) => {
                 if attempt > 1 {
                     tracing::info!(attempts = attempt, "Operation succeeded after retry");
@@ -276,6 +755,32 @@ where
                 return Err(e);
             }
             Err(e) => {
+                delay = config.delay(attempt, delay);
+
+                // Give up if another attempt of the longest duration seen so
+                // far would end past the deadline.
+                if let Some(deadline) = config.deadline {
+                    if start.elapsed() + delay + longest_attempt > deadline {
+                        tracing::warn!(
+                            attempts = attempt,
+                            deadline_ms = deadline.as_millis(),
+                            error = ?e,
+                            "Retry deadline reached"
+                        );
+                        return Err(e);
+                    }
+                }
+                if let Some(ref budget) = config.budget {
+                    if !budget.try_acquire() {
+                        tracing::warn!(
+                            attempts = attempt,
+                            error = ?e,
+                            "Retry budget exhausted"
+                        );
+                        return Err(e);
+                    }
+                }
+
                 tracing::warn!(
                     attempt = attempt,
                     max_attempts = config.max_attempts,
@@ -285,12 +790,6 @@ where
                 );
 
                 sleep(delay).await;
-
-                // Calculate next delay with exponential backoff
-                delay = Duration::from_secs_f64(
-                    (delay.as_secs_f64() * config.exponential_base)
-                        .min(config.max_delay.as_secs_f64()),
-                );
             }
         }
     }
@@ -372,6 +871,7 @@ mod tests {
             initial_delay: Duration::from_millis(1),
             max_delay: Duration::from_millis(10),
             exponential_base: 2.0,
+            ..RetryConfig::default()
         };
 
         let counter = Arc::new(AtomicU32::new(0));
@@ -412,4 +912,200 @@ mod tests {
         assert_eq!(result, Err("permanent failure"));
         assert_eq!(counter.load(Ordering::SeqCst), 1); // Only tried once
     }
+
+    #[test]
+    fn test_jitter_delays() {
+        let config = RetryConfig {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            ..RetryConfig::default()
+        };
+
+        let plain = config.clone().with_jitter(Jitter::None);
+        assert_eq!(plain.delay(1, Duration::ZERO), Duration::from_millis(100));
+        assert_eq!(plain.delay(3, Duration::ZERO), Duration::from_millis(400));
+        assert_eq!(plain.delay(10, Duration::ZERO), Duration::from_secs(1));
+
+        assert_eq!(config.jitter, Jitter::None);
+        for retry in 1..6 {
+            let full = config
+                .clone()
+                .with_jitter(Jitter::Full)
+                .delay(retry, Duration::ZERO);
+            assert!(full <= config.backoff(retry));
+
+            let previous = Duration::from_millis(200);
+            let decorrelated = config
+                .clone()
+                .with_jitter(Jitter::Decorrelated)
+                .delay(retry, previous);
+            assert!(decorrelated >= Duration::from_millis(100));
+            assert!(decorrelated <= Duration::from_millis(600));
+        }
+    }
+
+    #[test]
+    fn test_retry_budget_refills() {
+        let budget = RetryBudget::new(2, 1000.0);
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(budget.try_acquire());
+
+        let budgets = RetryBudgets::default();
+        assert!(Arc::ptr_eq(&budgets.get("verity"), &budgets.get("verity")));
+        assert!(!Arc::ptr_eq(
+            &budgets.get("verity"),
+            &budgets.get("noteman")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_stops_when_budget_is_empty() {
+        let budget = Arc::new(RetryBudget::new(1, 0.0));
+        let config = RetryConfig {
+            max_attempts: 5,
+            initial_delay: Duration::from_millis(1),
+            ..RetryConfig::default()
+        }
+        .with_budget(budget.clone());
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let result = with_retry(&config, || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err::<i32, _>("unavailable")
+            }
+        })
+        .await;
+
+        assert_eq!(result, Err("unavailable"));
+        // One retry drawn from the budget, then no more.
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        assert_eq!(budget.available(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_respects_deadline() {
+        let config = RetryConfig {
+            max_attempts: 10,
+            initial_delay: Duration::from_millis(20),
+            max_delay: Duration::from_millis(20),
+            jitter: Jitter::None,
+            ..RetryConfig::default()
+        }
+        .with_deadline(Duration::from_millis(50));
+
+        let counter = Arc::new(AtomicU32::new(0));
+        let result = with_retry(&config, || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err::<i32, _>("slow")
+            }
+        })
+        .await;
+
+        assert_eq!(result, Err("slow"));
+        // Attempts at 0ms and ~20ms; a third at ~40ms could still fit, a
+        // fourth at ~60ms could not.
+        let attempts = counter.load(Ordering::SeqCst);
+        assert!((2..=3).contains(&attempts), "{} attempts", attempts);
+    }
+
+    type Call = std::pin::Pin<Box<dyn std::future::Future<Output = Result<u32, String>>>>;
+
+    /// Returns a call whose first invocation takes `first` and later ones
+    /// take `rest`, tagging results with the invocation number.
+    fn calls(first: Duration, rest: Duration) -> (Arc<AtomicU32>, impl FnMut() -> Call) {
+        let counter = Arc::new(AtomicU32::new(0));
+        let calls = counter.clone();
+        let f = move || {
+            let n = calls.fetch_add(1, Ordering::SeqCst);
+            let delay = if n == 0 { first } else { rest };
+            Box::pin(async move {
+                sleep(delay).await;
+                Ok(n)
+            }) as Call
+        };
+        (counter, f)
+    }
+
+    #[tokio::test]
+    async fn test_hedge_wins_when_primary_is_slow() {
+        let policy = HedgePolicy::new(HedgeConfig {
+            max_delay: Duration::from_millis(20),
+            ..HedgeConfig::default()
+        });
+        let (counter, f) = calls(Duration::from_millis(500), Duration::from_millis(5));
+
+        let (result, outcome) = hedge(&policy, Result::is_ok, f).await;
+        assert_eq!(result, Ok(1));
+        assert_eq!(outcome, HedgeOutcome::HedgeWon);
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_not_sent_for_fast_call_or_empty_budget() {
+        let policy = HedgePolicy::new(HedgeConfig {
+            max_delay: Duration::from_millis(20),
+            ..HedgeConfig::default()
+        });
+        let (counter, f) = calls(Duration::from_millis(1), Duration::from_millis(1));
+        assert_eq!(
+            hedge(&policy, Result::is_ok, f).await,
+            (Ok(0), HedgeOutcome::NotHedged)
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        let policy = policy.with_budget(Arc::new(RetryBudget::new(0, 0.0)));
+        let (counter, f) = calls(Duration::from_millis(50), Duration::from_millis(1));
+        assert_eq!(
+            hedge(&policy, Result::is_ok, f).await,
+            (Ok(0), HedgeOutcome::NotHedged)
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_reports_both_failed() {
+        let policy = HedgePolicy::new(HedgeConfig {
+            max_delay: Duration::from_millis(20),
+            ..HedgeConfig::default()
+        });
+        let f = || async {
+            sleep(Duration::from_millis(50)).await;
+            Err::<u32, _>("unavailable".to_string())
+        };
+
+        let (result, outcome) = hedge(&policy, Result::is_ok, f).await;
+        assert!(result.is_err());
+        assert_eq!(outcome, HedgeOutcome::BothFailed);
+        assert_eq!(outcome.winner(), Some("none"));
+    }
+
+    #[test]
+    fn test_hedge_delay_follows_percentile() {
+        let policy = HedgePolicy::new(HedgeConfig {
+            percentile: 0.9,
+            min_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(500),
+            window: 10,
+            min_samples: 5,
+        });
+        assert_eq!(policy.delay(), Duration::from_millis(500));
+
+        for ms in [20, 30, 40, 50, 60, 70, 80, 90, 100, 200] {
+            policy.observe(Duration::from_millis(ms));
+        }
+        assert_eq!(policy.delay(), Duration::from_millis(200));
+
+        // Only the most recent window counts.
+        for _ in 0..10 {
+            policy.observe(Duration::from_millis(1));
+        }
+        assert_eq!(policy.delay(), Duration::from_millis(10));
+    }
 }
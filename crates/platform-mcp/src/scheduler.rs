@@ -0,0 +1,999 @@
+//! Scheduled and recurring workflow runs.
+//!
+//! A [`Scheduler`] runs tools, usually workflow tools, on cron schedules with
+//! stored arguments, e.g. `workflow_verify_documentation` every Monday at
+//! 06:00 (`0 6 * * MON`). Each [`Schedule`] belongs to an organization and
+//! runs under a service identity: the tool context carries the organization
+//! and the permissions of the caller who registered the schedule but no
+//! user, so clients authenticate with service credentials only. Registering
+//! a schedule requires the tool's permissions in the schedule's organization.
+//!
+//! - **Missed runs**: occurrences missed while the scheduler was not running
+//!   are skipped, run once, or all run, per [`MissedRunPolicy`].
+//! - **No overlap**: an occurrence is skipped while the previous run of the
+//!   same schedule is still in progress.
+//! - **Results**: every run publishes a `workflow.schedule.completed`,
+//!   `workflow.schedule.failed` or `workflow.schedule.skipped` event.
+//!
+//! Schedules are kept in a [`ScheduleStore`]. Use a [`FileScheduleStore`] so
+//! that schedules, and the occurrences they missed, survive a restart.
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::ServiceRegistry;
+//! use platform_mcp::scheduler::{Schedule, Scheduler, SchedulerConfig};
+//! use platform_mcp::tools::workflow_tools;
+//! use platform_mcp::ToolContext;
+//! use platform_events::MemoryEventBus;
+//! use std::sync::Arc;
+//! use uuid::Uuid;
+//!
+//! async fn start(org_id: Uuid, caller: &ToolContext) {
+//!     let services = Arc::new(ServiceRegistry::from_env());
+//!     let scheduler = Arc::new(
+//!         Scheduler::new(workflow_tools(&services), SchedulerConfig::default())
+//!             .with_event_bus(Arc::new(MemoryEventBus::new())),
+//!     );
+//!     scheduler
+//!         .add_schedule(
+//!             Schedule::new("nightly-sync", org_id, "workflow_sync_action_items", "0 2 * * *")
+//!                 .with_arguments(serde_json::json!({"meeting_id": "weekly-standup"})),
+//!             caller,
+//!         )
+//!         .unwrap();
+//!     let _task = scheduler.clone().spawn();
+//! }
+//! ```
+
+use crate::server::{Tool, ToolContext};
+use crate::types::ToolResult;
+use chrono::{DateTime, Utc};
+use croner::Cron;
+use platform_events::{Event, EventBus};
+use platform_rbac::App;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Event type published when a scheduled run completes.
+pub const SCHEDULE_COMPLETED_EVENT: &str = "workflow.schedule.completed";
+
+/// Event type published when a scheduled run fails.
+pub const SCHEDULE_FAILED_EVENT: &str = "workflow.schedule.failed";
+
+/// Event type published when a scheduled occurrence is skipped.
+pub const SCHEDULE_SKIPPED_EVENT: &str = "workflow.schedule.skipped";
+
+/// Errors from managing schedules.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SchedulerError {
+    /// The schedule is invalid, e.g. its cron expression does not parse.
+    #[error("Invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    /// The schedule runs a tool the scheduler does not have.
+    #[error("Unknown tool: {0}")]
+    UnknownTool(String),
+
+    /// The caller may not schedule the tool for the schedule's organization.
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// The schedule store failed.
+    #[error("Schedule store error: {0}")]
+    Store(String),
+}
+
+/// What to do with occurrences missed while the scheduler was not running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedRunPolicy {
+    /// Skip missed occurrences; only run on time.
+    #[default]
+    Skip,
+
+    /// Run once for all missed occurrences.
+    RunOnce,
+
+    /// Run every missed occurrence, up to [`SchedulerConfig::max_catch_up`].
+    RunAll,
+}
+
+/// Outcome of a scheduled run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledRun {
+    /// Occurrence the run was for.
+    pub scheduled_for: DateTime<Utc>,
+
+    /// When the run finished.
+    pub finished_at: DateTime<Utc>,
+
+    /// Error message if the run failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ScheduledRun {
+    /// Check whether the run succeeded.
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// A tool run on a cron schedule for an organization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Unique schedule ID.
+    pub id: String,
+
+    /// Organization the tool runs for.
+    pub org_id: Uuid,
+
+    /// Name of the tool to run.
+    pub tool: String,
+
+    /// Cron expression in UTC, e.g. `0 6 * * MON`.
+    pub cron: String,
+
+    /// Tool arguments.
+    #[serde(default)]
+    pub arguments: Value,
+
+    /// Permissions the tool runs with, those of the caller who registered
+    /// the schedule.
+    #[serde(default)]
+    pub permissions: Vec<String>,
+
+    /// What to do with missed occurrences.
+    #[serde(default)]
+    pub missed_runs: MissedRunPolicy,
+
+    /// Whether the schedule is active.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Next occurrence to run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+
+    /// Outcome of the most recent run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<ScheduledRun>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Schedule {
+    /// Create a schedule running `tool` for `org_id` on `cron`.
+    pub fn new(
+        id: impl Into<String>,
+        org_id: Uuid,
+        tool: impl Into<String>,
+        cron: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            org_id,
+            tool: tool.into(),
+            cron: cron.into(),
+            arguments: Value::Object(Default::default()),
+            permissions: Vec::new(),
+            missed_runs: MissedRunPolicy::default(),
+            enabled: true,
+            next_run_at: None,
+            last_run: None,
+        }
+    }
+
+    /// Set the tool arguments.
+    pub fn with_arguments(mut self, arguments: Value) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Set what to do with missed occurrences.
+    pub fn with_missed_runs(mut self, policy: MissedRunPolicy) -> Self {
+        self.missed_runs = policy;
+        self
+    }
+
+    /// Enable or disable the schedule.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Get the first occurrence after `time`.
+    pub fn next_after(&self, time: DateTime<Utc>) -> Result<DateTime<Utc>, SchedulerError> {
+        self.parse_cron()?
+            .find_next_occurrence(&time, false)
+            .map_err(|e| SchedulerError::InvalidSchedule(format!("{}: {}", self.id, e)))
+    }
+
+    fn parse_cron(&self) -> Result<Cron, SchedulerError> {
+        Cron::new(&self.cron)
+            .parse()
+            .map_err(|e| SchedulerError::InvalidSchedule(format!("{}: {}", self.id, e)))
+    }
+}
+
+/// Stores schedules.
+pub trait ScheduleStore: Send + Sync {
+    /// Insert or replace a schedule.
+    fn save(&self, schedule: &Schedule) -> Result<(), SchedulerError>;
+
+    /// Remove a schedule, returning whether it existed.
+    fn remove(&self, id: &str) -> Result<bool, SchedulerError>;
+
+    /// Get a schedule by ID.
+    fn get(&self, id: &str) -> Result<Option<Schedule>, SchedulerError>;
+
+    /// List all schedules.
+    fn list(&self) -> Result<Vec<Schedule>, SchedulerError>;
+}
+
+/// In-memory schedule store.
+#[derive(Debug, Default)]
+pub struct MemoryScheduleStore {
+    schedules: Mutex<HashMap<String, Schedule>>,
+}
+
+impl MemoryScheduleStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScheduleStore for MemoryScheduleStore {
+    fn save(&self, schedule: &Schedule) -> Result<(), SchedulerError> {
+        let mut schedules = self.schedules.lock().unwrap_or_else(|e| e.into_inner());
+        schedules.insert(schedule.id.clone(), schedule.clone());
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) -> Result<bool, SchedulerError> {
+        let mut schedules = self.schedules.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(schedules.remove(id).is_some())
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Schedule>, SchedulerError> {
+        let schedules = self.schedules.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(schedules.get(id).cloned())
+    }
+
+    fn list(&self) -> Result<Vec<Schedule>, SchedulerError> {
+        let schedules = self.schedules.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(schedules.values().cloned().collect())
+    }
+}
+
+/// Schedule store keeping all schedules in one JSON file.
+///
+/// The file is rewritten through a temporary file and a rename, so a crash
+/// mid-write leaves the previous version intact.
+#[derive(Debug)]
+pub struct FileScheduleStore {
+    /// Path of the JSON file.
+    path: PathBuf,
+
+    /// Serializes read-modify-write cycles.
+    lock: Mutex<()>,
+}
+
+impl FileScheduleStore {
+    /// Open the store at `path`; the file is created on the first save.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read(&self) -> Result<HashMap<String, Schedule>, SchedulerError> {
+        if !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let data = fs::read(&self.path).map_err(|e| SchedulerError::Store(e.to_string()))?;
+        serde_json::from_slice(&data).map_err(|e| SchedulerError::Store(e.to_string()))
+    }
+
+    fn write(&self, schedules: &HashMap<String, Schedule>) -> Result<(), SchedulerError> {
+        let data = serde_json::to_vec_pretty(schedules)
+            .map_err(|e| SchedulerError::Store(e.to_string()))?;
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, data)
+            .and_then(|_| fs::rename(&tmp, &self.path))
+            .map_err(|e| SchedulerError::Store(e.to_string()))
+    }
+}
+
+impl ScheduleStore for FileScheduleStore {
+    fn save(&self, schedule: &Schedule) -> Result<(), SchedulerError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut schedules = self.read()?;
+        schedules.insert(schedule.id.clone(), schedule.clone());
+        self.write(&schedules)
+    }
+
+    fn remove(&self, id: &str) -> Result<bool, SchedulerError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut schedules = self.read()?;
+        let removed = schedules.remove(id).is_some();
+        if removed {
+            self.write(&schedules)?;
+        }
+        Ok(removed)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Schedule>, SchedulerError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.remove(id))
+    }
+
+    fn list(&self) -> Result<Vec<Schedule>, SchedulerError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.read()?.into_values().collect())
+    }
+}
+
+/// Scheduler configuration.
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// Time between checks for due schedules.
+    pub interval: Duration,
+
+    /// How late an occurrence may start before it counts as missed.
+    pub grace: Duration,
+
+    /// Maximum number of missed occurrences run under
+    /// [`MissedRunPolicy::RunAll`]; older ones are skipped.
+    pub max_catch_up: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            grace: Duration::from_secs(5 * 60),
+            max_catch_up: 10,
+        }
+    }
+}
+
+/// Runs tools on their schedules.
+pub struct Scheduler {
+    /// Tools schedules can run, by name.
+    tools: HashMap<String, Arc<dyn Tool>>,
+
+    /// Configuration.
+    config: SchedulerConfig,
+
+    /// Schedules.
+    store: Arc<dyn ScheduleStore>,
+
+    /// Bus the results are published on.
+    bus: Option<Arc<dyn EventBus>>,
+
+    /// IDs of the schedules with a run in progress.
+    running: Mutex<HashSet<String>>,
+}
+
+impl Scheduler {
+    /// Create a scheduler for `tools`, keeping schedules in memory.
+    pub fn new(tools: Vec<Arc<dyn Tool>>, config: SchedulerConfig) -> Self {
+        Self {
+            tools: tools
+                .into_iter()
+                .map(|tool| (tool.definition().name, tool))
+                .collect(),
+            config,
+            store: Arc::new(MemoryScheduleStore::new()),
+            bus: None,
+            running: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Keep schedules in `store`.
+    pub fn with_store(mut self, store: Arc<dyn ScheduleStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Publish run results on `bus`.
+    pub fn with_event_bus(mut self, bus: Arc<dyn EventBus>) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
+    /// Register a schedule on behalf of the caller in `context`, replacing
+    /// any schedule with the same ID.
+    ///
+    /// The caller must belong to the schedule's organization and have the
+    /// tool's required permissions; the schedule runs with the caller's
+    /// permissions. It first runs at the next occurrence after now.
+    pub fn add_schedule(
+        &self,
+        mut schedule: Schedule,
+        context: &ToolContext,
+    ) -> Result<(), SchedulerError> {
+        if schedule.id.is_empty() {
+            return Err(SchedulerError::InvalidSchedule(
+                "missing schedule ID".to_string(),
+            ));
+        }
+        let Some(tool) = self.tools.get(&schedule.tool) else {
+            return Err(SchedulerError::UnknownTool(schedule.tool));
+        };
+        if context.org_id != Some(schedule.org_id) {
+            return Err(SchedulerError::PermissionDenied(format!(
+                "not a member of organization {}",
+                schedule.org_id
+            )));
+        }
+        if let Some(missing) = missing_permission(tool.as_ref(), &context.permissions) {
+            return Err(SchedulerError::PermissionDenied(format!(
+                "Missing permission: {}",
+                missing
+            )));
+        }
+        schedule.permissions = context.permissions.clone();
+        schedule.next_run_at = Some(schedule.next_after(Utc::now())?);
+        if let Some(existing) = self.store.get(&schedule.id)? {
+            schedule.last_run = existing.last_run;
+        }
+
+        info!(schedule = %schedule.id, org_id = %schedule.org_id, tool = %schedule.tool, cron = %schedule.cron, "Registered schedule");
+        self.store.save(&schedule)
+    }
+
+    /// Remove a schedule, returning whether it existed.
+    pub fn remove_schedule(&self, id: &str) -> Result<bool, SchedulerError> {
+        self.store.remove(id)
+    }
+
+    /// Get a schedule by ID.
+    pub fn schedule(&self, id: &str) -> Result<Option<Schedule>, SchedulerError> {
+        self.store.get(id)
+    }
+
+    /// Get the schedules of an organization.
+    pub fn schedules_for_org(&self, org_id: Uuid) -> Result<Vec<Schedule>, SchedulerError> {
+        let mut schedules: Vec<_> = self
+            .store
+            .list()?
+            .into_iter()
+            .filter(|s| s.org_id == org_id)
+            .collect();
+        schedules.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(schedules)
+    }
+
+    /// Start checking for due schedules in the background.
+    ///
+    /// Abort the returned handle to stop; runs in progress continue.
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                self.tick(Utc::now());
+                tokio::time::sleep(self.config.interval).await;
+            }
+        })
+    }
+
+    /// Start the runs of every schedule due at `now`.
+    ///
+    /// Returns the handles of the started runs.
+    pub fn tick(self: &Arc<Self>, now: DateTime<Utc>) -> Vec<JoinHandle<()>> {
+        let schedules = match self.store.list() {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                warn!(error = %e, "Failed to list schedules");
+                return Vec::new();
+            }
+        };
+
+        let mut handles = Vec::new();
+        for mut schedule in schedules {
+            if !schedule.enabled {
+                continue;
+            }
+            match self.due(&mut schedule, now) {
+                Ok(occurrences) => {
+                    if let Some(handle) = self.start(schedule, occurrences) {
+                        handles.push(handle);
+                    }
+                }
+                Err(e) => warn!(schedule = %schedule.id, error = %e, "Failed to schedule run"),
+            }
+        }
+        handles
+    }
+
+    /// Advance a schedule past `now`, returning the occurrences to run.
+    fn due(
+        &self,
+        schedule: &mut Schedule,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, SchedulerError> {
+        let Some(next) = schedule.next_run_at else {
+            schedule.next_run_at = Some(schedule.next_after(now)?);
+            self.store.save(schedule)?;
+            return Ok(Vec::new());
+        };
+        if next > now {
+            return Ok(Vec::new());
+        }
+
+        let cron = schedule.parse_cron()?;
+        let mut due: Vec<_> = cron
+            .iter_from(next)
+            .take_while(|occurrence| *occurrence <= now)
+            .collect();
+        schedule.next_run_at = Some(schedule.next_after(now)?);
+        self.store.save(schedule)?;
+
+        let grace = chrono::Duration::from_std(self.config.grace).unwrap_or_default();
+        let on_time = |occurrence: &DateTime<Utc>| now - *occurrence <= grace;
+        let missed = due.iter().filter(|o| !on_time(o)).count();
+        if missed > 0 {
+            info!(schedule = %schedule.id, missed, policy = ?schedule.missed_runs, "Schedule missed occurrences");
+        }
+
+        Ok(match schedule.missed_runs {
+            MissedRunPolicy::Skip => due.into_iter().rfind(on_time).into_iter().collect(),
+            MissedRunPolicy::RunOnce => due.pop().into_iter().collect(),
+            MissedRunPolicy::RunAll => {
+                let skip = due.len().saturating_sub(self.config.max_catch_up);
+                due.split_off(skip)
+            }
+        })
+    }
+
+    /// Run the occurrences of a schedule in order, unless a run of the
+    /// schedule is already in progress.
+    fn start(
+        self: &Arc<Self>,
+        schedule: Schedule,
+        occurrences: Vec<DateTime<Utc>>,
+    ) -> Option<JoinHandle<()>> {
+        let first = *occurrences.first()?;
+        let started = self
+            .running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(schedule.id.clone());
+        let scheduler = self.clone();
+
+        if !started {
+            info!(schedule = %schedule.id, "Previous run still in progress, skipping");
+            return Some(tokio::spawn(async move {
+                let event = schedule_event(
+                    SCHEDULE_SKIPPED_EVENT,
+                    &schedule,
+                    first,
+                    json!({"reason": "overlap"}),
+                )
+                .with_correlation_id(call_id(&schedule, first));
+                scheduler.publish(event).await;
+            }));
+        }
+
+        Some(tokio::spawn(async move {
+            let _running = Running {
+                scheduler: scheduler.clone(),
+                id: schedule.id.clone(),
+            };
+            for occurrence in occurrences {
+                scheduler.run(&schedule, occurrence).await;
+            }
+        }))
+    }
+
+    /// Run one occurrence of a schedule and publish the result.
+    async fn run(&self, schedule: &Schedule, occurrence: DateTime<Utc>) {
+        let Some(tool) = self.tools.get(&schedule.tool) else {
+            warn!(schedule = %schedule.id, tool = %schedule.tool, "Scheduled tool is not registered");
+            return;
+        };
+
+        // Service identity: the organization and the permissions of the
+        // schedule's creator, but no user. The request ID keeps retries of
+        // one occurrence idempotent.
+        let call_id = call_id(schedule, occurrence);
+        let context = ToolContext {
+            org_id: Some(schedule.org_id),
+            permissions: schedule.permissions.clone(),
+            request_id: Some(call_id.clone()),
+            correlation_id: Some(call_id),
+            ..ToolContext::empty()
+        };
+
+        info!(schedule = %schedule.id, tool = %schedule.tool, scheduled_for = %occurrence, "Running scheduled tool");
+        let result = match missing_permission(tool.as_ref(), &context.permissions) {
+            Some(missing) => Err(format!("Missing permission: {}", missing)),
+            None => tool
+                .execute(schedule.arguments.clone(), &context)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(ToolResult::into_output),
+        };
+
+        let event = match result {
+            Ok(ref output) => schedule_event(
+                SCHEDULE_COMPLETED_EVENT,
+                schedule,
+                occurrence,
+                json!({"output": output}),
+            ),
+            Err(ref error) => {
+                warn!(schedule = %schedule.id, error = %error, "Scheduled run failed");
+                schedule_event(
+                    SCHEDULE_FAILED_EVENT,
+                    schedule,
+                    occurrence,
+                    json!({"error": error}),
+                )
+            }
+        };
+        let event = context.stamp_event(event);
+        self.record(
+            &schedule.id,
+            ScheduledRun {
+                scheduled_for: occurrence,
+                finished_at: Utc::now(),
+                error: result.err(),
+            },
+        );
+        self.publish(event).await;
+    }
+
+    /// Store the outcome of a schedule's latest run.
+    fn record(&self, id: &str, run: ScheduledRun) {
+        let result = self.store.get(id).and_then(|schedule| match schedule {
+            Some(mut schedule) => {
+                schedule.last_run = Some(run);
+                self.store.save(&schedule)
+            }
+            None => Ok(()),
+        });
+        if let Err(e) = result {
+            warn!(schedule = %id, error = %e, "Failed to record scheduled run");
+        }
+    }
+
+    async fn publish(&self, event: Event) {
+        if let Some(ref bus) = self.bus {
+            if let Err(e) = bus.publish(event).await {
+                warn!(error = %e, "Failed to publish schedule event");
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Get the first of `tool`'s required permissions missing from
+/// `permissions`.
+fn missing_permission(tool: &dyn Tool, permissions: &[String]) -> Option<String> {
+    tool.definition()
+        .required_permissions
+        .into_iter()
+        .find(|required| !permissions.contains(required))
+}
+
+/// Marks a schedule's run as in progress until dropped, including when the
+/// run panics.
+struct Running {
+    scheduler: Arc<Scheduler>,
+    id: String,
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        self.scheduler
+            .running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+    }
+}
+
+/// Get the request and correlation ID of a schedule's occurrence.
+fn call_id(schedule: &Schedule, occurrence: DateTime<Utc>) -> String {
+    format!("schedule:{}:{}", schedule.id, occurrence.timestamp())
+}
+
+/// Build an event about an occurrence of a schedule, adding `details` to the
+/// payload.
+fn schedule_event(
+    event_type: &str,
+    schedule: &Schedule,
+    occurrence: DateTime<Utc>,
+    details: Value,
+) -> Event {
+    let mut payload = json!({
+        "schedule_id": schedule.id,
+        "tool": schedule.tool,
+        "scheduled_for": occurrence,
+    });
+    if let (Some(payload), Value::Object(details)) = (payload.as_object_mut(), details) {
+        payload.extend(details);
+    }
+    Event::new(event_type, App::Shared, payload).with_org(schedule.org_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::McpServerResult;
+    use crate::types::ToolDefinition;
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use platform_events::MemoryEventBus;
+    use tokio::sync::Semaphore;
+
+    /// Counts its calls, waiting for a permit first when gated.
+    struct Counter {
+        calls: Mutex<Vec<ToolContext>>,
+        gate: Option<Semaphore>,
+    }
+
+    #[async_trait]
+    impl Tool for Counter {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new("workflow_test", "")
+                .with_permissions(vec!["repository:read".to_string()])
+        }
+
+        async fn execute(
+            &self,
+            _args: Value,
+            context: &ToolContext,
+        ) -> McpServerResult<ToolResult> {
+            if let Some(ref gate) = self.gate {
+                gate.acquire().await.unwrap().forget();
+            }
+            self.calls.lock().unwrap().push(context.clone());
+            Ok(ToolResult::json(json!({"status": "ok"})))
+        }
+    }
+
+    /// Panics on every call.
+    struct Panicking;
+
+    #[async_trait]
+    impl Tool for Panicking {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new("workflow_test", "")
+        }
+
+        async fn execute(
+            &self,
+            _args: Value,
+            _context: &ToolContext,
+        ) -> McpServerResult<ToolResult> {
+            panic!("tool failed");
+        }
+    }
+
+    fn scheduler(counter: Arc<Counter>) -> Arc<Scheduler> {
+        Arc::new(Scheduler::new(vec![counter], SchedulerConfig::default()))
+    }
+
+    fn counter(gate: Option<Semaphore>) -> Arc<Counter> {
+        Arc::new(Counter {
+            calls: Mutex::new(Vec::new()),
+            gate,
+        })
+    }
+
+    /// Permissions of the callers registering the test schedules.
+    fn permissions() -> Vec<String> {
+        vec!["repository:read".to_string(), "meeting:read".to_string()]
+    }
+
+    fn caller(org_id: Uuid) -> ToolContext {
+        ToolContext {
+            org_id: Some(org_id),
+            permissions: permissions(),
+            ..ToolContext::empty()
+        }
+    }
+
+    /// An hourly schedule whose next occurrence was three hours before `now`.
+    fn hourly(policy: MissedRunPolicy, now: DateTime<Utc>) -> Schedule {
+        let mut schedule = Schedule::new("hourly", Uuid::new_v4(), "workflow_test", "0 * * * *")
+            .with_missed_runs(policy);
+        schedule.permissions = permissions();
+        schedule.next_run_at = Some(now - chrono::Duration::minutes(3 * 60 + 1));
+        schedule
+    }
+
+    #[tokio::test]
+    async fn test_missed_run_policies() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 1, 0).unwrap();
+        for (policy, runs) in [
+            (MissedRunPolicy::Skip, 1),
+            (MissedRunPolicy::RunOnce, 1),
+            (MissedRunPolicy::RunAll, 4),
+        ] {
+            let counter = counter(None);
+            let scheduler = scheduler(counter.clone());
+            scheduler.store.save(&hourly(policy, now)).unwrap();
+
+            for handle in scheduler.tick(now) {
+                handle.await.unwrap();
+            }
+            assert_eq!(counter.calls.lock().unwrap().len(), runs, "{:?}", policy);
+
+            let schedule = scheduler.schedule("hourly").unwrap().unwrap();
+            assert_eq!(
+                schedule.next_run_at,
+                Some(Utc.with_ymd_and_hms(2026, 3, 2, 13, 0, 0).unwrap())
+            );
+            assert!(schedule.last_run.unwrap().succeeded());
+        }
+
+        // Nothing on time: a skipping schedule only moves on.
+        let counter = counter(None);
+        let scheduler = scheduler(counter.clone());
+        let late = now + chrono::Duration::minutes(30);
+        scheduler
+            .store
+            .save(&hourly(MissedRunPolicy::Skip, now))
+            .unwrap();
+        assert!(scheduler.tick(late).is_empty());
+        assert!(counter.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_runs_do_not_overlap_and_publish_results() {
+        let counter = counter(Some(Semaphore::new(0)));
+        let bus = Arc::new(MemoryEventBus::new());
+        let mut events = bus.subscribe("shared.workflow.schedule.*").await.unwrap();
+        let scheduler = Arc::new(
+            Scheduler::new(vec![counter.clone()], SchedulerConfig::default())
+                .with_event_bus(bus.clone()),
+        );
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+        let mut schedule =
+            Schedule::new("every-minute", Uuid::new_v4(), "workflow_test", "* * * * *");
+        schedule.permissions = permissions();
+        schedule.next_run_at = Some(now);
+        scheduler.store.save(&schedule).unwrap();
+
+        let running = scheduler.tick(now);
+        // The next occurrence comes due while the first run is blocked.
+        let later = now + chrono::Duration::minutes(1);
+        for handle in scheduler.tick(later) {
+            handle.await.unwrap();
+        }
+        let skipped = events.recv().await.unwrap();
+        assert_eq!(skipped.event_type, SCHEDULE_SKIPPED_EVENT);
+        assert_eq!(skipped.payload["reason"], "overlap");
+        assert_eq!(
+            skipped.correlation_id,
+            Some(format!("schedule:every-minute:{}", later.timestamp()))
+        );
+
+        counter.gate.as_ref().unwrap().add_permits(1);
+        for handle in running {
+            handle.await.unwrap();
+        }
+        let completed = events.recv().await.unwrap();
+        assert_eq!(completed.event_type, SCHEDULE_COMPLETED_EVENT);
+        assert_eq!(completed.org_id, Some(schedule.org_id));
+        assert_eq!(completed.payload["output"], json!({"status": "ok"}));
+        assert_eq!(
+            completed.correlation_id,
+            Some(format!("schedule:every-minute:{}", now.timestamp()))
+        );
+
+        // The run used the service identity with the creator's permissions.
+        let calls = counter.calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].user_id.is_none());
+        assert_eq!(calls[0].org_id, Some(schedule.org_id));
+        assert_eq!(calls[0].permissions, permissions());
+    }
+
+    #[tokio::test]
+    async fn test_panicking_run_does_not_block_the_schedule() {
+        let scheduler = Arc::new(Scheduler::new(
+            vec![Arc::new(Panicking)],
+            SchedulerConfig::default(),
+        ));
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+        let mut schedule = Schedule::new("panics", Uuid::new_v4(), "workflow_test", "* * * * *");
+        schedule.next_run_at = Some(now);
+        scheduler.store.save(&schedule).unwrap();
+
+        for handle in scheduler.tick(now) {
+            assert!(handle.await.unwrap_err().is_panic());
+        }
+        assert!(scheduler.running.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_runs_need_the_tool_permissions() {
+        let counter = counter(None);
+        let scheduler = scheduler(counter.clone());
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+        let mut schedule = Schedule::new("stale", Uuid::new_v4(), "workflow_test", "* * * * *");
+        schedule.next_run_at = Some(now);
+        scheduler.store.save(&schedule).unwrap();
+
+        for handle in scheduler.tick(now) {
+            handle.await.unwrap();
+        }
+        assert!(counter.calls.lock().unwrap().is_empty());
+        let last_run = scheduler.schedule("stale").unwrap().unwrap().last_run;
+        assert_eq!(
+            last_run.unwrap().error.as_deref(),
+            Some("Missing permission: repository:read")
+        );
+    }
+
+    #[test]
+    fn test_add_schedule_validates() {
+        let scheduler = scheduler(counter(None));
+        let org = Uuid::new_v4();
+        let weekly = || Schedule::new("weekly", org, "workflow_test", "0 6 * * MON");
+        assert!(matches!(
+            scheduler.add_schedule(
+                Schedule::new("bad", org, "workflow_test", "every day"),
+                &caller(org)
+            ),
+            Err(SchedulerError::InvalidSchedule(_))
+        ));
+        assert_eq!(
+            scheduler.add_schedule(
+                Schedule::new("unknown", org, "other", "0 * * * *"),
+                &caller(org)
+            ),
+            Err(SchedulerError::UnknownTool("other".to_string()))
+        );
+        assert!(matches!(
+            scheduler.add_schedule(weekly(), &caller(Uuid::new_v4())),
+            Err(SchedulerError::PermissionDenied(_))
+        ));
+        let reader = ToolContext {
+            permissions: vec!["meeting:read".to_string()],
+            ..caller(org)
+        };
+        assert_eq!(
+            scheduler.add_schedule(weekly(), &reader),
+            Err(SchedulerError::PermissionDenied(
+                "Missing permission: repository:read".to_string()
+            ))
+        );
+
+        scheduler.add_schedule(weekly(), &caller(org)).unwrap();
+        let schedules = scheduler.schedules_for_org(org).unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert!(schedules[0].next_run_at.unwrap() > Utc::now());
+        assert_eq!(schedules[0].permissions, permissions());
+        assert!(scheduler
+            .schedules_for_org(Uuid::new_v4())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,598 @@
+//! Federated search across NoteMan, ShipCheck and Verity.
+//!
+//! [`FederatedSearch`] sends one query with one [`SearchFilter`] to Verity's
+//! knowledge base, NoteMan's meetings and ShipCheck's findings in parallel,
+//! and merges the answers into a single list of [`SearchHit`]s.
+//!
+//! ## Ranking
+//!
+//! The apps score results on unrelated scales, so hits are ranked with
+//! reciprocal-rank fusion: a hit at rank `r` (from 1) in its app's list
+//! scores `1 / (k + r)`, with `k` = [`DEFAULT_RRF_K`]. Each app's best hit
+//! thus scores the same, and lower ranks fall off slowly.
+//!
+//! ## Linked items
+//!
+//! A Verity fact taken from a NoteMan meeting or ShipCheck finding refers to
+//! it by `source_app` and `external_id`. When both are found, the fact is
+//! folded into the item it came from: the item's score adds the fact's, and
+//! the fact's key is listed in the item's `linked` keys.
+//!
+//! ## Failures
+//!
+//! An app that fails or times out does not fail the search. Its error is
+//! reported in [`SearchResponse::sources`] and the other apps' hits are
+//! returned.
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::ServiceRegistry;
+//! use platform_mcp::search::{FederatedSearch, SearchFilter, SearchRequest};
+//! use platform_mcp::ToolContext;
+//! use std::sync::Arc;
+//!
+//! async fn search() {
+//!     let search = FederatedSearch::new(Arc::new(ServiceRegistry::from_env()));
+//!     let request = SearchRequest::new("Q4 revenue")
+//!         .with_filter(SearchFilter::new().with_project("proj-billing"));
+//!     let response = search.search(&request, &ToolContext::empty()).await;
+//!     for hit in response.results {
+//!         println!("{:.4} {} {}", hit.score, hit.key(), hit.title);
+//!     }
+//! }
+//! ```
+
+use crate::clients::registry::ServiceRegistry;
+use crate::clients::{noteman, shipcheck, verity};
+use crate::server::ToolContext;
+use platform_rbac::App;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Default `k` of reciprocal-rank fusion.
+pub const DEFAULT_RRF_K: f64 = 60.0;
+
+/// Default number of results.
+pub const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Apps searched by default.
+pub const SEARCH_APPS: [App; 3] = [App::Verity, App::NoteMan, App::ShipCheck];
+
+/// Date range for filtering.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    /// Start date (ISO 8601).
+    pub start: String,
+
+    /// End date (ISO 8601).
+    pub end: String,
+}
+
+/// Filter applied to the search of every app.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFilter {
+    /// Only include items in this date range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_range: Option<DateRange>,
+
+    /// Only include items of this project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+
+    /// Only include items with all of these tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl SearchFilter {
+    /// Create an empty filter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only include items from `start` to `end`.
+    pub fn with_date_range(mut self, start: impl Into<String>, end: impl Into<String>) -> Self {
+        self.date_range = Some(DateRange {
+            start: start.into(),
+            end: end.into(),
+        });
+        self
+    }
+
+    /// Only include items of a project.
+    pub fn with_project(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+
+    /// Only include items with all of `tags`.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+/// A federated search query.
+#[derive(Debug, Clone)]
+pub struct SearchRequest {
+    /// Search query.
+    pub query: String,
+
+    /// Filter applied in every app.
+    pub filter: SearchFilter,
+
+    /// Apps to search.
+    pub apps: Vec<App>,
+
+    /// Maximum number of results, and of results requested from each app.
+    pub limit: usize,
+}
+
+impl SearchRequest {
+    /// Search all apps for `query`.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            filter: SearchFilter::default(),
+            apps: SEARCH_APPS.to_vec(),
+            limit: DEFAULT_SEARCH_LIMIT,
+        }
+    }
+
+    /// Set the filter.
+    pub fn with_filter(mut self, filter: SearchFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Only search `apps`.
+    pub fn with_apps(mut self, apps: Vec<App>) -> Self {
+        self.apps = apps;
+        self
+    }
+
+    /// Set the maximum number of results.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+/// A search result from any app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    /// App the item is in.
+    pub app: App,
+
+    /// Kind of item: `knowledge`, `meeting` or `finding`.
+    pub kind: String,
+
+    /// Item ID within the app.
+    pub id: String,
+
+    /// Title or text of the item.
+    pub title: String,
+
+    /// Matching excerpt or location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+
+    /// Date of the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+
+    /// Fused score across apps.
+    pub score: f64,
+
+    /// Score given by the app.
+    pub app_score: f64,
+
+    /// Key of the item this one was taken from, e.g. `noteman:mtg-1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Keys of the hits folded into this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub linked: Vec<String>,
+
+    /// App-specific details, e.g. a finding's severity.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+}
+
+impl SearchHit {
+    /// Create a hit with an app score.
+    pub fn new(
+        app: App,
+        kind: impl Into<String>,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        app_score: f64,
+    ) -> Self {
+        Self {
+            app,
+            kind: kind.into(),
+            id: id.into(),
+            title: title.into(),
+            snippet: None,
+            date: None,
+            score: 0.0,
+            app_score,
+            source: None,
+            linked: Vec::new(),
+            details: serde_json::Value::Null,
+        }
+    }
+
+    /// Key identifying the item across apps, e.g. `noteman:mtg-1`.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.app.as_str(), self.id)
+    }
+
+    fn from_knowledge(result: verity::KnowledgeResult) -> Self {
+        let source = match (result.source_app, result.external_id) {
+            (Some(app), Some(id)) => App::parse(&app).map(|app| format!("{}:{}", app.as_str(), id)),
+            _ => None,
+        };
+        Self {
+            snippet: result.source,
+            date: result.last_verified,
+            source,
+            details: serde_json::json!({"confidence": result.confidence}),
+            ..Self::new(
+                App::Verity,
+                "knowledge",
+                result.id,
+                result.fact,
+                result.relevance,
+            )
+        }
+    }
+
+    fn from_meeting(result: noteman::MeetingSearchResult) -> Self {
+        Self {
+            snippet: result.excerpt,
+            date: Some(result.date),
+            details: serde_json::json!({"participants": result.participants}),
+            ..Self::new(
+                App::NoteMan,
+                "meeting",
+                result.id,
+                result.title,
+                result.score,
+            )
+        }
+    }
+
+    fn from_finding(result: shipcheck::FindingSearchResult) -> Self {
+        let location = match result.line {
+            Some(line) => format!("{}:{}", result.file_path, line),
+            None => result.file_path,
+        };
+        Self {
+            snippet: Some(location),
+            details: serde_json::json!({
+                "severity": result.severity,
+                "repository_id": result.repository_id
+            }),
+            ..Self::new(
+                App::ShipCheck,
+                "finding",
+                result.id,
+                result.title,
+                result.score,
+            )
+        }
+    }
+}
+
+/// Outcome of the search in one app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SourceStatus {
+    /// The app answered with `count` hits.
+    Ok {
+        /// Number of hits from the app.
+        count: usize,
+    },
+
+    /// The app failed.
+    Failed {
+        /// Error message.
+        error: String,
+    },
+
+    /// The app was not searched.
+    Skipped {
+        /// Why the app was not searched.
+        reason: String,
+    },
+}
+
+/// Results of a federated search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResponse {
+    /// Fused results, best first.
+    pub results: Vec<SearchHit>,
+
+    /// Outcome in each app, by app name.
+    pub sources: BTreeMap<String, SourceStatus>,
+}
+
+impl SearchResponse {
+    /// Check whether every app that was searched failed.
+    pub fn all_failed(&self) -> bool {
+        let mut searched = self
+            .sources
+            .values()
+            .filter(|status| !matches!(status, SourceStatus::Skipped { .. }))
+            .peekable();
+        searched.peek().is_some()
+            && searched.all(|status| matches!(status, SourceStatus::Failed { .. }))
+    }
+}
+
+/// Searches all apps with one query.
+pub struct FederatedSearch {
+    services: Arc<ServiceRegistry>,
+    rrf_k: f64,
+}
+
+impl FederatedSearch {
+    /// Create a search using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self {
+            services,
+            rrf_k: DEFAULT_RRF_K,
+        }
+    }
+
+    /// Set the `k` of reciprocal-rank fusion.
+    pub fn with_rrf_k(mut self, k: f64) -> Self {
+        self.rrf_k = k;
+        self
+    }
+
+    /// Search the requested apps in parallel and fuse their results.
+    pub async fn search(&self, request: &SearchRequest, context: &ToolContext) -> SearchResponse {
+        let searches = request
+            .apps
+            .iter()
+            .filter(|app| SEARCH_APPS.contains(app))
+            .map(|&app| async move { (app, self.search_app(app, request, context).await) });
+
+        let mut sources = BTreeMap::new();
+        let mut lists = Vec::new();
+        for (app, result) in futures::future::join_all(searches).await {
+            let status = match result {
+                Ok(hits) => {
+                    debug!(app = app.as_str(), hits = hits.len(), "Search answered");
+                    let count = hits.len();
+                    lists.push(hits);
+                    SourceStatus::Ok { count }
+                }
+                Err(error) => {
+                    warn!(app = app.as_str(), error = %error, "Search failed");
+                    SourceStatus::Failed { error }
+                }
+            };
+            sources.insert(app.as_str().to_string(), status);
+        }
+
+        SearchResponse {
+            results: fuse(lists, self.rrf_k, request.limit),
+            sources,
+        }
+    }
+
+    async fn search_app(
+        &self,
+        app: App,
+        request: &SearchRequest,
+        context: &ToolContext,
+    ) -> Result<Vec<SearchHit>, String> {
+        let filter = &request.filter;
+        let limit = request.limit as u32;
+        match app {
+            App::Verity => {
+                let params = verity::SearchKnowledgeParams {
+                    query: request.query.clone(),
+                    limit,
+                    filters: Some(verity::SearchFilters {
+                        source_types: None,
+                        min_confidence: None,
+                        date_range: filter.date_range.clone().map(|range| verity::DateRange {
+                            start: range.start,
+                            end: range.end,
+                        }),
+                        project_id: filter.project_id.clone(),
+                        tags: filter.tags.clone(),
+                    }),
+                };
+                let response = self.services.verity(context).search_knowledge(params).await;
+                response
+                    .map(|r| {
+                        r.results
+                            .into_iter()
+                            .map(SearchHit::from_knowledge)
+                            .collect()
+                    })
+                    .map_err(|e| e.to_string())
+            }
+            App::NoteMan => {
+                let params = noteman::SearchMeetingsParams {
+                    query: request.query.clone(),
+                    date_range: filter.date_range.clone().map(|range| noteman::DateRange {
+                        start: range.start,
+                        end: range.end,
+                    }),
+                    participants: Vec::new(),
+                    project_id: filter.project_id.clone(),
+                    tags: filter.tags.clone(),
+                    limit,
+                };
+                let response = self.services.noteman(context).search_meetings(params).await;
+                response
+                    .map(|r| {
+                        r.meetings
+                            .into_iter()
+                            .map(SearchHit::from_meeting)
+                            .collect()
+                    })
+                    .map_err(|e| e.to_string())
+            }
+            App::ShipCheck => {
+                let params = shipcheck::SearchFindingsParams {
+                    query: request.query.clone(),
+                    repository_id: None,
+                    severity: Vec::new(),
+                    status: None,
+                    date_range: filter.date_range.clone().map(|range| shipcheck::DateRange {
+                        start: range.start,
+                        end: range.end,
+                    }),
+                    project_id: filter.project_id.clone(),
+                    tags: filter.tags.clone(),
+                    limit,
+                };
+                let response = self
+                    .services
+                    .shipcheck(context)
+                    .search_findings(params)
+                    .await;
+                response
+                    .map(|r| {
+                        r.findings
+                            .into_iter()
+                            .map(SearchHit::from_finding)
+                            .collect()
+                    })
+                    .map_err(|e| e.to_string())
+            }
+            App::Shared => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Fuse ranked lists with reciprocal-rank fusion.
+///
+/// Each list is ranked by app score. A hit whose `source` is another hit is
+/// folded into it. Returns at most `limit` hits, best first.
+pub fn fuse(lists: Vec<Vec<SearchHit>>, k: f64, limit: usize) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for mut list in lists {
+        list.sort_by(|a, b| b.app_score.total_cmp(&a.app_score));
+        for (rank, mut hit) in list.into_iter().enumerate() {
+            let key = hit.key();
+            if index.contains_key(&key) {
+                continue;
+            }
+            hit.score = 1.0 / (k + rank as f64 + 1.0);
+            index.insert(key, hits.len());
+            hits.push(hit);
+        }
+    }
+
+    // Fold hits into the items they were taken from.
+    let mut folded = vec![false; hits.len()];
+    for i in 0..hits.len() {
+        let Some(&target) = hits[i].source.as_ref().and_then(|source| index.get(source)) else {
+            continue;
+        };
+        if target == i || folded[target] {
+            continue;
+        }
+        let (key, score) = (hits[i].key(), hits[i].score);
+        hits[target].score += score;
+        hits[target].linked.push(key);
+        folded[i] = true;
+    }
+
+    let mut hits: Vec<_> = hits
+        .into_iter()
+        .zip(folded)
+        .filter_map(|(hit, folded)| (!folded).then_some(hit))
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.app_score.total_cmp(&a.app_score))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(app: App, id: &str, app_score: f64) -> SearchHit {
+        SearchHit::new(app, "test", id, id, app_score)
+    }
+
+    #[test]
+    fn test_fuse_ranks_by_reciprocal_rank() {
+        let verity = vec![hit(App::Verity, "k1", 0.2), hit(App::Verity, "k2", 0.1)];
+        // NoteMan scores on a larger scale; only the rank matters.
+        let noteman = vec![
+            hit(App::NoteMan, "m2", 40.0),
+            hit(App::NoteMan, "m1", 90.0),
+            hit(App::NoteMan, "m3", 10.0),
+        ];
+
+        let results = fuse(vec![verity, noteman], DEFAULT_RRF_K, 4);
+        let keys: Vec<_> = results.iter().map(SearchHit::key).collect();
+        assert_eq!(
+            keys,
+            vec!["noteman:m1", "verity:k1", "noteman:m2", "verity:k2"]
+        );
+        assert_eq!(results[0].score, results[1].score);
+        assert!((results[0].score - 1.0 / 61.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fuse_folds_linked_items() {
+        let mut fact = hit(App::Verity, "k1", 0.9);
+        fact.source = Some("noteman:m2".to_string());
+        let mut orphan = hit(App::Verity, "k2", 0.5);
+        orphan.source = Some("noteman:gone".to_string());
+        let noteman = vec![hit(App::NoteMan, "m1", 2.0), hit(App::NoteMan, "m2", 1.0)];
+
+        let results = fuse(vec![vec![fact, orphan], noteman], DEFAULT_RRF_K, 10);
+        let keys: Vec<_> = results.iter().map(SearchHit::key).collect();
+        // m2 was second in NoteMan but gains the score of the fact taken from it.
+        assert_eq!(keys, vec!["noteman:m2", "noteman:m1", "verity:k2"]);
+        assert_eq!(results[0].linked, vec!["verity:k1".to_string()]);
+        assert!((results[0].score - (1.0 / 62.0 + 1.0 / 61.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_all_failed_ignores_skipped_apps() {
+        let mut response = SearchResponse {
+            results: Vec::new(),
+            sources: BTreeMap::new(),
+        };
+        assert!(!response.all_failed());
+
+        response.sources.insert(
+            "verity".to_string(),
+            SourceStatus::Failed {
+                error: "down".to_string(),
+            },
+        );
+        response.sources.insert(
+            "noteman".to_string(),
+            SourceStatus::Skipped {
+                reason: "permission".to_string(),
+            },
+        );
+        assert!(response.all_failed());
+
+        response
+            .sources
+            .insert("shipcheck".to_string(), SourceStatus::Ok { count: 0 });
+        assert!(!response.all_failed());
+    }
+}
@@ -3,14 +3,17 @@
 //! This module provides the unified MCP server that aggregates tools
 //! from all Relay platform applications.
 
+use crate::health::MetricsCollector;
+use crate::metrics::ToolOutcome;
+use crate::trace::TraceContext;
 use crate::types::*;
 use async_trait::async_trait;
 use platform_rbac::App;
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tracing::Instrument;
 
 /// MCP server error types.
 #[derive(Debug, Error)]
@@ -59,6 +62,9 @@ pub struct ToolContext {
     /// User ID
     pub user_id: Option<uuid::Uuid>,
 
+    /// User email, forwarded to services with the user's identity
+    pub user_email: Option<String>,
+
     /// Organization ID
     pub org_id: Option<uuid::Uuid>,
 
@@ -68,9 +74,15 @@ pub struct ToolContext {
     /// User permissions
     pub permissions: Vec<String>,
 
+    /// ID of the tool call request, used to derive idempotency keys
+    pub request_id: Option<String>,
+
     /// Request correlation ID
     pub correlation_id: Option<String>,
 
+    /// Distributed trace context of the call
+    pub trace: Option<TraceContext>,
+
     /// API key for external service calls
     pub api_key: Option<String>,
 }
@@ -80,10 +92,13 @@ impl ToolContext {
     pub fn empty() -> Self {
         Self {
             user_id: None,
+            user_email: None,
             org_id: None,
             project_id: None,
             permissions: Vec::new(),
+            request_id: None,
             correlation_id: None,
+            trace: None,
             api_key: None,
         }
     }
@@ -113,6 +128,9 @@ pub struct McpServer {
 
     /// Tool categories
     categories: Arc<RwLock<Vec<String>>>,
+
+    /// Collector for tool-call metrics
+    metrics: Option<Arc<MetricsCollector>>,
 }
 
 impl McpServer {
@@ -135,9 +153,21 @@ impl McpServer {
             tools: Arc::new(RwLock::new(HashMap::new())),
             resources: Arc::new(RwLock::new(HashMap::new())),
             categories: Arc::new(RwLock::new(Vec::new())),
+            metrics: None,
         }
     }
 
+    /// Record tool-call counts by tool and outcome in `metrics`.
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Get the metrics collector, if one is attached.
+    pub fn metrics(&self) -> Option<&Arc<MetricsCollector>> {
+        self.metrics.as_ref()
+    }
+
     /// Create with default platform configuration.
     pub fn platform() -> Self {
         Self::new("relay-platform-mcp", env!("CARGO_PKG_VERSION"))
@@ -227,6 +257,7 @@ impl McpServer {
         let definition = tool.definition();
         for required in &definition.required_permissions {
             if !context.has_permission(required) {
+                self.record_tool_call(name, ToolOutcome::Denied);
                 return Err(McpServerError::PermissionDenied(format!(
                     "Missing permission: {}",
                     required
@@ -234,15 +265,51 @@ impl McpServer {
             }
         }
 
-        tool.execute(arguments, context).await
+        let context = context.traced();
+        let span = tracing::info_span!(
+            "tool_call",
+            tool = %name,
+            correlation_id = context.correlation_id.as_deref().unwrap_or_default(),
+            trace_id = context.trace.as_ref().map(|t| t.trace_id.as_str()).unwrap_or_default(),
+        );
+        let result = tool.execute(arguments, &context).instrument(span).await;
+
+        let outcome = match result {
+            Ok(ref result) if result.is_error => ToolOutcome::Error,
+            Ok(_) => ToolOutcome::Success,
+            Err(_) => ToolOutcome::Failed,
+        };
+        self.record_tool_call(name, outcome);
+        result
+    }
+
+    fn record_tool_call(&self, name: &str, outcome: ToolOutcome) {
+        if let Some(ref metrics) = self.metrics {
+            metrics.record_tool_call(name, outcome);
+        }
     }
 
     /// Handle an MCP request.
     pub async fn handle_request(&self, request: McpRequest) -> McpResponse {
+        self.handle_request_with_context(request, &ToolContext::empty())
+            .await
+    }
+
+    /// Handle an MCP request from the authenticated caller in `context`.
+    ///
+    /// Tool calls run with the caller's identity and permissions.
+    pub async fn handle_request_with_context(
+        &self,
+        request: McpRequest,
+        context: &ToolContext,
+    ) -> McpResponse {
         match request.method.as_str() {
             "initialize" => self.handle_initialize(request.id),
             "tools/list" => self.handle_tools_list(request.id).await,
-            "tools/call" => self.handle_tools_call(request.id, request.params).await,
+            "tools/call" => {
+                self.handle_tools_call(request.id, request.params, context)
+                    .await
+            }
             "resources/list" => self.handle_resources_list(request.id).await,
             "resources/read" => self.handle_resources_read(request.id, request.params).await,
             _ => McpResponse::error(request.id, McpError::method_not_found(&request.method)),
@@ -269,20 +336,23 @@ impl McpServer {
         &self,
         id: RequestId,
         params: Option<serde_json::Value>,
+        context: &ToolContext,
     ) -> McpResponse {
         let params = match params {
             Some(p) => p,
             None => return McpResponse::error(id, McpError::invalid_params("Missing params")),
         };
 
+        // A retry of the call must reuse its idempotency keys, so the request
+        // ID comes from the call rather than being generated here.
+        let mut context = context.clone();
+        context.request_id = call_request_id(&id, &params);
+
         let call: ToolCall = match serde_json::from_value(params) {
             Ok(c) => c,
             Err(e) => return McpResponse::error(id, McpError::invalid_params(e.to_string())),
         };
 
-        // Create empty context (in real use, this would come from auth)
-        let context = ToolContext::empty();
-
         match self.call_tool(&call.name, call.arguments, &context).await {
             Ok(result) => McpResponse::success(id, serde_json::to_value(result).unwrap()),
             Err(e) => McpResponse::error(id, McpError::internal_error(e.to_string())),
@@ -319,6 +389,32 @@ impl McpServer {
     }
 }
 
+/// Get the stable identity of a tool call for its idempotency keys.
+///
+/// Uses the caller's idempotency key or request ID from the call's `_meta`
+/// (`idempotencyKey` or `requestId`) or params (`idempotencyKey`), falling
+/// back to the JSON-RPC ID. JSON-RPC IDs are only unique per session, but the
+/// keys also cover the calling user and organization.
+fn call_request_id(id: &RequestId, params: &serde_json::Value) -> Option<String> {
+    let meta = &params["_meta"];
+    let supplied = [
+        &meta["idempotencyKey"],
+        &meta["requestId"],
+        &params["idempotencyKey"],
+    ]
+    .into_iter()
+    .find_map(|value| match value {
+        serde_json::Value::String(s) if !s.is_empty() => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    });
+    supplied.or(match id {
+        RequestId::String(s) => Some(s.clone()),
+        RequestId::Number(n) => Some(n.to_string()),
+        RequestId::Null => None,
+    })
+}
+
 /// Simple tool wrapper for function-based tools.
 pub struct FunctionTool<F>
 where
@@ -425,6 +521,69 @@ mod tests {
         assert_eq!(noteman_tools.len(), 0);
     }
 
+    /// Tool recording the request ID of each call.
+    #[derive(Default)]
+    struct RequestIdTool {
+        request_ids: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait]
+    impl Tool for RequestIdTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new("request_id_tool", "Records request IDs")
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+            context: &ToolContext,
+        ) -> McpServerResult<ToolResult> {
+            self.request_ids
+                .lock()
+                .unwrap()
+                .push(context.request_id.clone());
+            Ok(ToolResult::text("ok"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_request_ids_are_stable() {
+        let server = McpServer::platform();
+        let tool = Arc::new(RequestIdTool::default());
+        server.register_tool(tool.clone()).await;
+
+        let call = |id: i64, params: serde_json::Value| {
+            McpRequest::new(id, "tools/call").with_params(params)
+        };
+        let plain = serde_json::json!({"name": "request_id_tool", "arguments": {}});
+        let keyed = serde_json::json!({
+            "name": "request_id_tool",
+            "arguments": {},
+            "_meta": {"idempotencyKey": "retry-me"}
+        });
+        // A retry with the same JSON-RPC ID, then retries under new IDs that
+        // carry the caller's key.
+        for request in [
+            call(1, plain.clone()),
+            call(1, plain),
+            call(2, keyed.clone()),
+            call(3, keyed),
+        ] {
+            assert!(server.handle_request(request).await.error.is_none());
+        }
+
+        let request_ids = tool.request_ids.lock().unwrap();
+        assert_eq!(
+            *request_ids,
+            vec![
+                Some("1".to_string()),
+                Some("1".to_string()),
+                Some("retry-me".to_string()),
+                Some("retry-me".to_string())
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_request() {
         let server = McpServer::platform();
@@ -0,0 +1,481 @@
+//! Batch workflow MCP tools
+//!
+//! Batch variants of the workflow tools that take many items at once, either
+//! as a list of IDs or as a query resolved to IDs:
+//! - `workflow_verify_meeting_notes_batch`: meetings by ID, or all meetings
+//!   matching a NoteMan search, e.g. every meeting in a date range
+//! - `workflow_verify_documentation_batch`: repositories by ID, or all
+//!   repositories in a ShipCheck project
+//!
+//! Each item runs the single-item workflow tool, at most `max_concurrency`
+//! at a time. One failed item does not fail the batch: the tool returns a
+//! report with the status and output or error of every item, and the number
+//! of search matches left out by the search limit (`truncated`).
+
+use crate::clients::noteman::{DateRange, SearchMeetingsParams};
+use crate::clients::registry::ServiceRegistry;
+use crate::server::{McpServerError, McpServerResult, Tool, ToolContext};
+use crate::tools::workflow::{VerifyDocumentationTool, VerifyMeetingNotesTool};
+use crate::types::{ToolDefinition, ToolResult};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+/// Default number of items run at the same time.
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+/// Maximum number of items run at the same time.
+pub const MAX_BATCH_CONCURRENCY: usize = 16;
+
+/// Maximum number of items in one batch.
+pub const MAX_BATCH_ITEMS: usize = 100;
+
+/// Default number of meetings taken from a search.
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Tool to verify the notes of many meetings with Verity.
+///
+/// Runs `workflow_verify_meeting_notes` for each meeting in `meeting_ids`,
+/// or for each meeting found by searching NoteMan with `query`, `date_range`
+/// and `participants`.
+pub struct VerifyMeetingNotesBatchTool {
+    services: Arc<ServiceRegistry>,
+    tool: VerifyMeetingNotesTool,
+}
+
+impl VerifyMeetingNotesBatchTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self {
+            tool: VerifyMeetingNotesTool::new(services.clone()),
+            services,
+        }
+    }
+
+    /// Get the IDs of the meetings to verify, and the number of matching
+    /// meetings left out by the search limit.
+    async fn meeting_ids(
+        &self,
+        params: MeetingBatchParams,
+        context: &ToolContext,
+    ) -> McpServerResult<Result<(Vec<String>, usize), String>> {
+        if !params.meeting_ids.is_empty() {
+            return Ok(Ok((params.meeting_ids, 0)));
+        }
+        if params.query.is_none() && params.date_range.is_none() {
+            return Err(McpServerError::InvalidParams(
+                "Provide meeting_ids, or a query or date_range to search meetings".to_string(),
+            ));
+        }
+
+        let limit = params.limit.min(MAX_BATCH_ITEMS);
+        let search = SearchMeetingsParams {
+            query: params.query.unwrap_or_default(),
+            date_range: params.date_range,
+            participants: params.participants,
+            project_id: None,
+            tags: Vec::new(),
+            limit: limit as u32,
+        };
+        Ok(self
+            .services
+            .noteman(context)
+            .search_meetings(search)
+            .await
+            .map(|response| {
+                let ids: Vec<String> = response
+                    .meetings
+                    .into_iter()
+                    .take(limit)
+                    .map(|m| m.id)
+                    .collect();
+                let truncated = (response.total_results as usize).saturating_sub(ids.len());
+                if truncated > 0 {
+                    info!(
+                        limit,
+                        truncated, "Meeting search matched more meetings than the limit"
+                    );
+                }
+                (ids, truncated)
+            })
+            .map_err(|e| format!("Failed to search meetings: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Tool for VerifyMeetingNotesBatchTool {
+    fn definition(&self) -> ToolDefinition {
+        batch_definition(
+            self.tool.definition(),
+            "workflow_verify_meeting_notes_batch",
+            "Verify factual claims in the notes of many meetings using Verity",
+            "meeting_id",
+            json!({
+                "meeting_ids": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "NoteMan meeting IDs; if empty, meetings are found by searching"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Search query for the meetings to verify"
+                },
+                "date_range": {
+                    "type": "object",
+                    "properties": {
+                        "start": {"type": "string", "format": "date"},
+                        "end": {"type": "string", "format": "date"}
+                    },
+                    "required": ["start", "end"],
+                    "description": "Only verify meetings in this date range"
+                },
+                "participants": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only verify meetings with these participants"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of meetings taken from the search; the report counts the rest as truncated",
+                    "default": DEFAULT_SEARCH_LIMIT
+                }
+            }),
+        )
+    }
+
+    #[instrument(
+        skip(self, args, context),
+        fields(tool = "workflow_verify_meeting_notes_batch")
+    )]
+    async fn execute(&self, args: Value, context: &ToolContext) -> McpServerResult<ToolResult> {
+        let params: MeetingBatchParams = serde_json::from_value(args.clone())
+            .map_err(|e| McpServerError::InvalidParams(e.to_string()))?;
+        let concurrency = params.max_concurrency;
+
+        match self.meeting_ids(params, context).await? {
+            Ok((ids, truncated)) => {
+                run_batch(
+                    &self.tool,
+                    "meeting_id",
+                    ids,
+                    truncated,
+                    &args,
+                    concurrency,
+                    context,
+                )
+                .await
+            }
+            Err(message) => {
+                error!("{}", message);
+                Ok(ToolResult::error(message))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MeetingBatchParams {
+    #[serde(default)]
+    meeting_ids: Vec<String>,
+    query: Option<String>,
+    date_range: Option<DateRange>,
+    #[serde(default)]
+    participants: Vec<String>,
+    #[serde(default = "default_search_limit")]
+    limit: usize,
+    #[serde(default = "default_concurrency")]
+    max_concurrency: usize,
+}
+
+fn default_search_limit() -> usize {
+    DEFAULT_SEARCH_LIMIT
+}
+
+/// Tool to verify the documentation of many repositories with Verity.
+///
+/// Runs `workflow_verify_documentation` for each repository in
+/// `repository_ids`, or for each repository in the ShipCheck project
+/// `project_id`.
+pub struct VerifyDocumentationBatchTool {
+    services: Arc<ServiceRegistry>,
+    tool: VerifyDocumentationTool,
+}
+
+impl VerifyDocumentationBatchTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self {
+            tool: VerifyDocumentationTool::new(services.clone()),
+            services,
+        }
+    }
+
+    /// Get the IDs of the repositories to verify.
+    async fn repository_ids(
+        &self,
+        params: DocumentationBatchParams,
+        context: &ToolContext,
+    ) -> McpServerResult<Result<Vec<String>, String>> {
+        if !params.repository_ids.is_empty() {
+            return Ok(Ok(params.repository_ids));
+        }
+        let Some(project_id) = params.project_id else {
+            return Err(McpServerError::InvalidParams(
+                "Provide repository_ids or a project_id".to_string(),
+            ));
+        };
+
+        Ok(self
+            .services
+            .shipcheck(context)
+            .list_repositories(&project_id)
+            .await
+            .map(|project| project.repositories.into_iter().map(|r| r.id).collect())
+            .map_err(|e| format!("Failed to list repositories: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Tool for VerifyDocumentationBatchTool {
+    fn definition(&self) -> ToolDefinition {
+        batch_definition(
+            self.tool.definition(),
+            "workflow_verify_documentation_batch",
+            "Verify the documentation of many repositories using Verity",
+            "repository_id",
+            json!({
+                "repository_ids": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "ShipCheck repository IDs; if empty, all repositories in project_id"
+                },
+                "project_id": {
+                    "type": "string",
+                    "description": "ShipCheck project whose repositories to verify"
+                }
+            }),
+        )
+    }
+
+    #[instrument(
+        skip(self, args, context),
+        fields(tool = "workflow_verify_documentation_batch")
+    )]
+    async fn execute(&self, args: Value, context: &ToolContext) -> McpServerResult<ToolResult> {
+        let params: DocumentationBatchParams = serde_json::from_value(args.clone())
+            .map_err(|e| McpServerError::InvalidParams(e.to_string()))?;
+        let concurrency = params.max_concurrency;
+
+        match self.repository_ids(params, context).await? {
+            Ok(ids) => {
+                run_batch(
+                    &self.tool,
+                    "repository_id",
+                    ids,
+                    0,
+                    &args,
+                    concurrency,
+                    context,
+                )
+                .await
+            }
+            Err(message) => {
+                error!("{}", message);
+                Ok(ToolResult::error(message))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DocumentationBatchParams {
+    #[serde(default)]
+    repository_ids: Vec<String>,
+    project_id: Option<String>,
+    #[serde(default = "default_concurrency")]
+    max_concurrency: usize,
+}
+
+fn default_concurrency() -> usize {
+    DEFAULT_BATCH_CONCURRENCY
+}
+
+/// Build the definition of a batch tool from the single-item tool's.
+///
+/// The batch tool takes the single-item tool's arguments, applied to every
+/// item, except `item_arg`, which is replaced by `properties`.
+fn batch_definition(
+    single: ToolDefinition,
+    name: &str,
+    description: &str,
+    item_arg: &str,
+    properties: Value,
+) -> ToolDefinition {
+    let mut shared = single
+        .input_schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    shared.remove(item_arg);
+    if let Value::Object(properties) = properties {
+        shared.extend(properties);
+    }
+    shared.insert(
+        "max_concurrency".to_string(),
+        json!({
+            "type": "integer",
+            "description": format!(
+                "Maximum number of items verified at the same time (at most {})",
+                MAX_BATCH_CONCURRENCY
+            ),
+            "default": DEFAULT_BATCH_CONCURRENCY
+        }),
+    );
+
+    let mut definition = ToolDefinition::new(name, description).with_schema(json!({
+        "type": "object",
+        "properties": shared,
+        "required": []
+    }));
+    definition.source_app = single.source_app;
+    definition.category = single.category;
+    definition.required_permissions = single.required_permissions;
+    definition
+}
+
+/// Run `tool` once per item and report the outcome of each.
+///
+/// Every item is called with the arguments of `args` that `tool` takes, and
+/// `item_arg` set to the item. Duplicate items run once. `truncated` is the
+/// number of items left out before the batch, e.g. by a search limit.
+async fn run_batch(
+    tool: &dyn Tool,
+    item_arg: &str,
+    mut items: Vec<String>,
+    truncated: usize,
+    args: &Value,
+    concurrency: usize,
+    context: &ToolContext,
+) -> McpServerResult<ToolResult> {
+    let mut seen = std::collections::HashSet::new();
+    items.retain(|item| seen.insert(item.clone()));
+    if items.len() > MAX_BATCH_ITEMS {
+        return Err(McpServerError::InvalidParams(format!(
+            "A batch can have at most {} items, got {}",
+            MAX_BATCH_ITEMS,
+            items.len()
+        )));
+    }
+
+    let properties = tool.definition().input_schema["properties"].clone();
+    let shared: Map<String, Value> = args
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| *key != item_arg && properties.get(key.as_str()).is_some())
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let concurrency = concurrency.clamp(1, MAX_BATCH_CONCURRENCY);
+
+    info!(items = items.len(), concurrency, "Running batch");
+    let reports: Vec<Value> = stream::iter(items)
+        .map(|item| {
+            let mut args = shared.clone();
+            args.insert(item_arg.to_string(), Value::String(item.clone()));
+            async move {
+                let result = tool.execute(Value::Object(args), context).await;
+                match result
+                    .map_err(|e| e.to_string())
+                    .and_then(ToolResult::into_output)
+                {
+                    Ok(output) => json!({"id": item, "status": "succeeded", "output": output}),
+                    Err(error) => json!({"id": item, "status": "failed", "error": error}),
+                }
+            }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let succeeded = reports
+        .iter()
+        .filter(|report| report["status"] == "succeeded")
+        .count();
+    let failed = reports.len() - succeeded;
+    let status = match (succeeded, failed) {
+        (_, 0) => "completed",
+        (0, _) => "failed",
+        _ => "partially_failed",
+    };
+
+    Ok(ToolResult::json(json!({
+        "status": status,
+        "total": reports.len(),
+        "succeeded": succeeded,
+        "failed": failed,
+        "truncated": truncated,
+        "items": reports
+    })))
+}
+
+/// Get all batch workflow tools.
+pub fn batch_workflow_tools(services: &Arc<ServiceRegistry>) -> Vec<Arc<dyn Tool>> {
+    vec![
+        Arc::new(VerifyMeetingNotesBatchTool::new(services.clone())),
+        Arc::new(VerifyDocumentationBatchTool::new(services.clone())),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn services() -> Arc<ServiceRegistry> {
+        Arc::new(ServiceRegistry::default())
+    }
+
+    #[test]
+    fn test_batch_definitions_extend_single_item_tools() {
+        let services = services();
+        let tool = VerifyMeetingNotesBatchTool::new(services.clone());
+        let def = tool.definition();
+        assert_eq!(def.name, "workflow_verify_meeting_notes_batch");
+        assert_eq!(
+            def.required_permissions,
+            VerifyMeetingNotesTool::new(services.clone())
+                .definition()
+                .required_permissions
+        );
+        let properties = &def.input_schema["properties"];
+        assert!(properties.get("meeting_id").is_none());
+        assert!(properties.get("meeting_ids").is_some());
+        assert_eq!(properties["verification_level"]["default"], "standard");
+        assert_eq!(
+            properties["max_concurrency"]["default"],
+            DEFAULT_BATCH_CONCURRENCY
+        );
+
+        let def = VerifyDocumentationBatchTool::new(services).definition();
+        assert!(def.input_schema["properties"].get("project_id").is_some());
+        assert!(def.input_schema["properties"].get("paths").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_batch_needs_items_or_query() {
+        let tool = VerifyDocumentationBatchTool::new(services());
+        let result = tool.execute(json!({}), &ToolContext::empty()).await;
+        assert!(matches!(result, Err(McpServerError::InvalidParams(_))));
+
+        let ids: Vec<String> = (0..=MAX_BATCH_ITEMS)
+            .map(|i| format!("repo-{}", i))
+            .collect();
+        let result = tool
+            .execute(json!({"repository_ids": ids}), &ToolContext::empty())
+            .await;
+        assert!(matches!(result, Err(McpServerError::InvalidParams(_))));
+    }
+}
@@ -3,16 +3,21 @@
 //! This module provides pre-built tools for cross-app workflows.
 //! Each tool category handles integration between specific apps.
 
+pub mod batch;
 pub mod noteman;
+pub mod platform;
 pub mod shipcheck;
 pub mod verity;
 pub mod workflow;
 
+pub use batch::*;
 pub use noteman::*;
+pub use platform::*;
 pub use shipcheck::*;
 pub use verity::*;
 pub use workflow::*;
 
+use crate::clients::registry::ServiceRegistry;
 use crate::server::Tool;
 use std::sync::Arc;
 
@@ -22,30 +27,46 @@ use std::sync::Arc;
 /// - NoteMan: Meeting transcription, summarization, and action items
 /// - ShipCheck: Code analysis, verification, and security scanning
 /// - Verity: Document verification, assertion extraction, and knowledge management
-/// - Workflow: Cross-app orchestration tools
+/// - Workflow: Cross-app orchestration tools, their batch variants and
+///   inspection of their runs
+/// - Platform: Service health and search across all apps
+///
+/// Every tool calls services through the clients in `services`.
 ///
 /// # Example
 ///
 /// ```rust,no_run
+/// use platform_mcp::clients::ServiceRegistry;
 /// use platform_mcp::tools::all_tools;
+/// use std::sync::Arc;
 ///
-/// let tools = all_tools();
+/// let services = Arc::new(ServiceRegistry::from_env());
+/// let tools = all_tools(&services);
 /// println!("Available tools: {}", tools.len());
 /// ```
-pub fn all_tools() -> Vec<Arc<dyn Tool>> {
+pub fn all_tools(services: &Arc<ServiceRegistry>) -> Vec<Arc<dyn Tool>> {
     let mut tools = Vec::new();
 
     // NoteMan tools (4)
-    tools.extend(noteman_tools());
+    tools.extend(noteman_tools(services));
 
     // ShipCheck tools (4)
-    tools.extend(shipcheck_tools());
+    tools.extend(shipcheck_tools(services));
 
     // Verity tools (4)
-    tools.extend(verity_tools());
+    tools.extend(verity_tools(services));
 
     // Workflow tools (5)
-    tools.extend(workflow_tools());
+    tools.extend(workflow_tools(services));
+
+    // Batch workflow tools (2)
+    tools.extend(batch_workflow_tools(services));
+
+    // Workflow run tools (2)
+    tools.extend(workflow_run_tools(services));
+
+    // Platform tools (2)
+    tools.extend(platform_tools(services));
 
     tools
 }
@@ -54,16 +75,21 @@ pub fn all_tools() -> Vec<Arc<dyn Tool>> {
 mod tests {
     use super::*;
 
+    fn services() -> Arc<ServiceRegistry> {
+        Arc::new(ServiceRegistry::default())
+    }
+
     #[test]
     fn test_all_tools_count() {
-        let tools = all_tools();
-        // 4 NoteMan + 4 ShipCheck + 4 Verity + 5 Workflow = 17 tools
-        assert_eq!(tools.len(), 17, "Expected 17 total tools");
+        let tools = all_tools(&services());
+        // 4 NoteMan + 4 ShipCheck + 4 Verity + 5 Workflow + 2 Batch workflow
+        // + 2 Workflow run + 2 Platform = 23 tools
+        assert_eq!(tools.len(), 23, "Expected 23 total tools");
     }
 
     #[test]
     fn test_all_tools_unique_names() {
-        let tools = all_tools();
+        let tools = all_tools(&services());
         let mut names = std::collections::HashSet::new();
 
         for tool in tools {
@@ -78,14 +104,21 @@ mod tests {
 
     #[test]
     fn test_tool_categories() {
-        let noteman = noteman_tools();
-        let shipcheck = shipcheck_tools();
-        let verity = verity_tools();
-        let workflow = workflow_tools();
+        let services = services();
+        let noteman = noteman_tools(&services);
+        let shipcheck = shipcheck_tools(&services);
+        let verity = verity_tools(&services);
+        let workflow = workflow_tools(&services);
+        let batch = batch_workflow_tools(&services);
+        let workflow_runs = workflow_run_tools(&services);
+        let platform = platform_tools(&services);
 
         assert_eq!(noteman.len(), 4, "Expected 4 NoteMan tools");
         assert_eq!(shipcheck.len(), 4, "Expected 4 ShipCheck tools");
         assert_eq!(verity.len(), 4, "Expected 4 Verity tools");
         assert_eq!(workflow.len(), 5, "Expected 5 Workflow tools");
+        assert_eq!(batch.len(), 2, "Expected 2 Batch workflow tools");
+        assert_eq!(workflow_runs.len(), 2, "Expected 2 Workflow run tools");
+        assert_eq!(platform.len(), 2, "Expected 2 Platform tools");
     }
 }
@@ -4,37 +4,34 @@
 //! These tools communicate with the NoteMan service via HTTP to perform
 //! meeting intelligence operations.
 
-use crate::clients::config::ServiceConfig;
 use crate::clients::noteman::{
-    DateRange, ExtractActionItemsParams as ClientExtractParams, NoteManClient,
+    DateRange, ExtractActionItemsParams as ClientExtractParams,
     SearchMeetingsParams as ClientSearchParams, SummarizeMeetingParams as ClientSummarizeParams,
     TranscribeMeetingParams as ClientTranscribeParams,
 };
+use crate::clients::registry::ServiceRegistry;
 use crate::server::{McpServerError, McpServerResult, Tool, ToolContext};
 use crate::types::{ToolDefinition, ToolResult};
 use async_trait::async_trait;
 use platform_rbac::App;
 use serde::Deserialize;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 use tracing::{debug, error, instrument};
 
-/// Lazily initialized NoteMan client singleton.
-static NOTEMAN_CLIENT: OnceLock<NoteManClient> = OnceLock::new();
-
-/// Get or initialize the NoteMan client.
-fn get_client() -> &'static NoteManClient {
-    NOTEMAN_CLIENT.get_or_init(|| {
-        let config = ServiceConfig::from_env();
-        let timeout = config.timeout();
-        NoteManClient::new(config.noteman, timeout)
-    })
-}
-
 /// Tool to transcribe a meeting.
 ///
 /// Generates a transcript from meeting audio/video by calling the NoteMan
 /// transcription service. Supports speaker diarization and multiple languages.
-pub struct TranscribeMeetingTool;
+pub struct TranscribeMeetingTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl TranscribeMeetingTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for TranscribeMeetingTool {
@@ -82,7 +79,7 @@ impl Tool for TranscribeMeetingTool {
 
         debug!("Transcribing meeting: {}", params.meeting_id);
 
-        let client = get_client();
+        let client = self.services.noteman(context);
 
         let client_params = ClientTranscribeParams {
             meeting_id: params.meeting_id.clone(),
@@ -131,7 +128,16 @@ fn default_true() -> bool {
 ///
 /// Generates a summary from meeting transcript or notes by calling the
 /// NoteMan summarization service. Supports multiple output formats.
-pub struct SummarizeMeetingTool;
+pub struct SummarizeMeetingTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl SummarizeMeetingTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for SummarizeMeetingTool {
@@ -185,7 +191,7 @@ impl Tool for SummarizeMeetingTool {
 
         debug!("Summarizing meeting: {}", params.meeting_id);
 
-        let client = get_client();
+        let client = self.services.noteman(context);
 
         let client_params = ClientSummarizeParams {
             meeting_id: params.meeting_id.clone(),
@@ -236,7 +242,16 @@ fn default_format() -> String {
 ///
 /// Identifies and extracts action items with assignees and due dates
 /// by calling the NoteMan extraction service.
-pub struct ExtractActionItemsTool;
+pub struct ExtractActionItemsTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl ExtractActionItemsTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for ExtractActionItemsTool {
@@ -287,7 +302,7 @@ impl Tool for ExtractActionItemsTool {
             params.meeting_id
         );
 
-        let client = get_client();
+        let client = self.services.noteman(context);
 
         let client_params = ClientExtractParams {
             meeting_id: params.meeting_id.clone(),
@@ -326,7 +341,16 @@ struct ExtractActionItemsParams {
 ///
 /// Searches through meeting transcripts and summaries using the
 /// NoteMan search service.
-pub struct SearchMeetingsTool;
+pub struct SearchMeetingsTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl SearchMeetingsTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for SearchMeetingsTool {
@@ -375,7 +399,7 @@ impl Tool for SearchMeetingsTool {
 
         debug!("Searching meetings with query: {}", params.query);
 
-        let client = get_client();
+        let client = self.services.noteman(context);
 
         // Convert date range if provided
         let date_range = params
@@ -392,6 +416,8 @@ impl Tool for SearchMeetingsTool {
             query: params.query.clone(),
             date_range,
             participants: params.participants.clone(),
+            project_id: None,
+            tags: Vec::new(),
             limit: params.limit,
         };
 
@@ -430,12 +456,12 @@ fn default_limit() -> u32 {
 ///
 /// Returns a vector of all NoteMan MCP tools that can be registered
 /// with an MCP server.
-pub fn noteman_tools() -> Vec<Arc<dyn Tool>> {
+pub fn noteman_tools(services: &Arc<ServiceRegistry>) -> Vec<Arc<dyn Tool>> {
     vec![
-        Arc::new(TranscribeMeetingTool),
-        Arc::new(SummarizeMeetingTool),
-        Arc::new(ExtractActionItemsTool),
-        Arc::new(SearchMeetingsTool),
+        Arc::new(TranscribeMeetingTool::new(services.clone())),
+        Arc::new(SummarizeMeetingTool::new(services.clone())),
+        Arc::new(ExtractActionItemsTool::new(services.clone())),
+        Arc::new(SearchMeetingsTool::new(services.clone())),
     ]
 }
 
@@ -445,7 +471,7 @@ mod tests {
 
     #[test]
     fn test_transcribe_meeting_tool_definition() {
-        let tool = TranscribeMeetingTool;
+        let tool = TranscribeMeetingTool::new(Arc::new(ServiceRegistry::default()));
         let def = tool.definition();
         assert_eq!(def.name, "noteman_transcribe_meeting");
         assert_eq!(def.source_app, Some(App::NoteMan));
@@ -453,13 +479,13 @@ mod tests {
 
     #[test]
     fn test_all_noteman_tools() {
-        let tools = noteman_tools();
+        let tools = noteman_tools(&Arc::new(ServiceRegistry::default()));
         assert_eq!(tools.len(), 4);
     }
 
     #[test]
     fn test_tool_categories() {
-        let tools = noteman_tools();
+        let tools = noteman_tools(&Arc::new(ServiceRegistry::default()));
         let categories: Vec<_> = tools
             .iter()
             .map(|t| t.definition().category.clone())
@@ -0,0 +1,323 @@
+//! Platform MCP tools
+//!
+//! Tools that work across the platform rather than in one app, such as the
+//! health of the services the other tools depend on and search over all of
+//! them.
+
+use crate::clients::registry::ServiceRegistry;
+use crate::health::{HealthCheckConfig, HealthChecker};
+use crate::search::{
+    FederatedSearch, SearchFilter, SearchRequest, SourceStatus, DEFAULT_SEARCH_LIMIT, SEARCH_APPS,
+};
+use crate::server::{McpServerError, McpServerResult, Tool, ToolContext};
+use crate::types::{ToolDefinition, ToolResult};
+use crate::workflow::automation::MANAGE_PERMISSION;
+use async_trait::async_trait;
+use platform_rbac::App;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{error, instrument};
+
+/// Tool to check the health of the platform services.
+///
+/// Returns the full [`HealthReport`](crate::health::HealthReport) and, when
+/// the registry has a health monitor, the recent check history of each
+/// service, so the assistant can explain why calls to a service fail.
+///
+/// The services' internal URLs are only shown to organization administrators
+/// ([`MANAGE_PERMISSION`]); for other callers they are redacted, including
+/// from error messages.
+pub struct PlatformHealthTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl PlatformHealthTool {
+    /// Create the tool checking the services configured in `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
+
+#[async_trait]
+impl Tool for PlatformHealthTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "platform_health",
+            "Check the health of NoteMan, ShipCheck and Verity, including latency and recent failures",
+        )
+        .with_app(App::Shared)
+        .with_category("monitoring")
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": {},
+            "required": []
+        }))
+    }
+
+    #[instrument(skip(self, _args, context), fields(tool = "platform_health"))]
+    async fn execute(
+        &self,
+        _args: serde_json::Value,
+        context: &ToolContext,
+    ) -> McpServerResult<ToolResult> {
+        let config = self.services.config().current();
+        let checker = HealthChecker::new((*config).clone(), HealthCheckConfig::default());
+        let mut report = checker.check_all().await;
+
+        let urls: Vec<String> = if context.has_permission(MANAGE_PERMISSION) {
+            Vec::new()
+        } else {
+            report
+                .services
+                .iter()
+                .map(|service| service.url.trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .collect()
+        };
+        let redact = |text: &mut String| {
+            for url in &urls {
+                *text = text.replace(url.as_str(), REDACTED);
+            }
+        };
+        if !urls.is_empty() {
+            for service in &mut report.services {
+                service.url = REDACTED.to_string();
+                if let Some(ref mut error) = service.error {
+                    redact(error);
+                }
+            }
+        }
+
+        let mut output = serde_json::to_value(&report).unwrap_or_default();
+        if let Some(monitor) = self.services.health_monitor() {
+            let history: serde_json::Map<_, _> = report
+                .services
+                .iter()
+                .map(|service| {
+                    let name = service.name.to_lowercase();
+                    let mut samples = monitor.history(&name);
+                    for sample in &mut samples {
+                        if let Some(ref mut error) = sample.error {
+                            redact(error);
+                        }
+                    }
+                    (name, serde_json::to_value(samples).unwrap_or_default())
+                })
+                .collect();
+            output["history"] = history.into();
+        }
+        Ok(ToolResult::json(output))
+    }
+}
+
+/// Replacement for internal URLs shown to callers who may not see them.
+const REDACTED: &str = "[redacted]";
+
+/// Maximum number of results of [`PlatformSearchTool`].
+const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Tool to search Verity, NoteMan and ShipCheck at once.
+///
+/// Runs a [`FederatedSearch`] over the apps the caller may read: knowledge
+/// needs `knowledge:read`, meetings `meeting:read` and findings
+/// `code_finding:read`. Apps the caller may not read are skipped, and an app
+/// that is down is reported without failing the search.
+pub struct PlatformSearchTool {
+    search: FederatedSearch,
+}
+
+impl PlatformSearchTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self {
+            search: FederatedSearch::new(services),
+        }
+    }
+}
+
+/// Permission needed to search an app.
+fn search_permission(app: App) -> &'static str {
+    match app {
+        App::Verity => "knowledge:read",
+        App::NoteMan => "meeting:read",
+        App::ShipCheck => "code_finding:read",
+        App::Shared => "",
+    }
+}
+
+#[async_trait]
+impl Tool for PlatformSearchTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(
+            "platform_search",
+            "Search Verity knowledge, NoteMan meetings and ShipCheck findings at once",
+        )
+        .with_app(App::Shared)
+        .with_category("search")
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Search query"
+                },
+                "date_range": {
+                    "type": "object",
+                    "properties": {
+                        "start": {"type": "string", "format": "date"},
+                        "end": {"type": "string", "format": "date"}
+                    },
+                    "required": ["start", "end"],
+                    "description": "Only include items in this date range"
+                },
+                "project_id": {
+                    "type": "string",
+                    "description": "Only include items of this project"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Only include items with all of these tags"
+                },
+                "apps": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": ["verity", "noteman", "shipcheck"]},
+                    "description": "Apps to search (default: all)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of results",
+                    "default": DEFAULT_SEARCH_LIMIT
+                }
+            },
+            "required": ["query"]
+        }))
+    }
+
+    #[instrument(skip(self, args, context), fields(tool = "platform_search"))]
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        context: &ToolContext,
+    ) -> McpServerResult<ToolResult> {
+        let params: PlatformSearchParams = serde_json::from_value(args)
+            .map_err(|e| McpServerError::InvalidParams(e.to_string()))?;
+
+        let requested = match params.apps {
+            Some(apps) => apps
+                .iter()
+                .map(|name| {
+                    App::parse(name)
+                        .filter(|app| SEARCH_APPS.contains(app))
+                        .ok_or_else(|| {
+                            McpServerError::InvalidParams(format!("Unknown app: {}", name))
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => SEARCH_APPS.to_vec(),
+        };
+        let (allowed, denied): (Vec<_>, Vec<_>) = requested
+            .into_iter()
+            .partition(|app| context.has_permission(search_permission(*app)));
+        if allowed.is_empty() {
+            return Err(McpServerError::PermissionDenied(
+                "Missing permission to search any app".to_string(),
+            ));
+        }
+
+        let request = SearchRequest::new(params.query.clone())
+            .with_filter(params.filter)
+            .with_apps(allowed)
+            .with_limit(params.limit.clamp(1, MAX_SEARCH_LIMIT));
+        let mut response = self.search.search(&request, context).await;
+        for app in denied {
+            response.sources.insert(
+                app.as_str().to_string(),
+                SourceStatus::Skipped {
+                    reason: format!("Missing permission: {}", search_permission(app)),
+                },
+            );
+        }
+
+        if response.all_failed() {
+            error!("Search failed in every app");
+            let errors: Vec<_> = response
+                .sources
+                .iter()
+                .filter_map(|(app, status)| match status {
+                    SourceStatus::Failed { error } => Some(format!("{}: {}", app, error)),
+                    _ => None,
+                })
+                .collect();
+            return Ok(ToolResult::error(format!(
+                "Search failed in every app: {}",
+                errors.join("; ")
+            )));
+        }
+
+        Ok(ToolResult::json(serde_json::json!({
+            "query": params.query,
+            "total_results": response.results.len(),
+            "results": response.results,
+            "sources": response.sources
+        })))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatformSearchParams {
+    query: String,
+    #[serde(flatten)]
+    filter: SearchFilter,
+    apps: Option<Vec<String>>,
+    #[serde(default = "default_search_limit")]
+    limit: usize,
+}
+
+fn default_search_limit() -> usize {
+    DEFAULT_SEARCH_LIMIT
+}
+
+/// Get all platform tools.
+pub fn platform_tools(services: &Arc<ServiceRegistry>) -> Vec<Arc<dyn Tool>> {
+    vec![
+        Arc::new(PlatformHealthTool::new(services.clone())),
+        Arc::new(PlatformSearchTool::new(services.clone())),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_health_tool_definition() {
+        let tools = platform_tools(&Arc::new(ServiceRegistry::default()));
+        assert_eq!(tools.len(), 2);
+
+        let def = tools[0].definition();
+        assert_eq!(def.name, "platform_health");
+        assert_eq!(def.source_app, Some(App::Shared));
+        assert!(def.required_permissions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_platform_search_needs_a_search_permission() {
+        let tool = PlatformSearchTool::new(Arc::new(ServiceRegistry::default()));
+        let result = tool
+            .execute(
+                serde_json::json!({"query": "revenue"}),
+                &ToolContext::empty(),
+            )
+            .await;
+        assert!(matches!(result, Err(McpServerError::PermissionDenied(_))));
+
+        let result = tool
+            .execute(
+                serde_json::json!({"query": "revenue", "apps": ["jira"]}),
+                &ToolContext::empty(),
+            )
+            .await;
+        assert!(matches!(result, Err(McpServerError::InvalidParams(_))));
+    }
+}
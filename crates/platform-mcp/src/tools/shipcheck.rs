@@ -4,37 +4,33 @@
 //! These tools communicate with the ShipCheck service via HTTP to perform
 //! code verification operations.
 
-use crate::clients::config::ServiceConfig;
+use crate::clients::registry::ServiceRegistry;
 use crate::clients::shipcheck::{
     AnalyzeCodeParams as ClientAnalyzeParams, RunPipelineParams as ClientPipelineParams,
-    SearchFindingsParams as ClientSearchParams, ShipCheckClient,
-    VerifyPRParams as ClientVerifyPRParams,
+    SearchFindingsParams as ClientSearchParams, VerifyPRParams as ClientVerifyPRParams,
 };
 use crate::server::{McpServerError, McpServerResult, Tool, ToolContext};
 use crate::types::{ToolDefinition, ToolResult};
 use async_trait::async_trait;
 use platform_rbac::App;
 use serde::Deserialize;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 use tracing::{debug, error, instrument};
 
-/// Lazily initialized ShipCheck client singleton.
-static SHIPCHECK_CLIENT: OnceLock<ShipCheckClient> = OnceLock::new();
-
-/// Get or initialize the ShipCheck client.
-fn get_client() -> &'static ShipCheckClient {
-    SHIPCHECK_CLIENT.get_or_init(|| {
-        let config = ServiceConfig::from_env();
-        let timeout = config.timeout();
-        ShipCheckClient::new(config.shipcheck, timeout)
-    })
-}
-
 /// Tool to analyze code for issues.
 ///
 /// Performs static analysis on code to find bugs, security issues, and style problems
 /// by calling the ShipCheck analysis service.
-pub struct AnalyzeCodeTool;
+pub struct AnalyzeCodeTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl AnalyzeCodeTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for AnalyzeCodeTool {
@@ -88,7 +84,7 @@ impl Tool for AnalyzeCodeTool {
 
         debug!("Analyzing code for repository: {}", params.repository_id);
 
-        let client = get_client();
+        let client = self.services.shipcheck(context);
 
         let client_params = ClientAnalyzeParams {
             repository_id: params.repository_id.clone(),
@@ -132,7 +128,16 @@ fn default_checks() -> Vec<String> {
 ///
 /// Analyzes a PR for code quality, security, and compliance by calling
 /// the ShipCheck PR verification service.
-pub struct VerifyPRTool;
+pub struct VerifyPRTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl VerifyPRTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for VerifyPRTool {
@@ -188,7 +193,7 @@ impl Tool for VerifyPRTool {
             params.pr_number, params.repository_id
         );
 
-        let client = get_client();
+        let client = self.services.shipcheck(context);
 
         let client_params = ClientVerifyPRParams {
             repository_id: params.repository_id.clone(),
@@ -233,7 +238,16 @@ fn default_true() -> bool {
 ///
 /// Searches through code analysis findings across repositories using the
 /// ShipCheck search service.
-pub struct SearchFindingsTool;
+pub struct SearchFindingsTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl SearchFindingsTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for SearchFindingsTool {
@@ -286,13 +300,16 @@ impl Tool for SearchFindingsTool {
 
         debug!("Searching findings with query: {}", params.query);
 
-        let client = get_client();
+        let client = self.services.shipcheck(context);
 
         let client_params = ClientSearchParams {
             query: params.query.clone(),
             repository_id: params.repository_id.clone(),
             severity: params.severity.clone(),
             status: params.status.clone(),
+            date_range: None,
+            project_id: None,
+            tags: Vec::new(),
             limit: params.limit,
         };
 
@@ -332,7 +349,16 @@ fn default_limit() -> u32 {
 ///
 /// Triggers a full verification pipeline on a repository or branch by calling
 /// the ShipCheck pipeline service.
-pub struct RunPipelineTool;
+pub struct RunPipelineTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl RunPipelineTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for RunPipelineTool {
@@ -385,7 +411,7 @@ impl Tool for RunPipelineTool {
 
         debug!("Running pipeline for repository: {}", params.repository_id);
 
-        let client = get_client();
+        let client = self.services.shipcheck(context);
 
         let client_params = ClientPipelineParams {
             repository_id: params.repository_id.clone(),
@@ -429,12 +455,12 @@ fn default_pipeline() -> String {
 ///
 /// Returns a vector of all ShipCheck MCP tools that can be registered
 /// with an MCP server.
-pub fn shipcheck_tools() -> Vec<Arc<dyn Tool>> {
+pub fn shipcheck_tools(services: &Arc<ServiceRegistry>) -> Vec<Arc<dyn Tool>> {
     vec![
-        Arc::new(AnalyzeCodeTool),
-        Arc::new(VerifyPRTool),
-        Arc::new(SearchFindingsTool),
-        Arc::new(RunPipelineTool),
+        Arc::new(AnalyzeCodeTool::new(services.clone())),
+        Arc::new(VerifyPRTool::new(services.clone())),
+        Arc::new(SearchFindingsTool::new(services.clone())),
+        Arc::new(RunPipelineTool::new(services.clone())),
     ]
 }
 
@@ -444,7 +470,7 @@ mod tests {
 
     #[test]
     fn test_analyze_code_tool_definition() {
-        let tool = AnalyzeCodeTool;
+        let tool = AnalyzeCodeTool::new(Arc::new(ServiceRegistry::default()));
         let def = tool.definition();
         assert_eq!(def.name, "shipcheck_analyze_code");
         assert_eq!(def.source_app, Some(App::ShipCheck));
@@ -452,13 +478,13 @@ mod tests {
 
     #[test]
     fn test_all_shipcheck_tools() {
-        let tools = shipcheck_tools();
+        let tools = shipcheck_tools(&Arc::new(ServiceRegistry::default()));
         assert_eq!(tools.len(), 4);
     }
 
     #[test]
     fn test_tool_categories() {
-        let tools = shipcheck_tools();
+        let tools = shipcheck_tools(&Arc::new(ServiceRegistry::default()));
         let categories: Vec<_> = tools
             .iter()
             .map(|t| t.definition().category.clone())
@@ -4,38 +4,34 @@
 //! These tools communicate with the Verity service via HTTP to perform
 //! content verification operations.
 
-use crate::clients::config::ServiceConfig;
+use crate::clients::registry::ServiceRegistry;
 use crate::clients::verity::{
     CheckPropagationParams as ClientPropagationParams,
     ExtractAssertionsParams as ClientExtractParams, SearchFilters,
     SearchKnowledgeParams as ClientSearchParams, VerifyDocumentParams as ClientVerifyParams,
-    VerityClient,
 };
 use crate::server::{McpServerError, McpServerResult, Tool, ToolContext};
 use crate::types::{ToolDefinition, ToolResult};
 use async_trait::async_trait;
 use platform_rbac::App;
 use serde::Deserialize;
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 use tracing::{debug, error, instrument};
 
-/// Lazily initialized Verity client singleton.
-static VERITY_CLIENT: OnceLock<VerityClient> = OnceLock::new();
-
-/// Get or initialize the Verity client.
-fn get_client() -> &'static VerityClient {
-    VERITY_CLIENT.get_or_init(|| {
-        let config = ServiceConfig::from_env();
-        let timeout = config.timeout();
-        VerityClient::new(config.verity, timeout)
-    })
-}
-
 /// Tool to verify document assertions.
 ///
 /// Analyzes a document and verifies all factual claims against trusted knowledge
 /// sources by calling the Verity verification service.
-pub struct VerifyDocumentTool;
+pub struct VerifyDocumentTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl VerifyDocumentTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for VerifyDocumentTool {
@@ -72,7 +68,7 @@ impl Tool for VerifyDocumentTool {
 
         debug!("Verifying document: {}", params.document_id);
 
-        let client = get_client();
+        let client = self.services.verity(context);
 
         let client_params = ClientVerifyParams {
             document_id: params.document_id.clone(),
@@ -109,7 +105,16 @@ struct VerifyDocumentParams {
 ///
 /// Analyzes text content and extracts factual claims that can be verified
 /// by calling the Verity extraction service.
-pub struct ExtractAssertionsTool;
+pub struct ExtractAssertionsTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl ExtractAssertionsTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for ExtractAssertionsTool {
@@ -156,7 +161,7 @@ impl Tool for ExtractAssertionsTool {
             params.content.len()
         );
 
-        let client = get_client();
+        let client = self.services.verity(context);
 
         let client_params = ClientExtractParams {
             content: params.content.clone(),
@@ -194,7 +199,16 @@ struct ExtractAssertionsParams {
 ///
 /// Searches for verified facts and sources in the knowledge base using the
 /// Verity search service.
-pub struct SearchKnowledgeTool;
+pub struct SearchKnowledgeTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl SearchKnowledgeTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for SearchKnowledgeTool {
@@ -255,7 +269,7 @@ impl Tool for SearchKnowledgeTool {
 
         debug!("Searching knowledge base with query: {}", params.query);
 
-        let client = get_client();
+        let client = self.services.verity(context);
 
         // Convert filters if provided
         let filters = params.filters.map(|f| SearchFilters {
@@ -273,6 +287,8 @@ impl Tool for SearchKnowledgeTool {
                     end: end.to_string(),
                 })
             }),
+            project_id: None,
+            tags: Vec::new(),
         });
 
         let client_params = ClientSearchParams {
@@ -314,7 +330,16 @@ fn default_limit() -> u32 {
 ///
 /// Analyzes how an assertion or correction propagates through related documents
 /// using the Verity propagation analysis service.
-pub struct CheckPropagationTool;
+pub struct CheckPropagationTool {
+    services: Arc<ServiceRegistry>,
+}
+
+impl CheckPropagationTool {
+    /// Create the tool using clients from `services`.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self { services }
+    }
+}
 
 #[async_trait]
 impl Tool for CheckPropagationTool {
@@ -359,7 +384,7 @@ impl Tool for CheckPropagationTool {
             params.assertion_id
         );
 
-        let client = get_client();
+        let client = self.services.verity(context);
 
         let client_params = ClientPropagationParams {
             assertion_id: params.assertion_id.clone(),
@@ -399,12 +424,12 @@ fn default_depth() -> u32 {
 ///
 /// Returns a vector of all Verity MCP tools that can be registered
 /// with an MCP server.
-pub fn verity_tools() -> Vec<Arc<dyn Tool>> {
+pub fn verity_tools(services: &Arc<ServiceRegistry>) -> Vec<Arc<dyn Tool>> {
     vec![
-        Arc::new(VerifyDocumentTool),
-        Arc::new(ExtractAssertionsTool),
-        Arc::new(SearchKnowledgeTool),
-        Arc::new(CheckPropagationTool),
+        Arc::new(VerifyDocumentTool::new(services.clone())),
+        Arc::new(ExtractAssertionsTool::new(services.clone())),
+        Arc::new(SearchKnowledgeTool::new(services.clone())),
+        Arc::new(CheckPropagationTool::new(services.clone())),
     ]
 }
 
@@ -414,7 +439,7 @@ mod tests {
 
     #[test]
     fn test_verify_document_tool_definition() {
-        let tool = VerifyDocumentTool;
+        let tool = VerifyDocumentTool::new(Arc::new(ServiceRegistry::default()));
         let def = tool.definition();
         assert_eq!(def.name, "verity_verify_document");
         assert_eq!(def.source_app, Some(App::Verity));
@@ -422,13 +447,13 @@ mod tests {
 
     #[test]
     fn test_all_verity_tools() {
-        let tools = verity_tools();
+        let tools = verity_tools(&Arc::new(ServiceRegistry::default()));
         assert_eq!(tools.len(), 4);
     }
 
     #[test]
     fn test_tool_categories() {
-        let tools = verity_tools();
+        let tools = verity_tools(&Arc::new(ServiceRegistry::default()));
         let categories: Vec<_> = tools
             .iter()
             .map(|t| t.definition().category.clone())
@@ -0,0 +1,331 @@
+//! Distributed trace context propagation.
+//!
+//! Every request made by the service clients carries W3C Trace Context
+//! headers (`traceparent` and `tracestate`) and, when the tool call has one,
+//! the caller's correlation ID as `X-Correlation-ID`. All requests made during
+//! a tool call share the call's trace ID, so a workflow such as
+//! `workflow_verify_meeting_notes` can be followed from NoteMan into Verity.
+//!
+//! [`McpServer::call_tool`](crate::McpServer::call_tool) starts a trace (and
+//! assigns a correlation ID) for calls that arrive without one. Events emitted
+//! on behalf of a tool call should be stamped with
+//! [`ToolContext::stamp_event`] so subscribers can join the same trace.
+//!
+//! With the `otel` feature, the `otel` module exports spans over OTLP and
+//! the headers follow the current OpenTelemetry span instead.
+
+use crate::server::ToolContext;
+use reqwest::RequestBuilder;
+use uuid::Uuid;
+
+/// W3C trace parent header.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// W3C vendor trace state header.
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+/// Correlation ID header.
+pub const CORRELATION_ID_HEADER: &str = "X-Correlation-ID";
+
+/// Event metadata key carrying the trace parent of the emitting call.
+pub const TRACEPARENT_METADATA_KEY: &str = "traceparent";
+
+/// W3C trace context for one span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// Trace ID (32 lowercase hex characters).
+    pub trace_id: String,
+
+    /// Span ID of the current span (16 lowercase hex characters).
+    pub span_id: String,
+
+    /// Whether the trace is sampled.
+    pub sampled: bool,
+
+    /// Vendor-specific trace state, passed through unchanged.
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Start a new sampled trace.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: hex(Uuid::new_v4().as_bytes()),
+            span_id: new_span_id(),
+            sampled: true,
+            tracestate: None,
+        }
+    }
+
+    /// Parse `traceparent` and `tracestate` header values.
+    ///
+    /// Returns `None` for malformed or invalid trace parents.
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if !is_hex(version, 2) || version == "ff" {
+            return None;
+        }
+        // Version 00 has exactly four fields; later versions may append more.
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+        if !is_hex(trace_id, 32) || !is_hex(span_id, 16) || !is_hex(flags, 2) {
+            return None;
+        }
+        if trace_id.bytes().all(|b| b == b'0') || span_id.bytes().all(|b| b == b'0') {
+            return None;
+        }
+
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            sampled: flags & 0x01 == 0x01,
+            tracestate: tracestate
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        })
+    }
+
+    /// Create a child span in the same trace.
+    pub fn child(&self) -> Self {
+        Self {
+            span_id: new_span_id(),
+            ..self.clone()
+        }
+    }
+
+    /// Format the `traceparent` header value.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            self.trace_id,
+            self.span_id,
+            u8::from(self.sampled)
+        )
+    }
+}
+
+impl ToolContext {
+    /// Get a copy of this context ready for a tool call.
+    ///
+    /// Continues the incoming trace in a new span (or starts a trace) and
+    /// assigns a correlation ID if the caller did not provide one.
+    pub fn traced(&self) -> Self {
+        let mut context = self.clone();
+        context.trace = Some(match self.trace {
+            Some(ref trace) => trace.child(),
+            None => TraceContext::new_root(),
+        });
+        if context.correlation_id.is_none() {
+            context.correlation_id = Some(Uuid::now_v7().to_string());
+        }
+        context
+    }
+
+    /// Stamp an event emitted during this tool call with the caller's
+    /// correlation ID, trace and identity.
+    ///
+    /// Fields already set on the event are left unchanged.
+    pub fn stamp_event(&self, mut event: platform_events::Event) -> platform_events::Event {
+        if event.correlation_id.is_none() {
+            event.correlation_id = self.correlation_id.clone();
+        }
+        event.org_id = event.org_id.or(self.org_id);
+        event.project_id = event.project_id.or(self.project_id);
+        event.user_id = event.user_id.or(self.user_id);
+        if let Some(trace) = current_trace(self) {
+            event
+                .metadata
+                .entry(TRACEPARENT_METADATA_KEY.to_string())
+                .or_insert_with(|| trace.traceparent().into());
+        }
+        event
+    }
+}
+
+/// Add trace context and correlation headers to an outgoing request.
+///
+/// Each request is sent as a new child span of the caller's trace.
+pub(crate) fn inject(mut request: RequestBuilder, context: Option<&ToolContext>) -> RequestBuilder {
+    let span = match context.and_then(current_trace) {
+        Some(trace) => trace.child(),
+        None => otel::current().unwrap_or_else(TraceContext::new_root),
+    };
+    request = request.header(TRACEPARENT_HEADER, span.traceparent());
+    if let Some(ref state) = span.tracestate {
+        request = request.header(TRACESTATE_HEADER, state);
+    }
+
+    if let Some(id) = context.and_then(|c| c.correlation_id.as_deref()) {
+        request = request.header(CORRELATION_ID_HEADER, id);
+    }
+    request
+}
+
+/// Get the trace to continue for a tool call.
+///
+/// The active OpenTelemetry span wins when the `otel` feature is enabled.
+fn current_trace(context: &ToolContext) -> Option<TraceContext> {
+    otel::current().or_else(|| context.trace.clone())
+}
+
+fn new_span_id() -> String {
+    hex(&Uuid::new_v4().as_bytes()[..8])
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// OpenTelemetry integration.
+///
+/// Install the exporter and add [`otel::layer`] to the application's
+/// `tracing` subscriber:
+///
+/// ```rust,ignore
+/// use tracing_subscriber::prelude::*;
+///
+/// let tracer = platform_mcp::trace::otel::otlp_tracer("platform-mcp", "http://localhost:4317")?;
+/// tracing_subscriber::registry()
+///     .with(platform_mcp::trace::otel::layer(tracer))
+///     .init();
+/// ```
+#[cfg(feature = "otel")]
+pub mod otel {
+    use super::TraceContext;
+    use opentelemetry::trace::{TraceContextExt, TraceError};
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    pub use opentelemetry_sdk::trace::Tracer;
+
+    /// Build a tracer that exports spans to an OTLP gRPC collector.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn otlp_tracer(service_name: &str, endpoint: &str) -> Result<Tracer, TraceError> {
+        opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+                opentelemetry_sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+                    "service.name",
+                    service_name.to_string(),
+                )]),
+            ))
+            .install_batch(opentelemetry_sdk::runtime::Tokio)
+    }
+
+    /// Create a `tracing` layer that records spans with `tracer`.
+    pub fn layer<S>(tracer: Tracer) -> tracing_opentelemetry::OpenTelemetryLayer<S, Tracer>
+    where
+        S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(tracer)
+    }
+
+    /// Get the trace context of the current `tracing` span, if it is
+    /// recorded by OpenTelemetry.
+    pub(crate) fn current() -> Option<TraceContext> {
+        let context = tracing::Span::current().context();
+        let span = context.span();
+        let span_context = span.span_context();
+        if !span_context.is_valid() {
+            return None;
+        }
+
+        let state = span_context.trace_state().header();
+        Some(TraceContext {
+            trace_id: span_context.trace_id().to_string(),
+            span_id: span_context.span_id().to_string(),
+            sampled: span_context.is_sampled(),
+            tracestate: (!state.is_empty()).then_some(state),
+        })
+    }
+}
+
+#[cfg(not(feature = "otel"))]
+mod otel {
+    use super::TraceContext;
+
+    /// Without OpenTelemetry there is no ambient span to follow.
+    pub(crate) fn current() -> Option<TraceContext> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traceparent_roundtrip() {
+        let header = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let trace = TraceContext::parse(header, Some("vendor=abc")).unwrap();
+        assert_eq!(trace.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(trace.sampled);
+        assert_eq!(trace.traceparent(), header);
+
+        let child = trace.child();
+        assert_eq!(child.trace_id, trace.trace_id);
+        assert_ne!(child.span_id, trace.span_id);
+        assert_eq!(child.tracestate.as_deref(), Some("vendor=abc"));
+
+        let root = TraceContext::new_root();
+        assert_eq!(TraceContext::parse(&root.traceparent(), None), Some(root));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_traceparent() {
+        for header in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(TraceContext::parse(header, None).is_none(), "{}", header);
+        }
+    }
+
+    #[test]
+    fn test_stamp_event_uses_call_context() {
+        let context = ToolContext::empty().traced();
+        let event = context.stamp_event(platform_events::Event::new(
+            "workflow.completed",
+            platform_rbac::App::Verity,
+            serde_json::json!({}),
+        ));
+
+        assert_eq!(event.correlation_id, context.correlation_id);
+        let traceparent = event.metadata[TRACEPARENT_METADATA_KEY].as_str().unwrap();
+        let trace = TraceContext::parse(traceparent, None).unwrap();
+        assert_eq!(trace.trace_id, context.trace.unwrap().trace_id);
+
+        // An explicit correlation ID on the event is kept.
+        let event = ToolContext::empty().traced().stamp_event(
+            platform_events::Event::new("x", platform_rbac::App::Verity, serde_json::json!({}))
+                .with_correlation_id("upstream"),
+        );
+        assert_eq!(event.correlation_id.as_deref(), Some("upstream"));
+    }
+}
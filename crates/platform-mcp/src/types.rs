@@ -300,6 +300,25 @@ impl ToolResult {
             is_error: false,
         }
     }
+
+    /// Get the output of a successful result, or the message of an error.
+    ///
+    /// Text content is joined by lines and parsed as JSON if possible.
+    pub fn into_output(self) -> Result<serde_json::Value, String> {
+        let text = self
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.is_error {
+            return Err(text);
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text)))
+    }
 }
 
 /// Content block in tool results.
@@ -446,4 +465,18 @@ mod tests {
         let error = ToolResult::error("Something went wrong");
         assert!(error.is_error);
     }
+
+    #[test]
+    fn test_tool_result_output() {
+        let output = serde_json::json!({"status": "ok"});
+        assert_eq!(ToolResult::json(output.clone()).into_output(), Ok(output));
+        assert_eq!(
+            ToolResult::text("done").into_output(),
+            Ok(serde_json::json!("done"))
+        );
+        assert_eq!(
+            ToolResult::error("failed").into_output(),
+            Err("failed".to_string())
+        );
+    }
 }
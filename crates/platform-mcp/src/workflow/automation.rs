@@ -0,0 +1,826 @@
+//! Event-triggered workflow automations.
+//!
+//! An [`AutomationRule`] runs a workflow when an event on the platform event
+//! bus matches a topic pattern and an optional condition. The workflow input
+//! is rendered from the event with the usual templates, where `event` refers
+//! to the event:
+//!
+//! ```yaml
+//! id: verify-completed-meetings
+//! org_id: 0190a0c2-5f3e-7000-8000-000000000001
+//! topic: noteman.meeting.completed
+//! when: {exists: "${event.payload.meeting_id}"}
+//! workflow: workflow_verify_meeting_notes
+//! input:
+//!   meeting_id: ${event.payload.meeting_id}
+//!   content_type: summary
+//! ```
+//!
+//! A rule only reacts to the events of its organization, and only the
+//! organization's administrators (with the [`MANAGE_PERMISSION`] permission)
+//! can register it. The workflow runs as the administrator who registered
+//! the rule, with their permissions, whoever caused the event.
+//!
+//! # Loop protection
+//!
+//! Workflows call services, which may publish events that trigger further
+//! rules. Events are grouped into chains by correlation ID: a triggered
+//! workflow calls services with the chain's correlation ID, so the events
+//! they publish in turn belong to the same chain. A chain runs at most
+//! [`DEFAULT_MAX_CHAIN_RUNS`] workflows, so rules cannot trigger each other
+//! endlessly.
+//!
+//! Each rule runs once per event. A redelivered event runs the rule again
+//! with the same request ID, so the workflow returns the recorded results of
+//! its mutating steps instead of repeating them, and does not count against
+//! the chain.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use platform_events::{EventBus, MemoryEventBus};
+//! use platform_mcp::clients::ServiceRegistry;
+//! use platform_mcp::tools::builtin_workflows;
+//! use platform_mcp::workflow::{AutomationRule, Automations, WorkflowEngine};
+//! use platform_mcp::ToolContext;
+//! use std::sync::Arc;
+//! use uuid::Uuid;
+//!
+//! async fn automate(bus: &MemoryEventBus, org_id: Uuid, admin: &ToolContext) {
+//!     let services = Arc::new(ServiceRegistry::from_env());
+//!     let automations = Arc::new(
+//!         Automations::new(WorkflowEngine::new(services)).with_workflows(builtin_workflows()),
+//!     );
+//!     automations
+//!         .add_rule(
+//!             AutomationRule::new(
+//!                 "verify-completed-meetings",
+//!                 org_id,
+//!                 "noteman.meeting.completed",
+//!                 "workflow_verify_meeting_notes",
+//!             )
+//!             .with_input(serde_json::json!({"meeting_id": "${event.payload.meeting_id}"})),
+//!             admin,
+//!         )
+//!         .unwrap();
+//!     bus.register_handler(automations).await.unwrap();
+//! }
+//! ```
+
+use super::definition::{Condition, WorkflowDefinition};
+use super::engine::{WorkflowEngine, WorkflowError};
+use super::template;
+use crate::server::ToolContext;
+use async_trait::async_trait;
+use platform_events::{topic_matches, Event, EventBusError, EventBusResult, EventHandler};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Default maximum number of workflows one event chain can trigger.
+pub const DEFAULT_MAX_CHAIN_RUNS: usize = 10;
+
+/// Default time an event chain is remembered after its last event.
+pub const DEFAULT_CHAIN_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Permission needed to register rules for an organization.
+pub const MANAGE_PERMISSION: &str = "organization:manage";
+
+/// Errors from registering or running automations.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum AutomationError {
+    /// The rule is invalid.
+    #[error("Invalid automation rule: {0}")]
+    InvalidRule(String),
+
+    /// The rule runs a workflow the runtime does not have.
+    #[error("Unknown workflow: {0}")]
+    UnknownWorkflow(String),
+
+    /// The caller may not register rules for the rule's organization.
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// Running the rule would continue an event loop.
+    #[error("Automation loop prevented: {0}")]
+    LoopDetected(String),
+
+    /// The triggered workflow failed.
+    #[error(transparent)]
+    Workflow(#[from] WorkflowError),
+}
+
+/// A rule running a workflow when a matching event is published.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationRule {
+    /// Unique rule ID.
+    pub id: String,
+
+    /// Organization whose events trigger the rule.
+    pub org_id: Uuid,
+
+    /// Topic pattern, e.g. `noteman.meeting.*` (see
+    /// [`topic_matches`](platform_events::topic_matches)).
+    pub topic: String,
+
+    /// Condition on the event; the rule only runs when it holds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+
+    /// Name of the workflow to run.
+    pub workflow: String,
+
+    /// Workflow input template.
+    #[serde(default)]
+    pub input: Value,
+
+    /// Whether the rule is active.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// User the workflow runs as, the caller who registered the rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<Uuid>,
+
+    /// Permissions the workflow runs with, those of the caller who
+    /// registered the rule.
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AutomationRule {
+    /// Create a rule running `workflow` on `org_id`'s events matching
+    /// `topic`.
+    pub fn new(
+        id: impl Into<String>,
+        org_id: Uuid,
+        topic: impl Into<String>,
+        workflow: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            org_id,
+            topic: topic.into(),
+            when: None,
+            workflow: workflow.into(),
+            input: Value::Object(Default::default()),
+            enabled: true,
+            created_by: None,
+            permissions: Vec::new(),
+        }
+    }
+
+    /// Parse a rule from YAML.
+    pub fn from_yaml(yaml: &str) -> Result<Self, AutomationError> {
+        let value: Value =
+            serde_yaml::from_str(yaml).map_err(|e| AutomationError::InvalidRule(e.to_string()))?;
+        serde_json::from_value(value).map_err(|e| AutomationError::InvalidRule(e.to_string()))
+    }
+
+    /// Parse a rule from JSON.
+    pub fn from_json(json: &str) -> Result<Self, AutomationError> {
+        serde_json::from_str(json).map_err(|e| AutomationError::InvalidRule(e.to_string()))
+    }
+
+    /// Only run when `condition` holds for the event.
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.when = Some(condition);
+        self
+    }
+
+    /// Set the workflow input template.
+    pub fn with_input(mut self, input: Value) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Enable or disable the rule.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Check the rule's fields and templates.
+    pub fn validate(&self) -> Result<(), AutomationError> {
+        if self.id.is_empty() {
+            return Err(AutomationError::InvalidRule("missing rule ID".to_string()));
+        }
+        if self.topic.is_empty() {
+            return Err(AutomationError::InvalidRule(format!(
+                "rule '{}' has no topic",
+                self.id
+            )));
+        }
+
+        let mut steps = BTreeSet::new();
+        template::references(&self.input, &mut steps)?;
+        if let Some(ref condition) = self.when {
+            condition.references(&mut steps)?;
+        }
+        Ok(())
+    }
+
+    /// Check whether the rule reacts to `event`.
+    fn matches(&self, event: &Event, scope: &Value) -> Result<bool, WorkflowError> {
+        if !self.enabled
+            || event.org_id != Some(self.org_id)
+            || !topic_matches(&self.topic, &event.topic())
+        {
+            return Ok(false);
+        }
+        match self.when {
+            Some(ref condition) => condition.evaluate(scope),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Workflows triggered so far by one chain of events.
+struct Chain {
+    /// Rules that already ran, with the event each ran for.
+    runs: HashSet<(String, Uuid)>,
+
+    /// When the chain last triggered a rule.
+    last_seen: Instant,
+}
+
+/// Runs workflows for the events matching registered rules.
+///
+/// Register the runtime with an event bus as an [`EventHandler`] to react to
+/// published events, or pass events to [`dispatch`](Self::dispatch).
+pub struct Automations {
+    /// Engine running the workflows.
+    engine: WorkflowEngine,
+
+    /// Workflows rules can run, by name.
+    workflows: HashMap<String, WorkflowDefinition>,
+
+    /// Registered rules.
+    rules: RwLock<Vec<AutomationRule>>,
+
+    /// Event chains by correlation ID.
+    chains: Mutex<HashMap<String, Chain>>,
+
+    /// Maximum number of workflows one chain can trigger.
+    max_chain_runs: usize,
+
+    /// Time a chain is remembered after its last event.
+    chain_ttl: Duration,
+}
+
+impl Automations {
+    /// Create a runtime without workflows or rules.
+    pub fn new(engine: WorkflowEngine) -> Self {
+        Self {
+            engine,
+            workflows: HashMap::new(),
+            rules: RwLock::new(Vec::new()),
+            chains: Mutex::new(HashMap::new()),
+            max_chain_runs: DEFAULT_MAX_CHAIN_RUNS,
+            chain_ttl: DEFAULT_CHAIN_TTL,
+        }
+    }
+
+    /// Make a workflow available to rules.
+    pub fn with_workflow(mut self, definition: WorkflowDefinition) -> Self {
+        self.workflows.insert(definition.name.clone(), definition);
+        self
+    }
+
+    /// Make several workflows available to rules.
+    pub fn with_workflows(self, definitions: impl IntoIterator<Item = WorkflowDefinition>) -> Self {
+        definitions
+            .into_iter()
+            .fold(self, |automations, definition| {
+                automations.with_workflow(definition)
+            })
+    }
+
+    /// Set the maximum number of workflows one event chain can trigger.
+    pub fn with_max_chain_runs(mut self, max_chain_runs: usize) -> Self {
+        self.max_chain_runs = max_chain_runs;
+        self
+    }
+
+    /// Set how long an event chain is remembered after its last event.
+    pub fn with_chain_ttl(mut self, ttl: Duration) -> Self {
+        self.chain_ttl = ttl;
+        self
+    }
+
+    /// Register a rule on behalf of the caller in `context`, replacing any
+    /// rule with the same ID.
+    ///
+    /// The caller must administer the rule's organization, i.e. belong to it
+    /// and have the [`MANAGE_PERMISSION`] permission, and have the
+    /// permissions of the workflow. The workflow runs as the caller.
+    pub fn add_rule(
+        &self,
+        mut rule: AutomationRule,
+        context: &ToolContext,
+    ) -> Result<(), AutomationError> {
+        if context.org_id != Some(rule.org_id) || !context.has_permission(MANAGE_PERMISSION) {
+            return Err(AutomationError::PermissionDenied(format!(
+                "registering rules for organization {} requires {}",
+                rule.org_id, MANAGE_PERMISSION
+            )));
+        }
+        rule.validate()?;
+        let Some(definition) = self.workflows.get(&rule.workflow) else {
+            return Err(AutomationError::UnknownWorkflow(rule.workflow));
+        };
+        if let Some(missing) = missing_permission(definition, &context.permissions) {
+            return Err(AutomationError::PermissionDenied(format!(
+                "Missing permission: {}",
+                missing
+            )));
+        }
+        rule.created_by = context.user_id;
+        rule.permissions = context.permissions.clone();
+
+        let mut rules = self.rules.write().unwrap_or_else(|e| e.into_inner());
+        rules.retain(|r| r.id != rule.id);
+        info!(rule = %rule.id, topic = %rule.topic, workflow = %rule.workflow, "Registered automation rule");
+        rules.push(rule);
+        Ok(())
+    }
+
+    /// Remove a rule, returning whether it existed.
+    pub fn remove_rule(&self, id: &str) -> bool {
+        let mut rules = self.rules.write().unwrap_or_else(|e| e.into_inner());
+        let before = rules.len();
+        rules.retain(|r| r.id != id);
+        rules.len() != before
+    }
+
+    /// Get a rule by ID.
+    pub fn rule(&self, id: &str) -> Option<AutomationRule> {
+        let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+        rules.iter().find(|r| r.id == id).cloned()
+    }
+
+    /// Get the rules registered for an organization.
+    pub fn rules_for_org(&self, org_id: Uuid) -> Vec<AutomationRule> {
+        let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+        rules
+            .iter()
+            .filter(|r| r.org_id == org_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Run the workflows of every rule matching `event`, returning the
+    /// result of each by rule ID.
+    ///
+    /// Rules whose condition does not hold are left out.
+    pub async fn dispatch(&self, event: &Event) -> Vec<(String, Result<Value, AutomationError>)> {
+        let scope = json!({
+            "event": {
+                "id": event.id,
+                "type": event.event_type,
+                "topic": event.topic(),
+                "source": event.source,
+                "timestamp": event.timestamp,
+                "org_id": event.org_id,
+                "project_id": event.project_id,
+                "user_id": event.user_id,
+                "correlation_id": event.correlation_id,
+                "payload": event.payload,
+                "metadata": event.metadata,
+            }
+        });
+
+        let matching: Vec<_> = {
+            let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+            rules
+                .iter()
+                .filter_map(|rule| match rule.matches(event, &scope) {
+                    Ok(true) => Some((rule.clone(), Ok(()))),
+                    Ok(false) => None,
+                    Err(e) => Some((rule.clone(), Err(AutomationError::from(e)))),
+                })
+                .collect()
+        };
+
+        let chain = event
+            .correlation_id
+            .clone()
+            .unwrap_or_else(|| event.id.to_string());
+        let runs = matching.into_iter().map(|(rule, matched)| {
+            let scope = &scope;
+            let chain = &chain;
+            async move {
+                let result = match matched {
+                    Ok(()) => self.trigger(&rule, event, scope, chain).await,
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(_) => info!(rule = %rule.id, event_id = %event.id, "Automation completed"),
+                    Err(ref e) => {
+                        warn!(rule = %rule.id, event_id = %event.id, error = %e, "Automation failed")
+                    }
+                }
+                (rule.id, result)
+            }
+        });
+        futures::future::join_all(runs).await
+    }
+
+    /// Run a matching rule's workflow as part of `chain`.
+    async fn trigger(
+        &self,
+        rule: &AutomationRule,
+        event: &Event,
+        scope: &Value,
+        chain: &str,
+    ) -> Result<Value, AutomationError> {
+        let definition = self
+            .workflows
+            .get(&rule.workflow)
+            .ok_or_else(|| AutomationError::UnknownWorkflow(rule.workflow.clone()))?;
+        if let Some(missing) = missing_permission(definition, &rule.permissions) {
+            return Err(AutomationError::PermissionDenied(format!(
+                "Missing permission: {}",
+                missing
+            )));
+        }
+        let input = template::render(&rule.input, scope)?;
+        self.enter_chain(chain, &rule.id, event.id)?;
+
+        // The workflow runs as the rule's creator, not as whoever caused the
+        // event. Services stamp the events they publish with the correlation
+        // ID, so events caused by this run continue the chain. The request ID
+        // makes a redelivered event reuse the recorded results of mutating
+        // steps.
+        let context = ToolContext {
+            user_id: rule.created_by,
+            org_id: Some(rule.org_id),
+            project_id: event.project_id,
+            permissions: rule.permissions.clone(),
+            request_id: Some(format!("automation:{}:{}", rule.id, event.id)),
+            correlation_id: Some(chain.to_string()),
+            ..ToolContext::empty()
+        };
+        info!(rule = %rule.id, workflow = %rule.workflow, event_id = %event.id, chain = %chain, "Running automation");
+        Ok(self.engine.run(definition, input, &context).await?)
+    }
+
+    /// Record that `rule` runs for `event` in `chain`, unless the chain has
+    /// run too many workflows.
+    ///
+    /// A redelivery of an event the rule already ran for is let through
+    /// without counting again.
+    fn enter_chain(&self, chain: &str, rule: &str, event: Uuid) -> Result<(), AutomationError> {
+        let mut chains = self.chains.lock().unwrap_or_else(|e| e.into_inner());
+        chains.retain(|_, c| c.last_seen.elapsed() < self.chain_ttl);
+
+        let entry = chains.entry(chain.to_string()).or_insert_with(|| Chain {
+            runs: HashSet::new(),
+            last_seen: Instant::now(),
+        });
+        entry.last_seen = Instant::now();
+        let run = (rule.to_string(), event);
+        if entry.runs.contains(&run) {
+            return Ok(());
+        }
+        if entry.runs.len() >= self.max_chain_runs {
+            return Err(AutomationError::LoopDetected(format!(
+                "event chain {} already triggered {} workflows",
+                chain, self.max_chain_runs
+            )));
+        }
+        entry.runs.insert(run);
+        Ok(())
+    }
+}
+
+/// Get a permission `definition` requires that is not in `permissions`.
+fn missing_permission(definition: &WorkflowDefinition, permissions: &[String]) -> Option<String> {
+    definition
+        .permissions
+        .iter()
+        .find(|required| !permissions.contains(required))
+        .cloned()
+}
+
+impl std::fmt::Debug for Automations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("Automations")
+            .field("workflows", &self.workflows.keys().collect::<Vec<_>>())
+            .field("rules", &rules.len())
+            .field("max_chain_runs", &self.max_chain_runs)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl EventHandler for Automations {
+    async fn handle(&self, event: Event) -> EventBusResult<()> {
+        // A prevented loop would be prevented again on retry, so only other
+        // failures fail the delivery.
+        let failures: Vec<_> = self
+            .dispatch(&event)
+            .await
+            .into_iter()
+            .filter_map(|(rule, result)| match result {
+                Ok(_) | Err(AutomationError::LoopDetected(_)) => None,
+                Err(e) => Some(format!("rule '{}': {}", rule, e)),
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(EventBusError::HandlerFailed(failures.join("; ")))
+        }
+    }
+
+    fn topics(&self) -> Vec<String> {
+        // Rules can be added after the handler is registered, so listen to
+        // everything and match rules per event.
+        vec!["#".to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::registry::ServiceRegistry;
+    use crate::server::ToolContext;
+    use crate::workflow::{Operation, StepDefinition};
+    use platform_events::{EventBus, MemoryEventBus};
+    use platform_rbac::App;
+    use std::sync::Arc;
+
+    const RECORD_PERMISSION: &str = "meeting:record";
+
+    /// Records the inputs and callers it is called with, failing for
+    /// `fail: true`.
+    #[derive(Default)]
+    struct Recorder {
+        calls: Mutex<Vec<Value>>,
+        callers: Mutex<Vec<(Option<Uuid>, Vec<String>)>>,
+    }
+
+    #[async_trait]
+    impl Operation for Recorder {
+        async fn call(
+            &self,
+            _services: &ServiceRegistry,
+            context: &ToolContext,
+            input: Value,
+        ) -> Result<Value, String> {
+            if input["fail"] == true {
+                return Err("boom".to_string());
+            }
+            self.calls.lock().unwrap().push(input.clone());
+            self.callers
+                .lock()
+                .unwrap()
+                .push((context.user_id, context.permissions.clone()));
+            Ok(input)
+        }
+
+        fn mutating(&self) -> bool {
+            true
+        }
+    }
+
+    fn automations(recorder: Arc<Recorder>) -> Automations {
+        let engine = WorkflowEngine::new(Arc::new(ServiceRegistry::default()))
+            .with_operation("test.record", recorder);
+        let workflow = WorkflowDefinition::new("record_meeting", "")
+            .with_permissions(vec![RECORD_PERMISSION.to_string()])
+            .with_step(StepDefinition::new("record", "test.record").with_input(
+                json!({"meeting_id": "${input.meeting_id}", "fail": "${input.fail ?? false}"}),
+            ));
+        Automations::new(engine).with_workflow(workflow)
+    }
+
+    fn rule(id: &str, org_id: Uuid) -> AutomationRule {
+        AutomationRule::new(id, org_id, "noteman.meeting.completed", "record_meeting")
+            .with_input(json!({"meeting_id": "${event.payload.meeting_id}"}))
+    }
+
+    fn admin(org_id: Uuid) -> ToolContext {
+        ToolContext {
+            org_id: Some(org_id),
+            permissions: vec![MANAGE_PERMISSION.to_string(), RECORD_PERMISSION.to_string()],
+            ..ToolContext::empty()
+        }
+    }
+
+    fn meeting_completed(org_id: Uuid) -> Event {
+        Event::new(
+            "meeting.completed",
+            App::NoteMan,
+            json!({"meeting_id": "mtg-1", "status": "completed"}),
+        )
+        .with_org(org_id)
+    }
+
+    #[tokio::test]
+    async fn test_rules_match_topic_org_and_condition() {
+        let recorder = Arc::new(Recorder::default());
+        let automations = automations(recorder.clone());
+        let org = Uuid::new_v4();
+        let other_org = Uuid::new_v4();
+
+        automations
+            .add_rule(rule("meetings", org), &admin(org))
+            .unwrap();
+        automations
+            .add_rule(rule("other-org", other_org), &admin(other_org))
+            .unwrap();
+        automations
+            .add_rule(
+                rule("cancelled", org).with_condition(Condition::Equals(
+                    json!("${event.payload.status}"),
+                    json!("cancelled"),
+                )),
+                &admin(org),
+            )
+            .unwrap();
+        assert_eq!(
+            automations.add_rule(AutomationRule::new("bad", org, "#", "unknown"), &admin(org)),
+            Err(AutomationError::UnknownWorkflow("unknown".to_string()))
+        );
+
+        let results = automations.dispatch(&meeting_completed(org)).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "meetings");
+        assert!(results[0].1.is_ok());
+        assert_eq!(
+            *recorder.calls.lock().unwrap(),
+            vec![json!({"meeting_id": "mtg-1", "fail": false})]
+        );
+
+        let other_topic = Event::new("meeting.started", App::NoteMan, json!({})).with_org(org);
+        assert!(automations.dispatch(&other_topic).await.is_empty());
+        let no_org = Event::new("meeting.completed", App::NoteMan, json!({}));
+        assert!(automations.dispatch(&no_org).await.is_empty());
+    }
+
+    #[test]
+    fn test_only_org_admins_add_rules() {
+        let automations = automations(Arc::new(Recorder::default()));
+        let org = Uuid::new_v4();
+        let member = ToolContext {
+            org_id: Some(org),
+            ..ToolContext::empty()
+        };
+        let without_workflow_permission = ToolContext {
+            permissions: vec![MANAGE_PERMISSION.to_string()],
+            ..member.clone()
+        };
+
+        for context in [member, without_workflow_permission, admin(Uuid::new_v4())] {
+            assert!(matches!(
+                automations.add_rule(rule("meetings", org), &context),
+                Err(AutomationError::PermissionDenied(_))
+            ));
+        }
+        assert!(automations.rules_for_org(org).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rules_run_as_their_creator() {
+        let recorder = Arc::new(Recorder::default());
+        let automations = automations(recorder.clone());
+        let org = Uuid::new_v4();
+        let creator = ToolContext {
+            user_id: Some(Uuid::new_v4()),
+            ..admin(org)
+        };
+        automations
+            .add_rule(rule("meetings", org), &creator)
+            .unwrap();
+
+        let event = meeting_completed(org).with_user(Uuid::new_v4());
+        assert!(automations.dispatch(&event).await[0].1.is_ok());
+        assert_eq!(
+            *recorder.callers.lock().unwrap(),
+            vec![(creator.user_id, creator.permissions.clone())]
+        );
+
+        // A rule whose stored permissions no longer cover the workflow does
+        // not run.
+        let mut stale = automations.rule("meetings").unwrap();
+        stale.permissions.clear();
+        automations.rules.write().unwrap()[0] = stale;
+        let results = automations.dispatch(&meeting_completed(org)).await;
+        assert!(matches!(
+            results[0].1,
+            Err(AutomationError::PermissionDenied(_))
+        ));
+        assert_eq!(recorder.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_event_chains_are_limited_and_redeliveries_replay() {
+        let recorder = Arc::new(Recorder::default());
+        let automations = automations(recorder.clone()).with_max_chain_runs(2);
+        let org = Uuid::new_v4();
+        for id in ["first", "second", "third"] {
+            automations.add_rule(rule(id, org), &admin(org)).unwrap();
+        }
+        let is_loop = |(_, r): &(String, Result<Value, AutomationError>)| {
+            matches!(r, Err(AutomationError::LoopDetected(_)))
+        };
+
+        // Three rules match, but the chain may only trigger two workflows.
+        let event = meeting_completed(org).with_correlation_id("chain-1");
+        let results = automations.dispatch(&event).await;
+        assert_eq!(results.iter().filter(|r| is_loop(r)).count(), 1);
+
+        // A redelivery reruns the same rules, replaying their results.
+        let redelivered = automations.dispatch(&event).await;
+        assert_eq!(redelivered, results);
+        assert_eq!(recorder.calls.lock().unwrap().len(), 2);
+
+        // An event caused by those runs carries the chain's correlation ID
+        // and does not trigger more workflows.
+        let caused = meeting_completed(org).with_correlation_id("chain-1");
+        let results = automations.dispatch(&caused).await;
+        assert!(results.iter().all(is_loop));
+        assert_eq!(recorder.calls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_distinct_events_of_a_chain_each_run() {
+        let recorder = Arc::new(Recorder::default());
+        let automations = automations(recorder.clone());
+        let org = Uuid::new_v4();
+        automations
+            .add_rule(rule("meetings", org), &admin(org))
+            .unwrap();
+
+        for _ in 0..2 {
+            let event = meeting_completed(org).with_correlation_id("request-1");
+            let results = automations.dispatch(&event).await;
+            assert!(results[0].1.is_ok());
+        }
+        assert_eq!(recorder.calls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_handler_reports_failed_rules() {
+        let automations = automations(Arc::new(Recorder::default()));
+        let org = Uuid::new_v4();
+        automations
+            .add_rule(
+                rule("failing", org).with_input(json!({"meeting_id": "mtg-1", "fail": true})),
+                &admin(org),
+            )
+            .unwrap();
+
+        let result = automations.handle(meeting_completed(org)).await;
+        assert!(
+            matches!(result, Err(EventBusError::HandlerFailed(ref e)) if e.contains("failing"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_event_bus_triggers_rules() {
+        let recorder = Arc::new(Recorder::default());
+        let automations = Arc::new(automations(recorder.clone()));
+        let org = Uuid::new_v4();
+        let rule = AutomationRule::from_yaml(&format!(
+            r#"
+id: verify-completed-meetings
+org_id: {}
+topic: noteman.meeting.*
+workflow: record_meeting
+input:
+  meeting_id: ${{event.payload.meeting_id}}
+"#,
+            org
+        ))
+        .unwrap();
+        automations.add_rule(rule, &admin(org)).unwrap();
+
+        let bus = MemoryEventBus::new();
+        bus.register_handler(automations).await.unwrap();
+        bus.publish(meeting_completed(org)).await.unwrap();
+
+        for _ in 0..50 {
+            if !recorder.calls.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            *recorder.calls.lock().unwrap(),
+            vec![json!({"meeting_id": "mtg-1", "fail": false})]
+        );
+    }
+}
@@ -0,0 +1,586 @@
+//! Workflow definitions.
+
+use super::engine::WorkflowError;
+use super::template;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A workflow exposed as an MCP tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowDefinition {
+    /// Tool name.
+    pub name: String,
+
+    /// Tool description.
+    pub description: String,
+
+    /// Tool category.
+    #[serde(default = "default_category")]
+    pub category: String,
+
+    /// Permissions required to run the workflow.
+    #[serde(default)]
+    pub permissions: Vec<String>,
+
+    /// JSON Schema of the workflow input.
+    ///
+    /// Required properties are checked and property defaults are applied
+    /// before the first step runs.
+    #[serde(default = "default_input_schema")]
+    pub input_schema: Value,
+
+    /// Steps of the workflow.
+    pub steps: Vec<StepDefinition>,
+
+    /// Template of the workflow output.
+    #[serde(default)]
+    pub output: Value,
+}
+
+impl WorkflowDefinition {
+    /// Create an empty workflow definition.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            category: default_category(),
+            permissions: Vec::new(),
+            input_schema: default_input_schema(),
+            steps: Vec::new(),
+            output: Value::Null,
+        }
+    }
+
+    /// Parse a definition from YAML.
+    pub fn from_yaml(yaml: &str) -> Result<Self, WorkflowError> {
+        // Go through JSON values so conditions use the same `{exists: ...}`
+        // map syntax as in JSON rather than YAML tags.
+        let value: Value = serde_yaml::from_str(yaml)
+            .map_err(|e| WorkflowError::InvalidDefinition(e.to_string()))?;
+        serde_json::from_value(value).map_err(|e| WorkflowError::InvalidDefinition(e.to_string()))
+    }
+
+    /// Parse a definition from JSON.
+    pub fn from_json(json: &str) -> Result<Self, WorkflowError> {
+        serde_json::from_str(json).map_err(|e| WorkflowError::InvalidDefinition(e.to_string()))
+    }
+
+    /// Set the tool category.
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = category.into();
+        self
+    }
+
+    /// Set the required permissions.
+    pub fn with_permissions(mut self, permissions: Vec<String>) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// Set the input schema.
+    pub fn with_schema(mut self, schema: Value) -> Self {
+        self.input_schema = schema;
+        self
+    }
+
+    /// Add a step.
+    pub fn with_step(mut self, step: StepDefinition) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Set the output template.
+    pub fn with_output(mut self, output: Value) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Get a step by ID.
+    pub fn step(&self, id: &str) -> Option<&StepDefinition> {
+        self.steps.iter().find(|step| step.id == id)
+    }
+
+    /// Check that step IDs are unique, that every template parses and every
+    /// referenced step exists, and that the steps do not form a cycle.
+    pub fn validate(&self) -> Result<(), WorkflowError> {
+        let invalid = |message: String| {
+            Err(WorkflowError::InvalidDefinition(format!(
+                "{}: {}",
+                self.name, message
+            )))
+        };
+
+        if self.name.is_empty() {
+            return invalid("workflow name is empty".to_string());
+        }
+
+        let mut ids = HashSet::new();
+        for step in &self.steps {
+            if step.id.is_empty() || step.id.contains('.') {
+                return invalid(format!("invalid step ID '{}'", step.id));
+            }
+            if !ids.insert(step.id.as_str()) {
+                return invalid(format!("duplicate step ID '{}'", step.id));
+            }
+        }
+
+        let mut dependencies = HashMap::new();
+        for step in &self.steps {
+            let deps = step.dependencies()?;
+            for dep in &deps {
+                if dep == &step.id {
+                    return invalid(format!("step '{}' depends on itself", step.id));
+                }
+                if !ids.contains(dep.as_str()) {
+                    return invalid(format!(
+                        "step '{}' depends on unknown step '{}'",
+                        step.id, dep
+                    ));
+                }
+            }
+            dependencies.insert(step.id.as_str(), deps);
+        }
+
+        let mut output_refs = BTreeSet::new();
+        template::references(&self.output, &mut output_refs)?;
+        if let Some(unknown) = output_refs.iter().find(|id| !ids.contains(id.as_str())) {
+            return invalid(format!("output references unknown step '{}'", unknown));
+        }
+
+        // Repeatedly remove steps whose dependencies are all removed; any
+        // steps left over are part of a cycle.
+        let mut resolved: HashSet<&str> = HashSet::new();
+        while resolved.len() < self.steps.len() {
+            let ready: Vec<&str> = self
+                .steps
+                .iter()
+                .map(|step| step.id.as_str())
+                .filter(|id| !resolved.contains(id))
+                .filter(|id| {
+                    dependencies[id]
+                        .iter()
+                        .all(|dep| resolved.contains(dep.as_str()))
+                })
+                .collect();
+            if ready.is_empty() {
+                let mut cycle: Vec<&str> = ids.difference(&resolved).copied().collect();
+                cycle.sort_unstable();
+                return invalid(format!("steps form a cycle: {}", cycle.join(", ")));
+            }
+            resolved.extend(ready);
+        }
+        Ok(())
+    }
+
+    /// Apply input schema defaults and check required properties and types.
+    pub(crate) fn prepare_input(&self, input: Value) -> Result<Value, WorkflowError> {
+        let mut input = match input {
+            Value::Object(fields) => fields,
+            Value::Null => serde_json::Map::new(),
+            _ => {
+                return Err(WorkflowError::InvalidInput(
+                    "expected an object".to_string(),
+                ))
+            }
+        };
+
+        if let Some(properties) = self.input_schema["properties"].as_object() {
+            for (name, property) in properties {
+                match input.get(name) {
+                    None | Some(Value::Null) => {
+                        if let Some(default) = property.get("default") {
+                            input.insert(name.clone(), default.clone());
+                        }
+                    }
+                    Some(value) => {
+                        if let Some(expected) = property["type"].as_str() {
+                            if !has_type(value, expected) {
+                                return Err(WorkflowError::InvalidInput(format!(
+                                    "invalid type for `{}`: expected {}",
+                                    name, expected
+                                )));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(required) = self.input_schema["required"].as_array() {
+            for name in required.iter().filter_map(Value::as_str) {
+                if input.get(name).is_none_or(Value::is_null) {
+                    return Err(WorkflowError::InvalidInput(format!(
+                        "missing field `{}`",
+                        name
+                    )));
+                }
+            }
+        }
+
+        Ok(Value::Object(input))
+    }
+}
+
+/// One step of a workflow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepDefinition {
+    /// Step ID, unique within the workflow.
+    pub id: String,
+
+    /// Name of the operation to call, e.g. `verity.create_document`.
+    pub operation: String,
+
+    /// Template of the operation input.
+    ///
+    /// Top-level fields that render to null are left out, so the operation's
+    /// own defaults apply.
+    #[serde(default)]
+    pub input: Value,
+
+    /// Steps that must finish first, in addition to those the step's
+    /// templates reference.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Condition for running the step; a skipped step's output is null.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+
+    /// Template of a list to run the operation over, once per element.
+    ///
+    /// The element is available as `item`, and the step's output is the list
+    /// of results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub for_each: Option<String>,
+
+    /// What to do when the operation fails.
+    #[serde(default)]
+    pub on_error: OnError,
+
+    /// Prefix of the workflow error when the step fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+
+    /// Action that undoes the step's side effect if the workflow fails later.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compensate: Option<Compensation>,
+}
+
+impl StepDefinition {
+    /// Create a step calling `operation`.
+    pub fn new(id: impl Into<String>, operation: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            operation: operation.into(),
+            input: Value::Null,
+            depends_on: Vec::new(),
+            when: None,
+            for_each: None,
+            on_error: OnError::default(),
+            error_message: None,
+            compensate: None,
+        }
+    }
+
+    /// Set the input template.
+    pub fn with_input(mut self, input: Value) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Run after another step.
+    pub fn with_dependency(mut self, step: impl Into<String>) -> Self {
+        self.depends_on.push(step.into());
+        self
+    }
+
+    /// Run only when `condition` holds.
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.when = Some(condition);
+        self
+    }
+
+    /// Run once per element of the list `items` renders to.
+    pub fn with_for_each(mut self, items: impl Into<String>) -> Self {
+        self.for_each = Some(items.into());
+        self
+    }
+
+    /// Set what to do when the operation fails.
+    pub fn with_on_error(mut self, on_error: OnError) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Set the prefix of the workflow error when the step fails.
+    pub fn with_error_message(mut self, message: impl Into<String>) -> Self {
+        self.error_message = Some(message.into());
+        self
+    }
+
+    /// Undo the step with `compensation` if the workflow fails later.
+    pub fn with_compensation(mut self, compensation: Compensation) -> Self {
+        self.compensate = Some(compensation);
+        self
+    }
+
+    /// Get the IDs of the steps this step waits for.
+    pub fn dependencies(&self) -> Result<BTreeSet<String>, WorkflowError> {
+        let mut steps: BTreeSet<String> = self.depends_on.iter().cloned().collect();
+        template::references(&self.input, &mut steps)?;
+        if let Some(ref items) = self.for_each {
+            template::references(&Value::String(items.clone()), &mut steps)?;
+        }
+        if let Some(ref condition) = self.when {
+            condition.references(&mut steps)?;
+        }
+        if let Some(ref compensation) = self.compensate {
+            template::references(&compensation.input, &mut steps)?;
+        }
+        Ok(steps)
+    }
+}
+
+/// Compensating action of a step.
+///
+/// When a workflow fails, the compensations of the steps that succeeded run
+/// in reverse order of completion. For a `for_each` step, the compensation
+/// runs once per successful element.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Compensation {
+    /// Name of the operation to call, e.g. `verity.delete_document`.
+    pub operation: String,
+
+    /// Template of the operation input.
+    ///
+    /// Besides the step's own scope, `output` holds the step's output (or the
+    /// element's output for a `for_each` step).
+    #[serde(default)]
+    pub input: Value,
+}
+
+impl Compensation {
+    /// Create a compensation calling `operation`.
+    pub fn new(operation: impl Into<String>) -> Self {
+        Self {
+            operation: operation.into(),
+            input: Value::Null,
+        }
+    }
+
+    /// Set the input template.
+    pub fn with_input(mut self, input: Value) -> Self {
+        self.input = input;
+        self
+    }
+}
+
+/// Condition on templated values.
+///
+/// In YAML: `when: {exists: "${input.decision_id}"}`, or
+/// `when: {all: [{truthy: "${input.notify}"}, {not: {empty: "${steps.items}"}}]}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    /// The value is true, a non-zero number, or a non-empty string, list or
+    /// object.
+    Truthy(Value),
+
+    /// The value is not null.
+    Exists(Value),
+
+    /// The value is null or an empty string, list or object.
+    Empty(Value),
+
+    /// The two values are equal.
+    Equals(Value, Value),
+
+    /// The condition does not hold.
+    Not(Box<Condition>),
+
+    /// All conditions hold.
+    All(Vec<Condition>),
+
+    /// At least one condition holds.
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// Evaluate the condition against a scope.
+    pub(crate) fn evaluate(&self, scope: &Value) -> Result<bool, WorkflowError> {
+        Ok(match self {
+            Condition::Truthy(value) => template::is_truthy(&template::render(value, scope)?),
+            Condition::Exists(value) => !template::render(value, scope)?.is_null(),
+            Condition::Empty(value) => template::is_empty(&template::render(value, scope)?),
+            Condition::Equals(left, right) => {
+                template::render(left, scope)? == template::render(right, scope)?
+            }
+            Condition::Not(condition) => !condition.evaluate(scope)?,
+            Condition::All(conditions) => {
+                for condition in conditions {
+                    if !condition.evaluate(scope)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            Condition::Any(conditions) => {
+                for condition in conditions {
+                    if condition.evaluate(scope)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+        })
+    }
+
+    pub(crate) fn references(&self, steps: &mut BTreeSet<String>) -> Result<(), WorkflowError> {
+        match self {
+            Condition::Truthy(value) | Condition::Exists(value) | Condition::Empty(value) => {
+                template::references(value, steps)
+            }
+            Condition::Equals(left, right) => {
+                template::references(left, steps)?;
+                template::references(right, steps)
+            }
+            Condition::Not(condition) => condition.references(steps),
+            Condition::All(conditions) | Condition::Any(conditions) => conditions
+                .iter()
+                .try_for_each(|condition| condition.references(steps)),
+        }
+    }
+}
+
+/// What to do when a step's operation fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnError {
+    /// Fail the workflow.
+    #[default]
+    Fail,
+
+    /// Log the error and continue. The step's output is null, or for a
+    /// `for_each` step, the failed elements are left out.
+    Continue,
+}
+
+fn default_category() -> String {
+    "workflow".to_string()
+}
+
+fn default_input_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {},
+        "required": []
+    })
+}
+
+/// Check a value against a JSON Schema type name.
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_yaml() {
+        let definition = WorkflowDefinition::from_yaml(
+            r#"
+name: example
+description: Example workflow
+steps:
+  - id: fetch
+    operation: noteman.get_meeting
+    input:
+      meeting_id: ${input.meeting_id}
+  - id: notify
+    operation: core.value
+    when: {all: [{exists: "${steps.fetch}"}, {not: {empty: "${input.emails}"}}]}
+    for_each: ${input.emails}
+    on_error: continue
+output:
+  title: ${steps.fetch.title}
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(definition.category, "workflow");
+        assert_eq!(definition.steps.len(), 2);
+        let notify = definition.step("notify").unwrap();
+        assert_eq!(notify.on_error, OnError::Continue);
+        assert_eq!(
+            notify
+                .dependencies()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["fetch"]
+        );
+        definition.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_graphs() {
+        let step = |id: &str, input: Value| StepDefinition::new(id, "core.value").with_input(input);
+
+        let cycle = WorkflowDefinition::new("cycle", "")
+            .with_step(step("a", json!("${steps.b}")))
+            .with_step(step("b", json!("${steps.a}")));
+        let err = cycle.validate().unwrap_err();
+        assert!(err.to_string().contains("cycle: a, b"), "{}", err);
+
+        let unknown = WorkflowDefinition::new("unknown", "")
+            .with_step(step("a", json!("${steps.missing.id}")));
+        assert!(unknown.validate().is_err());
+
+        let duplicate = WorkflowDefinition::new("duplicate", "")
+            .with_step(step("a", Value::Null))
+            .with_step(step("a", Value::Null));
+        assert!(duplicate.validate().is_err());
+
+        let output = WorkflowDefinition::new("output", "")
+            .with_step(step("a", Value::Null))
+            .with_output(json!({"id": "${steps.b.id}"}));
+        assert!(output.validate().is_err());
+    }
+
+    #[test]
+    fn test_prepare_input() {
+        let definition = WorkflowDefinition::new("inputs", "").with_schema(json!({
+            "type": "object",
+            "properties": {
+                "meeting_id": {"type": "string"},
+                "level": {"type": "string", "default": "standard"}
+            },
+            "required": ["meeting_id"]
+        }));
+
+        assert_eq!(
+            definition
+                .prepare_input(json!({"meeting_id": "mtg-1"}))
+                .unwrap(),
+            json!({"meeting_id": "mtg-1", "level": "standard"})
+        );
+        assert_eq!(
+            definition.prepare_input(json!({})),
+            Err(WorkflowError::InvalidInput(
+                "missing field `meeting_id`".to_string()
+            ))
+        );
+        assert!(definition.prepare_input(json!({"meeting_id": 7})).is_err());
+    }
+}
@@ -0,0 +1,1011 @@
+//! Workflow execution.
+
+use super::definition::{OnError, StepDefinition, WorkflowDefinition};
+use super::operations::{Operation, OperationRegistry, RETURN_OPERATION};
+use super::runs::{
+    CompensationRecord, RunFilter, RunStatus, RunStoreError, StepRecord, StepStatus, WorkflowRun,
+};
+use super::template;
+use crate::clients::registry::ServiceRegistry;
+use crate::idempotency::idempotency_key;
+use crate::server::ToolContext;
+use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+/// Errors from running a workflow.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum WorkflowError {
+    /// The definition is invalid or uses an unknown operation.
+    #[error("Invalid workflow definition: {0}")]
+    InvalidDefinition(String),
+
+    /// The workflow input does not match the input schema.
+    #[error("Invalid workflow input: {0}")]
+    InvalidInput(String),
+
+    /// A step failed.
+    #[error("{message}")]
+    StepFailed {
+        /// ID of the failed step.
+        step: String,
+
+        /// Error message, prefixed with the step's `error_message`.
+        message: String,
+
+        /// Compensations run after the failure, in the order they ran.
+        rollbacks: Vec<Rollback>,
+    },
+
+    /// The run does not exist, has finished or belongs to another workflow.
+    #[error("Cannot resume workflow run: {0}")]
+    NotResumable(String),
+
+    /// The run store failed.
+    #[error(transparent)]
+    Store(#[from] RunStoreError),
+}
+
+/// Result of compensating one side effect of a failed workflow.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rollback {
+    /// ID of the step whose side effect was compensated.
+    pub step: String,
+
+    /// Compensating operation.
+    pub operation: String,
+
+    /// Error message if the compensation failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Rollback {
+    /// Check whether the side effect was rolled back.
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Outcome of running one step.
+struct StepRun {
+    /// Step output or error.
+    result: Result<Value, WorkflowError>,
+
+    /// Rendered operation input, or the list of inputs of a `for_each` step.
+    input: Value,
+
+    /// Compensations of the step's successful calls.
+    compensations: Vec<CompensationRecord>,
+}
+
+/// Runs workflow definitions against the service clients.
+#[derive(Debug, Clone)]
+pub struct WorkflowEngine {
+    /// Clients called by the operations.
+    services: Arc<ServiceRegistry>,
+
+    /// Operations available to steps.
+    operations: Arc<OperationRegistry>,
+}
+
+impl WorkflowEngine {
+    /// Create an engine with the built-in operations.
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        Self {
+            services,
+            operations: Arc::new(OperationRegistry::builtin()),
+        }
+    }
+
+    /// Use a different set of operations.
+    pub fn with_operations(mut self, operations: OperationRegistry) -> Self {
+        self.operations = Arc::new(operations);
+        self
+    }
+
+    /// Add or replace an operation.
+    pub fn with_operation(
+        mut self,
+        name: impl Into<String>,
+        operation: Arc<dyn Operation>,
+    ) -> Self {
+        self.operations = Arc::new((*self.operations).clone().with_operation(name, operation));
+        self
+    }
+
+    /// Get the service registry.
+    pub fn services(&self) -> &Arc<ServiceRegistry> {
+        &self.services
+    }
+
+    /// Validate a definition, including that its operations exist.
+    pub fn validate(&self, definition: &WorkflowDefinition) -> Result<(), WorkflowError> {
+        definition.validate()?;
+        for step in &definition.steps {
+            let compensation = step.compensate.as_ref().map(|c| c.operation.as_str());
+            for operation in std::iter::once(step.operation.as_str()).chain(compensation) {
+                if self.operations.get(operation).is_none() {
+                    return Err(WorkflowError::InvalidDefinition(format!(
+                        "{}: step '{}' uses unknown operation '{}'",
+                        definition.name, step.id, operation
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run a workflow and return its rendered output.
+    ///
+    /// Steps start as soon as the steps they depend on have finished. If a
+    /// step fails, no further steps start; once the running ones finish, the
+    /// compensations of all successful steps run in reverse order.
+    ///
+    /// The run is recorded in the registry's run store (see
+    /// [`ServiceRegistry::runs`]) after every step, so it can be resumed with
+    /// [`resume`](Self::resume) if the process stops. A run that cannot be
+    /// recorded is not started.
+    pub async fn run(
+        &self,
+        definition: &WorkflowDefinition,
+        input: Value,
+        context: &ToolContext,
+    ) -> Result<Value, WorkflowError> {
+        self.validate(definition)?;
+        let input = definition.prepare_input(input)?;
+
+        let mut run = WorkflowRun::new(&definition.name, input, context);
+        self.save(&mut run).await?;
+        info!(workflow = %definition.name, run_id = %run.id, "Starting workflow");
+        self.execute(definition, &mut run, context).await
+    }
+
+    /// Resume an interrupted run of `definition` from its last finished step.
+    ///
+    /// Finished steps are not run again. Steps that were running when the run
+    /// was interrupted run again with the same idempotency keys, so services
+    /// can discard duplicate calls.
+    pub async fn resume(
+        &self,
+        definition: &WorkflowDefinition,
+        run_id: &str,
+    ) -> Result<Value, WorkflowError> {
+        self.validate(definition)?;
+        let mut run = self
+            .services
+            .runs()
+            .get(run_id)
+            .await?
+            .ok_or_else(|| WorkflowError::NotResumable(format!("Run {} not found", run_id)))?;
+        if run.workflow != definition.name {
+            return Err(WorkflowError::NotResumable(format!(
+                "Run {} is a run of {}, not {}",
+                run_id, run.workflow, definition.name
+            )));
+        }
+        if run.is_finished() {
+            return Err(WorkflowError::NotResumable(format!(
+                "Run {} has already finished",
+                run_id
+            )));
+        }
+
+        info!(workflow = %definition.name, run_id = %run.id, finished_steps = run.steps.len(), "Resuming workflow");
+        let context = run.context.to_tool_context();
+        self.execute(definition, &mut run, &context).await
+    }
+
+    /// Resume every interrupted run of one of `definitions`, returning the
+    /// result of each run by ID.
+    ///
+    /// Call this once at startup, before serving requests. If several
+    /// processes share a run store, only one of them may resume runs.
+    pub async fn resume_interrupted(
+        &self,
+        definitions: &[WorkflowDefinition],
+    ) -> Result<Vec<(String, Result<Value, WorkflowError>)>, WorkflowError> {
+        let runs = self
+            .services
+            .runs()
+            .list(&RunFilter::new().with_status(RunStatus::Running))
+            .await?;
+
+        let resumes = runs.into_iter().filter_map(|run| {
+            let Some(definition) = definitions.iter().find(|d| d.name == run.workflow) else {
+                warn!(workflow = %run.workflow, run_id = %run.id, "No definition for interrupted run");
+                return None;
+            };
+            Some(async move {
+                let result = self.resume(definition, &run.id).await;
+                (run.id, result)
+            })
+        });
+        Ok(futures::future::join_all(resumes).await)
+    }
+
+    /// Run the unfinished steps of a run.
+    async fn execute(
+        &self,
+        definition: &WorkflowDefinition,
+        run: &mut WorkflowRun,
+        context: &ToolContext,
+    ) -> Result<Value, WorkflowError> {
+        let mut dependencies: HashMap<&str, BTreeSet<String>> = HashMap::new();
+        for step in &definition.steps {
+            dependencies.insert(step.id.as_str(), step.dependencies()?);
+        }
+
+        // Steps that already finished keep their outputs. A recorded failure
+        // of a step that does not continue on error fails the run again.
+        let input = run.input.clone();
+        let mut outputs: Map<String, Value> = Map::new();
+        let mut failure: Option<WorkflowError> = None;
+        for record in &run.steps {
+            outputs.insert(
+                record.step.clone(),
+                match record.status {
+                    StepStatus::Completed => record.output.clone(),
+                    StepStatus::Skipped | StepStatus::Failed => Value::Null,
+                },
+            );
+            let fatal = definition
+                .step(&record.step)
+                .is_some_and(|step| step.on_error == OnError::Fail);
+            if record.status == StepStatus::Failed && fatal && failure.is_none() {
+                failure = Some(WorkflowError::StepFailed {
+                    step: record.step.clone(),
+                    message: record.error.clone().unwrap_or_default(),
+                    rollbacks: Vec::new(),
+                });
+            }
+        }
+
+        let mut pending: Vec<&StepDefinition> = definition
+            .steps
+            .iter()
+            .filter(|step| !outputs.contains_key(&step.id))
+            .collect();
+        let mut running = FuturesUnordered::new();
+
+        loop {
+            // Start every step whose dependencies have finished. Skipping a
+            // step can make others ready, so repeat until nothing changes.
+            let mut started = true;
+            while started && failure.is_none() {
+                started = false;
+                let mut i = 0;
+                while i < pending.len() {
+                    let step = pending[i];
+                    if !dependencies[step.id.as_str()]
+                        .iter()
+                        .all(|dep| outputs.contains_key(dep))
+                    {
+                        i += 1;
+                        continue;
+                    }
+                    pending.remove(i);
+                    started = true;
+
+                    let scope = scope(&input, &outputs, context);
+                    if let Some(ref condition) = step.when {
+                        match condition.evaluate(&scope) {
+                            Ok(true) => {}
+                            Ok(false) => {
+                                debug!(workflow = %definition.name, step = %step.id, "Skipping step");
+                                outputs.insert(step.id.clone(), Value::Null);
+                                run.steps
+                                    .push(StepRecord::new(&step.id, StepStatus::Skipped));
+                                self.checkpoint(run).await;
+                                continue;
+                            }
+                            Err(e) => {
+                                failure = Some(e);
+                                break;
+                            }
+                        }
+                    }
+                    running.push(async move { (step, self.run_step(step, scope, context).await) });
+                }
+            }
+
+            let Some((step, step_run)) = running.next().await else {
+                break;
+            };
+            let mut record = StepRecord::new(&step.id, StepStatus::Completed);
+            record.input = step_run.input;
+            record.compensations = step_run.compensations;
+
+            let mut returned = None;
+            match step_run.result {
+                Ok(output) => {
+                    record.output = output.clone();
+                    // After a failure the step is only recorded, so that its
+                    // side effects are rolled back.
+                    if failure.is_some() {
+                        debug!(workflow = %definition.name, step = %step.id, "Step completed after failure");
+                    } else if step.operation == RETURN_OPERATION {
+                        info!(workflow = %definition.name, step = %step.id, "Workflow returned early");
+                        returned = Some(output);
+                    } else {
+                        debug!(workflow = %definition.name, step = %step.id, "Step completed");
+                        outputs.insert(step.id.clone(), output);
+                    }
+                }
+                Err(e) => {
+                    record.status = StepStatus::Failed;
+                    record.error = Some(match e {
+                        WorkflowError::StepFailed { ref message, .. } => message.clone(),
+                        ref e => e.to_string(),
+                    });
+                    let continues = step.on_error == OnError::Continue
+                        && matches!(e, WorkflowError::StepFailed { .. });
+                    if continues && failure.is_none() {
+                        warn!(workflow = %definition.name, step = %step.id, error = %e, "Step failed, continuing");
+                        outputs.insert(step.id.clone(), Value::Null);
+                    } else if failure.is_none() {
+                        warn!(workflow = %definition.name, step = %step.id, error = %e, "Workflow failed");
+                        failure = Some(e);
+                    }
+                }
+            }
+            run.steps.push(record);
+
+            if let Some(output) = returned {
+                return Ok(self.complete(run, output).await);
+            }
+            self.checkpoint(run).await;
+        }
+
+        if let Some(error) = failure {
+            return Err(self.fail(run, error, context).await);
+        }
+
+        match template::render(&definition.output, &scope(&input, &outputs, context)) {
+            Ok(output) => {
+                info!(workflow = %definition.name, run_id = %run.id, "Workflow completed");
+                Ok(self.complete(run, output).await)
+            }
+            Err(e) => Err(self.fail(run, e, context).await),
+        }
+    }
+
+    /// Record a run as failed with `error` and roll back its side effects.
+    async fn fail(
+        &self,
+        run: &mut WorkflowRun,
+        error: WorkflowError,
+        context: &ToolContext,
+    ) -> WorkflowError {
+        run.status = RunStatus::Failed;
+        run.error = Some(error.to_string());
+        self.checkpoint(run).await;
+
+        let rollbacks = self.roll_back(run, context).await;
+        run.rollbacks = rollbacks.clone();
+        self.checkpoint(run).await;
+        match error {
+            WorkflowError::StepFailed { step, message, .. } => WorkflowError::StepFailed {
+                step,
+                message,
+                rollbacks,
+            },
+            error => error,
+        }
+    }
+
+    /// Record a run as completed with `output`.
+    async fn complete(&self, run: &mut WorkflowRun, output: Value) -> Value {
+        run.status = RunStatus::Completed;
+        run.output = Some(output.clone());
+        self.checkpoint(run).await;
+        output
+    }
+
+    /// Save the current state of a run.
+    async fn save(&self, run: &mut WorkflowRun) -> Result<(), RunStoreError> {
+        run.updated_at = Utc::now();
+        self.services.runs().save(run).await
+    }
+
+    /// Save the current state of a run, logging failures.
+    ///
+    /// The run goes on if it cannot be saved; it just cannot be resumed from
+    /// this point.
+    async fn checkpoint(&self, run: &mut WorkflowRun) {
+        if let Err(e) = self.save(run).await {
+            warn!(workflow = %run.workflow, run_id = %run.id, error = %e, "Failed to record workflow run");
+        }
+    }
+
+    /// Run a step, once per element for a `for_each` step.
+    async fn run_step(
+        &self,
+        step: &StepDefinition,
+        scope: Value,
+        context: &ToolContext,
+    ) -> StepRun {
+        let Some(ref items) = step.for_each else {
+            let mut compensations = Vec::new();
+            let (result, input) = match step_input(&step.input, &scope) {
+                Ok(input) => (
+                    self.call_step(step, scope, input.clone(), context, &mut compensations)
+                        .await,
+                    input,
+                ),
+                Err(e) => (Err(e), Value::Null),
+            };
+            return StepRun {
+                result,
+                input,
+                compensations,
+            };
+        };
+
+        let items = match template::render(&Value::String(items.clone()), &scope) {
+            Ok(Value::Array(items)) => items,
+            Ok(Value::Null) => Vec::new(),
+            Ok(other) => {
+                let error = step_error(step, format!("for_each must be a list, got {}", other));
+                return StepRun {
+                    result: Err(error),
+                    input: Value::Null,
+                    compensations: Vec::new(),
+                };
+            }
+            Err(e) => {
+                return StepRun {
+                    result: Err(e),
+                    input: Value::Null,
+                    compensations: Vec::new(),
+                }
+            }
+        };
+
+        let mut calls = Vec::with_capacity(items.len());
+        for item in items {
+            let mut scope = scope.clone();
+            scope["item"] = item;
+            calls.push(async move {
+                let mut compensations = Vec::new();
+                let (result, input) = match step_input(&step.input, &scope) {
+                    Ok(input) => (
+                        self.call_step(step, scope, input.clone(), context, &mut compensations)
+                            .await,
+                        input,
+                    ),
+                    Err(e) => (Err(e), Value::Null),
+                };
+                (result, input, compensations)
+            });
+        }
+
+        let mut results = Vec::with_capacity(calls.len());
+        let mut inputs = Vec::with_capacity(calls.len());
+        let mut compensations = Vec::new();
+        let mut error = None;
+        for (result, input, item_compensations) in futures::future::join_all(calls).await {
+            inputs.push(input);
+            compensations.extend(item_compensations);
+            match result {
+                Ok(output) => results.push(output),
+                Err(WorkflowError::StepFailed { message, .. })
+                    if step.on_error == OnError::Continue =>
+                {
+                    warn!(step = %step.id, error = %message, "Step item failed, continuing");
+                }
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        StepRun {
+            result: match error {
+                Some(e) => Err(e),
+                None => Ok(Value::Array(results)),
+            },
+            input: Value::Array(inputs),
+            compensations,
+        }
+    }
+
+    /// Call a step's operation, recording how to compensate the side effect
+    /// if the step has a compensation.
+    async fn call_step(
+        &self,
+        step: &StepDefinition,
+        mut scope: Value,
+        input: Value,
+        context: &ToolContext,
+        compensations: &mut Vec<CompensationRecord>,
+    ) -> Result<Value, WorkflowError> {
+        // A compensation that cannot be rendered could not undo the side
+        // effect, so check its template before calling the operation.
+        if let Some(ref compensation) = step.compensate {
+            template::references(&compensation.input, &mut BTreeSet::new())?;
+        }
+
+        debug!(step = %step.id, operation = %step.operation, "Calling operation");
+        let (output, key) = self
+            .call(&step.operation, input, context)
+            .await
+            .map_err(|e| step_error(step, e))?;
+
+        // The side effect happened, so it is recorded even if the
+        // compensation input cannot be rendered; the run then fails and
+        // reports the side effect as not rolled back.
+        if let Some(ref compensation) = step.compensate {
+            scope["output"] = output.clone();
+            let (input, render_error) = match step_input(&compensation.input, &scope) {
+                Ok(input) => (input, None),
+                Err(e) => {
+                    error!(step = %step.id, operation = %compensation.operation, error = %e, "Cannot render compensation input");
+                    (Value::Null, Some(e))
+                }
+            };
+            compensations.push(CompensationRecord {
+                operation: compensation.operation.clone(),
+                input,
+                key,
+                error: render_error.as_ref().map(|e| e.to_string()),
+            });
+            if let Some(e) = render_error {
+                return Err(WorkflowError::InvalidDefinition(format!(
+                    "step '{}' succeeded but cannot be compensated: {}",
+                    step.id, e
+                )));
+            }
+        }
+        Ok(output)
+    }
+
+    /// Call an operation, returning its output and the idempotency key the
+    /// output was recorded under.
+    ///
+    /// Mutating operations run at most once per idempotency key: if the call
+    /// already succeeded for this tool call and input, its recorded result is
+    /// returned without contacting the service.
+    async fn call(
+        &self,
+        name: &str,
+        input: Value,
+        context: &ToolContext,
+    ) -> Result<(Value, Option<String>), String> {
+        let operation = self
+            .operations
+            .get(name)
+            .ok_or_else(|| format!("Unknown operation: {}", name))?;
+
+        let key = operation
+            .mutating()
+            .then(|| idempotency_key(context, name, &input))
+            .flatten();
+        if let Some(ref key) = key {
+            if let Some(recorded) = self.services.idempotency().get(key) {
+                info!("Returning recorded result for {} operation", name);
+                return Ok((recorded, Some(key.clone())));
+            }
+        }
+
+        let output = operation.call(&self.services, context, input).await?;
+        if let Some(ref key) = key {
+            self.services.idempotency().put(key.clone(), output.clone());
+        }
+        Ok((output, key))
+    }
+
+    /// Run the recorded compensations of a run in reverse order.
+    ///
+    /// A rolled-back step's recorded result is forgotten, so that re-running
+    /// the workflow performs the step again.
+    async fn roll_back(&self, run: &WorkflowRun, context: &ToolContext) -> Vec<Rollback> {
+        let compensations: Vec<_> = run
+            .steps
+            .iter()
+            .flat_map(|record| record.compensations.iter().map(move |c| (record, c)))
+            .collect();
+
+        let mut rollbacks = Vec::with_capacity(compensations.len());
+        for (record, compensation) in compensations.into_iter().rev() {
+            let result = match compensation.error {
+                Some(ref e) => Err(e.clone()),
+                None => self
+                    .call(&compensation.operation, compensation.input.clone(), context)
+                    .await
+                    .map(|_| ()),
+            };
+
+            match result {
+                Ok(()) => {
+                    info!(step = %record.step, operation = %compensation.operation, "Rolled back step");
+                    if let Some(ref key) = compensation.key {
+                        self.services.idempotency().remove(key);
+                    }
+                }
+                Err(ref e) => {
+                    warn!(step = %record.step, operation = %compensation.operation, error = %e, "Failed to roll back step");
+                }
+            }
+            rollbacks.push(Rollback {
+                step: record.step.clone(),
+                operation: compensation.operation.clone(),
+                error: result.err(),
+            });
+        }
+        rollbacks
+    }
+}
+
+/// Build the scope templates are rendered against.
+fn scope(input: &Value, outputs: &Map<String, Value>, context: &ToolContext) -> Value {
+    json!({
+        "input": input,
+        "steps": outputs,
+        "context": {
+            "user_id": context.user_id,
+            "user_email": context.user_email,
+            "org_id": context.org_id,
+            "project_id": context.project_id,
+            "request_id": context.request_id,
+            "correlation_id": context.correlation_id,
+        }
+    })
+}
+
+/// Render a step input, leaving out top-level fields that render to null.
+fn step_input(template: &Value, scope: &Value) -> Result<Value, WorkflowError> {
+    Ok(match template::render(template, scope)? {
+        Value::Object(fields) => {
+            Value::Object(fields.into_iter().filter(|(_, v)| !v.is_null()).collect())
+        }
+        Value::Null => Value::Object(Map::new()),
+        other => other,
+    })
+}
+
+fn step_error(step: &StepDefinition, error: String) -> WorkflowError {
+    WorkflowError::StepFailed {
+        step: step.id.clone(),
+        message: match step.error_message {
+            Some(ref prefix) => format!("{}: {}", prefix, error),
+            None => error,
+        },
+        rollbacks: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::definition::{Compensation, Condition};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Records its calls, sleeping for `input.delay_ms` first.
+    #[derive(Default)]
+    struct Recorder {
+        calls: Mutex<Vec<Value>>,
+    }
+
+    #[async_trait]
+    impl Operation for Recorder {
+        async fn call(
+            &self,
+            _services: &ServiceRegistry,
+            _context: &ToolContext,
+            input: Value,
+        ) -> Result<Value, String> {
+            if let Some(ms) = input["delay_ms"].as_u64() {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+            }
+            if input["fail"] == true {
+                return Err("boom".to_string());
+            }
+            self.calls.lock().unwrap().push(input.clone());
+            Ok(input)
+        }
+
+        fn mutating(&self) -> bool {
+            true
+        }
+    }
+
+    fn engine(recorder: Arc<Recorder>) -> WorkflowEngine {
+        WorkflowEngine::new(Arc::new(ServiceRegistry::default()))
+            .with_operation("test.record", recorder)
+    }
+
+    #[tokio::test]
+    async fn test_run_passes_outputs_and_branches() {
+        let recorder = Arc::new(Recorder::default());
+        let definition = WorkflowDefinition::new("branching", "")
+            .with_step(
+                StepDefinition::new("first", "test.record")
+                    .with_input(json!({"name": "${input.name}", "delay_ms": 20})),
+            )
+            .with_step(
+                StepDefinition::new("parallel", "test.record").with_input(json!({"name": "p"})),
+            )
+            .with_step(
+                StepDefinition::new("skipped", "test.record")
+                    .with_input(json!({"name": "never"}))
+                    .with_condition(Condition::Truthy(json!("${input.flag}"))),
+            )
+            .with_step(
+                StepDefinition::new("second", "test.record").with_input(json!({
+                    "greeting": "hello ${steps.first.name}",
+                    "skipped": "${steps.skipped}"
+                })),
+            )
+            .with_output(json!({"greeting": "${steps.second.greeting}"}));
+
+        let output = engine(recorder.clone())
+            .run(&definition, json!({"name": "ada"}), &ToolContext::empty())
+            .await
+            .unwrap();
+
+        assert_eq!(output, json!({"greeting": "hello ada"}));
+        let calls = recorder.calls.lock().unwrap();
+        // The independent step finished before the slow first step, and the
+        // null field was left out of the input.
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], json!({"name": "p"}));
+        assert_eq!(calls[2], json!({"greeting": "hello ada"}));
+    }
+
+    #[tokio::test]
+    async fn test_for_each_and_errors() {
+        let recorder = Arc::new(Recorder::default());
+        let fan_out = StepDefinition::new("each", "test.record")
+            .with_for_each("${input.items}")
+            .with_input(json!({"item": "${item.id}", "fail": "${item.fail}"}));
+        let definition = WorkflowDefinition::new("fan_out", "")
+            .with_step(fan_out.clone().with_on_error(OnError::Continue))
+            .with_output(json!({"items": "${steps.each.*.item}"}));
+        let input = json!({"items": [{"id": 1}, {"id": 2, "fail": true}, {"id": 3}]});
+
+        let output = engine(recorder.clone())
+            .run(&definition, input.clone(), &ToolContext::empty())
+            .await
+            .unwrap();
+        assert_eq!(output, json!({"items": [1, 3]}));
+
+        let failing = WorkflowDefinition::new("fan_out", "")
+            .with_step(fan_out.with_error_message("Failed to record"));
+        let err = engine(recorder)
+            .run(&failing, input, &ToolContext::empty())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            WorkflowError::StepFailed {
+                step: "each".to_string(),
+                message: "Failed to record: boom".to_string(),
+                rollbacks: Vec::new()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_return_ends_workflow_and_mutations_are_recorded() {
+        let recorder = Arc::new(Recorder::default());
+        let definition = WorkflowDefinition::new("early", "")
+            .with_step(
+                StepDefinition::new("done", RETURN_OPERATION)
+                    .with_input(json!({"status": "nothing_to_do"}))
+                    .with_condition(Condition::Empty(json!("${input.items}"))),
+            )
+            .with_step(
+                StepDefinition::new("record", "test.record")
+                    .with_input(json!({"items": "${input.items}"}))
+                    .with_dependency("done"),
+            )
+            .with_output(json!({"status": "recorded"}));
+        let engine = engine(recorder.clone());
+        let context = ToolContext {
+            request_id: Some("call-1".to_string()),
+            ..ToolContext::empty()
+        };
+
+        let output = engine.run(&definition, json!({}), &context).await.unwrap();
+        assert_eq!(output, json!({"status": "nothing_to_do"}));
+        assert!(recorder.calls.lock().unwrap().is_empty());
+
+        for _ in 0..2 {
+            let output = engine
+                .run(&definition, json!({"items": [1]}), &context)
+                .await
+                .unwrap();
+            assert_eq!(output, json!({"status": "recorded"}));
+        }
+        assert_eq!(recorder.calls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failure_rolls_back_in_reverse_order() {
+        let recorder = Arc::new(Recorder::default());
+        let undo = Arc::new(Recorder::default());
+        let engine = engine(recorder.clone()).with_operation("test.undo", undo.clone());
+        let step = |id: &str, input: Value| {
+            StepDefinition::new(id, "test.record")
+                .with_input(input)
+                .with_compensation(
+                    Compensation::new("test.undo").with_input(json!({"undo": "${output.name}"})),
+                )
+        };
+        let definition = WorkflowDefinition::new("saga", "")
+            .with_step(step("first", json!({"name": "a"})))
+            .with_step(step("second", json!({"name": "${steps.first.name}b"})))
+            .with_step(
+                StepDefinition::new("last", "test.record")
+                    .with_input(json!({"after": "${steps.second.name}", "fail": true}))
+                    .with_error_message("Failed to finish"),
+            );
+        let context = ToolContext {
+            request_id: Some("call-1".to_string()),
+            ..ToolContext::empty()
+        };
+
+        let err = engine
+            .run(&definition, json!({}), &context)
+            .await
+            .unwrap_err();
+        let WorkflowError::StepFailed {
+            step,
+            message,
+            rollbacks,
+        } = err
+        else {
+            panic!("Expected a step failure");
+        };
+        assert_eq!(step, "last");
+        assert_eq!(message, "Failed to finish: boom");
+        assert_eq!(
+            rollbacks
+                .iter()
+                .map(|r| r.step.as_str())
+                .collect::<Vec<_>>(),
+            vec!["second", "first"]
+        );
+        assert!(rollbacks.iter().all(Rollback::succeeded));
+        assert_eq!(
+            *undo.calls.lock().unwrap(),
+            vec![json!({"undo": "ab"}), json!({"undo": "a"})]
+        );
+
+        let runs = engine
+            .services()
+            .runs()
+            .list(&RunFilter::new())
+            .await
+            .unwrap();
+        assert_eq!(runs[0].status, RunStatus::Failed);
+        assert_eq!(
+            runs[0].step("last").unwrap().error.as_deref(),
+            Some("Failed to finish: boom")
+        );
+        assert_eq!(runs[0].rollbacks, rollbacks);
+
+        // Rolled-back steps run again when the call is retried.
+        engine
+            .run(&definition, json!({}), &context)
+            .await
+            .unwrap_err();
+        assert_eq!(recorder.calls.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_output_failure_rolls_back() {
+        let recorder = Arc::new(Recorder::default());
+        let undo = Arc::new(Recorder::default());
+        let engine = engine(recorder).with_operation("test.undo", undo.clone());
+        // Validation rejects such templates, so run the steps directly.
+        let definition = WorkflowDefinition::new("bad_output", "")
+            .with_step(
+                StepDefinition::new("first", "test.record")
+                    .with_input(json!({"name": "a"}))
+                    .with_compensation(
+                        Compensation::new("test.undo")
+                            .with_input(json!({"undo": "${output.name}"})),
+                    ),
+            )
+            .with_output(json!({"name": "${steps.first.name | reverse}"}));
+        let context = ToolContext::empty();
+        let mut run = WorkflowRun::new(&definition.name, json!({}), &context);
+
+        let err = engine
+            .execute(&definition, &mut run, &context)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WorkflowError::InvalidDefinition(_)));
+        assert_eq!(run.status, RunStatus::Failed);
+        assert_eq!(run.rollbacks.len(), 1);
+        assert_eq!(*undo.calls.lock().unwrap(), vec![json!({"undo": "a"})]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_compensation_prevents_the_call() {
+        let recorder = Arc::new(Recorder::default());
+        let engine = engine(recorder.clone()).with_operation("test.undo", recorder.clone());
+        let step = StepDefinition::new("first", "test.record").with_compensation(
+            Compensation::new("test.undo").with_input(json!({"undo": "${output.name | reverse}"})),
+        );
+        let context = ToolContext::empty();
+        let mut compensations = Vec::new();
+
+        let err = engine
+            .call_step(
+                &step,
+                scope(&json!({}), &Map::new(), &context),
+                json!({"name": "a"}),
+                &context,
+                &mut compensations,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WorkflowError::InvalidDefinition(_)));
+        assert!(recorder.calls.lock().unwrap().is_empty());
+        assert!(compensations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_finished_steps() {
+        let recorder = Arc::new(Recorder::default());
+        let engine = engine(recorder.clone());
+        let definition = WorkflowDefinition::new("resumable", "")
+            .with_step(StepDefinition::new("first", "test.record").with_input(json!({"name": "a"})))
+            .with_step(
+                StepDefinition::new("second", "test.record")
+                    .with_input(json!({"name": "${steps.first.name}b"})),
+            )
+            .with_output(json!({"name": "${steps.second.name}"}));
+
+        // A run interrupted after its first step.
+        let mut interrupted = WorkflowRun::new("resumable", json!({}), &ToolContext::empty());
+        let mut first = StepRecord::new("first", StepStatus::Completed);
+        first.output = json!({"name": "a"});
+        interrupted.steps.push(first);
+        engine.services().runs().save(&interrupted).await.unwrap();
+
+        let results = engine
+            .resume_interrupted(std::slice::from_ref(&definition))
+            .await
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![(interrupted.id.clone(), Ok(json!({"name": "ab"})))]
+        );
+        assert_eq!(*recorder.calls.lock().unwrap(), vec![json!({"name": "ab"})]);
+
+        let run = engine
+            .services()
+            .runs()
+            .get(&interrupted.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.status, RunStatus::Completed);
+        assert_eq!(run.step("second").unwrap().input, json!({"name": "ab"}));
+        assert!(matches!(
+            engine.resume(&definition, &interrupted.id).await,
+            Err(WorkflowError::NotResumable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_operation_is_rejected() {
+        let definition = WorkflowDefinition::new("unknown", "")
+            .with_step(StepDefinition::new("a", "verity.delete_everything"));
+        let engine = WorkflowEngine::new(Arc::new(ServiceRegistry::default()));
+        assert!(matches!(
+            engine.validate(&definition),
+            Err(WorkflowError::InvalidDefinition(_))
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! Declarative cross-app workflows.
+//!
+//! A [`WorkflowDefinition`] describes a workflow as a set of steps. Each step
+//! calls a named [`Operation`], usually a client call such as
+//! `noteman.get_meeting_content` or `verity.create_document`. Step inputs are
+//! templates that reference the workflow input and the outputs of earlier
+//! steps:
+//!
+//! - `${input.meeting_id}`: a field of the workflow input
+//! - `${steps.content.content}`: a field of a step's output
+//! - `${steps.verify.*.verification_id}`: a field of every element of a list
+//! - `${item.path}`: the current element in a `for_each` step
+//! - `${context.org_id}`: a field of the calling [`ToolContext`](crate::ToolContext)
+//! - `${event.payload.meeting_id}`: a field of the triggering event, in
+//!   [automation rules](automation)
+//! - `${input.workspace_id ?? 'default'}`: the first value that is not null
+//! - `${steps.finding.severity | upper}`: a filter (`upper`, `lower`,
+//!   `length`, `exists`)
+//!
+//! A template that is a single `${...}` keeps the referenced value's type.
+//! Otherwise, the values are interpolated into the string.
+//!
+//! Steps form a DAG. A step runs once every step it references (or lists in
+//! `depends_on`) has finished, so independent steps run in parallel. A step
+//! with a `when` condition is skipped when the condition does not hold, and a
+//! step with `for_each` runs its operation once per list element,
+//! concurrently. The `core.return` operation ends the workflow early with its
+//! input as the output.
+//!
+//! A step can declare a `compensate` action that undoes its side effect, such
+//! as deleting a created document (`${output.document_id}` refers to the
+//! step's output). When a workflow fails, the compensations of the steps that
+//! succeeded run in reverse order, and the error lists each [`Rollback`].
+//!
+//! Every run is recorded step by step in the registry's [`RunStore`] (see
+//! [`runs`]), with each step's input, output or error. With a durable store
+//! such as [`FileRunStore`], runs interrupted by a restart are resumed with
+//! [`WorkflowEngine::resume_interrupted`].
+//!
+//! [`Automations`] run workflows when matching events are published on the
+//! platform event bus (see [`automation`]).
+//!
+//! Definitions can be written in YAML or JSON, or built in Rust, and are
+//! exposed as MCP tools with [`WorkflowTool`]:
+//!
+//! ```rust,no_run
+//! use platform_mcp::clients::ServiceRegistry;
+//! use platform_mcp::workflow::{WorkflowDefinition, WorkflowEngine, WorkflowTool};
+//! use std::sync::Arc;
+//!
+//! let definition = WorkflowDefinition::from_yaml(r#"
+//! name: summarize_and_verify
+//! description: Summarize a meeting and verify the summary
+//! permissions: [meeting:read, verification:execute]
+//! input_schema:
+//!   type: object
+//!   properties:
+//!     meeting_id: {type: string}
+//!   required: [meeting_id]
+//! steps:
+//!   - id: summary
+//!     operation: noteman.get_meeting_content
+//!     input:
+//!       meeting_id: ${input.meeting_id}
+//!       content_type: summary
+//!   - id: verification
+//!     operation: verity.verify_content
+//!     input:
+//!       content: ${steps.summary.content}
+//!       external_id: ${input.meeting_id}
+//! output:
+//!   verification_id: ${steps.verification.verification_id}
+//! "#).unwrap();
+//!
+//! let services = Arc::new(ServiceRegistry::from_env());
+//! let tool = WorkflowTool::new(definition, WorkflowEngine::new(services)).unwrap();
+//! ```
+
+pub mod automation;
+pub mod definition;
+pub mod engine;
+pub mod operations;
+pub mod runs;
+mod template;
+pub mod tool;
+
+pub use automation::{AutomationError, AutomationRule, Automations};
+pub use definition::{Compensation, Condition, OnError, StepDefinition, WorkflowDefinition};
+pub use engine::{Rollback, WorkflowEngine, WorkflowError};
+pub use operations::{Operation, OperationRegistry, RETURN_OPERATION};
+pub use runs::{
+    FileRunStore, MemoryRunStore, RunFilter, RunStatus, RunStore, RunStoreError, StepRecord,
+    StepStatus, WorkflowRun,
+};
+pub use tool::WorkflowTool;
@@ -0,0 +1,570 @@
+//! Operations workflow steps can call.
+//!
+//! The built-in operations are the client calls, named
+//! `<app>.<method>` (e.g. `noteman.get_meeting_content`) and taking the
+//! method's parameters as their input, plus these `core` operations:
+//!
+//! | Operation | Input | Output |
+//! |-----------|-------|--------|
+//! | `core.value` | any value | the input |
+//! | `core.find` | `items`, `field`, `value`, optional `default` | first item whose `field` equals `value`, else `default` |
+//! | `core.filter` | `items`, `field`, optional `values` | items whose `field` is in `values`; all items when `values` is empty |
+//! | `core.fail` | `message` | fails the step with `message` |
+//! | `core.return` | any value | ends the workflow with the input as its output |
+
+use crate::clients::registry::ServiceRegistry;
+use crate::clients::{noteman, shipcheck, verity};
+use crate::server::ToolContext;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Operation that ends the workflow with its input as the output.
+pub const RETURN_OPERATION: &str = "core.return";
+
+/// An operation a workflow step can call.
+#[async_trait]
+pub trait Operation: Send + Sync {
+    /// Run the operation.
+    async fn call(
+        &self,
+        services: &ServiceRegistry,
+        context: &ToolContext,
+        input: Value,
+    ) -> Result<Value, String>;
+
+    /// Whether the operation changes state in a service.
+    ///
+    /// Mutating steps run at most once per idempotency key (see
+    /// [`crate::idempotency`]).
+    fn mutating(&self) -> bool {
+        false
+    }
+}
+
+/// Client calls available as operations, and whether each one mutates.
+const CLIENT_OPERATIONS: &[(&str, bool)] = &[
+    ("noteman.transcribe_meeting", false),
+    ("noteman.summarize_meeting", false),
+    ("noteman.extract_action_items", false),
+    ("noteman.search_meetings", false),
+    ("noteman.get_meeting", false),
+    ("noteman.get_meeting_content", false),
+    ("noteman.get_meeting_decisions", false),
+    ("noteman.create_discussion", true),
+    ("noteman.delete_discussion", false),
+    ("shipcheck.analyze_code", false),
+    ("shipcheck.verify_pr", false),
+    ("shipcheck.search_findings", false),
+    ("shipcheck.run_pipeline", false),
+    ("shipcheck.get_repository", false),
+    ("shipcheck.list_repositories", false),
+    ("shipcheck.get_finding", false),
+    ("shipcheck.get_repository_docs", false),
+    ("shipcheck.link_decision", true),
+    ("shipcheck.unlink_decision", false),
+    ("shipcheck.sync_tasks", true),
+    ("verity.verify_document", false),
+    ("verity.extract_assertions", false),
+    ("verity.search_knowledge", false),
+    ("verity.check_propagation", false),
+    ("verity.get_document", false),
+    ("verity.create_document", true),
+    ("verity.delete_document", false),
+    ("verity.verify_content", false),
+    ("verity.get_assertion", false),
+];
+
+/// Built-in `core` operations.
+const CORE_OPERATIONS: &[&str] = &[
+    "core.value",
+    "core.find",
+    "core.filter",
+    "core.fail",
+    RETURN_OPERATION,
+];
+
+/// Named operations available to workflows.
+#[derive(Clone)]
+pub struct OperationRegistry {
+    operations: HashMap<String, Arc<dyn Operation>>,
+}
+
+impl OperationRegistry {
+    /// Create a registry without any operations.
+    pub fn empty() -> Self {
+        Self {
+            operations: HashMap::new(),
+        }
+    }
+
+    /// Create a registry with the built-in client and `core` operations.
+    pub fn builtin() -> Self {
+        let clients = CLIENT_OPERATIONS.iter().map(|(name, mutating)| {
+            let op: Arc<dyn Operation> = Arc::new(ClientOperation {
+                name,
+                mutating: *mutating,
+            });
+            (name.to_string(), op)
+        });
+        let core = CORE_OPERATIONS.iter().map(|name| {
+            let op: Arc<dyn Operation> = Arc::new(CoreOperation { name });
+            (name.to_string(), op)
+        });
+        Self {
+            operations: clients.chain(core).collect(),
+        }
+    }
+
+    /// Add or replace an operation.
+    pub fn with_operation(
+        mut self,
+        name: impl Into<String>,
+        operation: Arc<dyn Operation>,
+    ) -> Self {
+        self.operations.insert(name.into(), operation);
+        self
+    }
+
+    /// Get an operation by name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Operation>> {
+        self.operations.get(name).cloned()
+    }
+
+    /// Get the names of all operations, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.operations.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for OperationRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl std::fmt::Debug for OperationRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OperationRegistry")
+            .field("operations", &self.names())
+            .finish()
+    }
+}
+
+/// A call through one of the service clients.
+struct ClientOperation {
+    name: &'static str,
+    mutating: bool,
+}
+
+#[async_trait]
+impl Operation for ClientOperation {
+    async fn call(
+        &self,
+        services: &ServiceRegistry,
+        context: &ToolContext,
+        input: Value,
+    ) -> Result<Value, String> {
+        match self.name {
+            "noteman.transcribe_meeting" => output(
+                services
+                    .noteman(context)
+                    .transcribe_meeting(parse(input)?)
+                    .await,
+            ),
+            "noteman.summarize_meeting" => output(
+                services
+                    .noteman(context)
+                    .summarize_meeting(parse(input)?)
+                    .await,
+            ),
+            "noteman.extract_action_items" => output(
+                services
+                    .noteman(context)
+                    .extract_action_items(parse(input)?)
+                    .await,
+            ),
+            "noteman.search_meetings" => output(
+                services
+                    .noteman(context)
+                    .search_meetings(parse(input)?)
+                    .await,
+            ),
+            "noteman.get_meeting" => {
+                let input: MeetingInput = parse(input)?;
+                output(
+                    services
+                        .noteman(context)
+                        .get_meeting(&input.meeting_id)
+                        .await,
+                )
+            }
+            "noteman.get_meeting_content" => {
+                let input: MeetingContentInput = parse(input)?;
+                let noteman = services.noteman(context);
+                output(
+                    noteman
+                        .get_meeting_content(&input.meeting_id, &input.content_type)
+                        .await,
+                )
+            }
+            "noteman.get_meeting_decisions" => {
+                let input: MeetingInput = parse(input)?;
+                let noteman = services.noteman(context);
+                output(noteman.get_meeting_decisions(&input.meeting_id).await)
+            }
+            "noteman.create_discussion" => {
+                let params: noteman::CreateDiscussionParams = parse(input)?;
+                output(services.noteman(context).create_discussion(params).await)
+            }
+            "noteman.delete_discussion" => {
+                let input: DiscussionInput = parse(input)?;
+                let noteman = services.noteman(context);
+                output(noteman.delete_discussion(&input.discussion_id).await)
+            }
+            "shipcheck.analyze_code" => output(
+                services
+                    .shipcheck(context)
+                    .analyze_code(parse(input)?)
+                    .await,
+            ),
+            "shipcheck.verify_pr" => {
+                output(services.shipcheck(context).verify_pr(parse(input)?).await)
+            }
+            "shipcheck.search_findings" => output(
+                services
+                    .shipcheck(context)
+                    .search_findings(parse(input)?)
+                    .await,
+            ),
+            "shipcheck.run_pipeline" => output(
+                services
+                    .shipcheck(context)
+                    .run_pipeline(parse(input)?)
+                    .await,
+            ),
+            "shipcheck.get_repository" => {
+                let input: RepositoryInput = parse(input)?;
+                output(
+                    services
+                        .shipcheck(context)
+                        .get_repository(&input.repository_id)
+                        .await,
+                )
+            }
+            "shipcheck.list_repositories" => {
+                let input: ProjectInput = parse(input)?;
+                output(
+                    services
+                        .shipcheck(context)
+                        .list_repositories(&input.project_id)
+                        .await,
+                )
+            }
+            "shipcheck.get_finding" => {
+                let input: FindingInput = parse(input)?;
+                output(
+                    services
+                        .shipcheck(context)
+                        .get_finding(&input.finding_id)
+                        .await,
+                )
+            }
+            "shipcheck.get_repository_docs" => {
+                let input: RepositoryDocsInput = parse(input)?;
+                let shipcheck = services.shipcheck(context);
+                output(
+                    shipcheck
+                        .get_repository_docs(&input.repository_id, &input.paths)
+                        .await,
+                )
+            }
+            "shipcheck.link_decision" => {
+                let params: shipcheck::LinkDecisionParams = parse(input)?;
+                output(services.shipcheck(context).link_decision(params).await)
+            }
+            "shipcheck.unlink_decision" => {
+                let input: LinkInput = parse(input)?;
+                output(
+                    services
+                        .shipcheck(context)
+                        .unlink_decision(&input.link_id)
+                        .await,
+                )
+            }
+            "shipcheck.sync_tasks" => {
+                let params: shipcheck::SyncTasksParams = parse(input)?;
+                output(services.shipcheck(context).sync_tasks(params).await)
+            }
+            "verity.verify_document" => output(
+                services
+                    .verity(context)
+                    .verify_document(parse(input)?)
+                    .await,
+            ),
+            "verity.extract_assertions" => output(
+                services
+                    .verity(context)
+                    .extract_assertions(parse(input)?)
+                    .await,
+            ),
+            "verity.search_knowledge" => output(
+                services
+                    .verity(context)
+                    .search_knowledge(parse(input)?)
+                    .await,
+            ),
+            "verity.check_propagation" => output(
+                services
+                    .verity(context)
+                    .check_propagation(parse(input)?)
+                    .await,
+            ),
+            "verity.get_document" => {
+                let input: DocumentInput = parse(input)?;
+                output(
+                    services
+                        .verity(context)
+                        .get_document(&input.document_id)
+                        .await,
+                )
+            }
+            "verity.create_document" => {
+                let params: verity::CreateDocumentParams = parse(input)?;
+                output(services.verity(context).create_document(params).await)
+            }
+            "verity.delete_document" => {
+                let input: DocumentInput = parse(input)?;
+                output(
+                    services
+                        .verity(context)
+                        .delete_document(&input.document_id)
+                        .await,
+                )
+            }
+            "verity.verify_content" => {
+                let params: verity::VerifyContentParams = parse(input)?;
+                output(services.verity(context).verify_content(params).await)
+            }
+            "verity.get_assertion" => {
+                let input: AssertionInput = parse(input)?;
+                output(
+                    services
+                        .verity(context)
+                        .get_assertion(&input.assertion_id)
+                        .await,
+                )
+            }
+            other => Err(format!("Unknown operation: {}", other)),
+        }
+    }
+
+    fn mutating(&self) -> bool {
+        self.mutating
+    }
+}
+
+/// A built-in `core` operation.
+struct CoreOperation {
+    name: &'static str,
+}
+
+#[async_trait]
+impl Operation for CoreOperation {
+    async fn call(
+        &self,
+        _services: &ServiceRegistry,
+        _context: &ToolContext,
+        input: Value,
+    ) -> Result<Value, String> {
+        match self.name {
+            "core.value" | RETURN_OPERATION => Ok(input),
+            "core.find" => {
+                let input: FindInput = parse(input)?;
+                Ok(input
+                    .items
+                    .into_iter()
+                    .find(|item| item.get(&input.field) == Some(&input.value))
+                    .unwrap_or(input.default))
+            }
+            "core.filter" => {
+                let input: FilterInput = parse(input)?;
+                if input.values.is_empty() {
+                    return Ok(Value::Array(input.items));
+                }
+                Ok(Value::Array(
+                    input
+                        .items
+                        .into_iter()
+                        .filter(|item| {
+                            item.get(&input.field)
+                                .is_some_and(|field| input.values.contains(field))
+                        })
+                        .collect(),
+                ))
+            }
+            "core.fail" => {
+                let input: FailInput = parse(input)?;
+                Err(input.message)
+            }
+            other => Err(format!("Unknown operation: {}", other)),
+        }
+    }
+}
+
+/// Deserialize an operation input.
+fn parse<T: DeserializeOwned>(input: Value) -> Result<T, String> {
+    serde_json::from_value(input).map_err(|e| format!("Invalid input: {}", e))
+}
+
+/// Serialize a client result.
+fn output<T: Serialize, E: std::fmt::Display>(result: Result<T, E>) -> Result<Value, String> {
+    let value = result.map_err(|e| e.to_string())?;
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+#[derive(Deserialize)]
+struct MeetingInput {
+    meeting_id: String,
+}
+
+#[derive(Deserialize)]
+struct MeetingContentInput {
+    meeting_id: String,
+    #[serde(default = "default_content_type")]
+    content_type: String,
+}
+
+fn default_content_type() -> String {
+    "summary".to_string()
+}
+
+#[derive(Deserialize)]
+struct RepositoryInput {
+    repository_id: String,
+}
+
+#[derive(Deserialize)]
+struct ProjectInput {
+    project_id: String,
+}
+
+#[derive(Deserialize)]
+struct RepositoryDocsInput {
+    repository_id: String,
+    #[serde(default)]
+    paths: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct FindingInput {
+    finding_id: String,
+}
+
+#[derive(Deserialize)]
+struct DocumentInput {
+    document_id: String,
+}
+
+#[derive(Deserialize)]
+struct DiscussionInput {
+    discussion_id: String,
+}
+
+#[derive(Deserialize)]
+struct LinkInput {
+    link_id: String,
+}
+
+#[derive(Deserialize)]
+struct AssertionInput {
+    assertion_id: String,
+}
+
+#[derive(Deserialize)]
+struct FindInput {
+    #[serde(default)]
+    items: Vec<Value>,
+    field: String,
+    value: Value,
+    #[serde(default)]
+    default: Value,
+}
+
+#[derive(Deserialize)]
+struct FilterInput {
+    #[serde(default)]
+    items: Vec<Value>,
+    field: String,
+    #[serde(default)]
+    values: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct FailInput {
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_core_operations() {
+        let registry = OperationRegistry::builtin();
+        let services = ServiceRegistry::default();
+        let context = ToolContext::empty();
+        let call = |name: &str, input: Value| {
+            let op = registry.get(name).unwrap();
+            let services = &services;
+            let context = &context;
+            async move { op.call(services, context, input).await }
+        };
+        let items = json!([{"id": "a", "n": 1}, {"id": "b", "n": 2}]);
+
+        let found = call(
+            "core.find",
+            json!({"items": items, "field": "id", "value": "b"}),
+        )
+        .await;
+        assert_eq!(found.unwrap()["n"], 2);
+
+        let fallback = call(
+            "core.find",
+            json!({"items": items, "field": "id", "value": "c", "default": "none"}),
+        )
+        .await;
+        assert_eq!(fallback.unwrap(), "none");
+
+        let filtered = call(
+            "core.filter",
+            json!({"items": items, "field": "id", "values": ["a"]}),
+        )
+        .await;
+        assert_eq!(filtered.unwrap(), json!([{"id": "a", "n": 1}]));
+
+        let all = call("core.filter", json!({"items": items, "field": "id"})).await;
+        assert_eq!(all.unwrap(), items);
+
+        let failed = call("core.fail", json!({"message": "stop"})).await;
+        assert_eq!(failed.unwrap_err(), "stop");
+    }
+
+    #[test]
+    fn test_builtin_operations() {
+        let registry = OperationRegistry::builtin();
+        assert!(registry.get("verity.create_document").unwrap().mutating());
+        assert!(!registry
+            .get("noteman.get_meeting_content")
+            .unwrap()
+            .mutating());
+        assert!(registry.get(RETURN_OPERATION).is_some());
+        assert!(registry.get("verity.delete_everything").is_none());
+    }
+}
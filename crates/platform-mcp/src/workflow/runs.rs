@@ -0,0 +1,712 @@
+//! Durable workflow runs.
+//!
+//! The engine records every run in a [`RunStore`] as it progresses: the
+//! workflow input and caller, and for each finished step its input, output or
+//! error. A run that was still [`RunStatus::Running`] when the process stopped
+//! can be resumed from its last finished step with
+//! [`WorkflowEngine::resume`](super::WorkflowEngine::resume).
+//!
+//! Runs are kept in memory by default ([`MemoryRunStore`]). Use a
+//! [`FileRunStore`] so that runs survive a restart. Nothing resumes runs on
+//! its own: the embedding application calls
+//! [`resume_workflow_runs`](crate::tools::resume_workflow_runs) (or
+//! [`WorkflowEngine::resume_interrupted`](super::WorkflowEngine::resume_interrupted))
+//! once at startup.
+
+use super::engine::Rollback;
+use crate::server::ToolContext;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Default number of finished runs a [`MemoryRunStore`] or [`FileRunStore`]
+/// keeps.
+pub const DEFAULT_RUN_CAPACITY: usize = 1000;
+
+/// Errors from reading or writing workflow runs.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RunStoreError {
+    /// The store could not be read or written.
+    #[error("Run store I/O error: {0}")]
+    Io(String),
+
+    /// A stored run could not be encoded or decoded.
+    #[error("Invalid stored run: {0}")]
+    Serialization(String),
+}
+
+impl From<std::io::Error> for RunStoreError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for RunStoreError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e.to_string())
+    }
+}
+
+/// Status of a workflow run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// The run has not finished, or was interrupted.
+    Running,
+
+    /// The run finished and produced its output.
+    Completed,
+
+    /// A step failed and the run was rolled back.
+    Failed,
+}
+
+/// Status of a finished step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    /// The step's operation succeeded.
+    Completed,
+
+    /// The step's condition did not hold.
+    Skipped,
+
+    /// The step's operation failed.
+    Failed,
+}
+
+/// A compensation to run if the workflow fails after a step succeeded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompensationRecord {
+    /// Compensating operation.
+    pub operation: String,
+
+    /// Rendered input of the compensating operation.
+    pub input: Value,
+
+    /// Idempotency key the step's result was recorded under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+
+    /// Why the compensation cannot run, if its input could not be rendered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Record of a finished step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepRecord {
+    /// Step ID.
+    pub step: String,
+
+    /// How the step finished.
+    pub status: StepStatus,
+
+    /// Rendered operation input, or the list of inputs of a `for_each` step.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub input: Value,
+
+    /// Step output.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub output: Value,
+
+    /// Error message if the step failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Compensations of the step's side effects, in the order they happened.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compensations: Vec<CompensationRecord>,
+
+    /// When the step finished.
+    pub finished_at: DateTime<Utc>,
+}
+
+impl StepRecord {
+    /// Create a record of a step that finished now.
+    pub fn new(step: impl Into<String>, status: StepStatus) -> Self {
+        Self {
+            step: step.into(),
+            status,
+            input: Value::Null,
+            output: Value::Null,
+            error: None,
+            compensations: Vec::new(),
+            finished_at: Utc::now(),
+        }
+    }
+}
+
+/// The caller of a workflow run, as needed to resume it.
+///
+/// Credentials such as the caller's API key are not stored; a resumed run
+/// calls services with the configured service credentials.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunContext {
+    /// User ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+
+    /// User email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_email: Option<String>,
+
+    /// Organization ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+
+    /// Project ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+
+    /// User permissions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<String>,
+
+    /// ID of the tool call, so resumed steps keep their idempotency keys.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+
+    /// Request correlation ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl From<&ToolContext> for RunContext {
+    fn from(context: &ToolContext) -> Self {
+        Self {
+            user_id: context.user_id,
+            user_email: context.user_email.clone(),
+            org_id: context.org_id,
+            project_id: context.project_id,
+            permissions: context.permissions.clone(),
+            request_id: context.request_id.clone(),
+            correlation_id: context.correlation_id.clone(),
+        }
+    }
+}
+
+impl RunContext {
+    /// Build the tool context a resumed run executes with.
+    pub fn to_tool_context(&self) -> ToolContext {
+        ToolContext {
+            user_id: self.user_id,
+            user_email: self.user_email.clone(),
+            org_id: self.org_id,
+            project_id: self.project_id,
+            permissions: self.permissions.clone(),
+            request_id: self.request_id.clone(),
+            correlation_id: self.correlation_id.clone(),
+            ..ToolContext::empty()
+        }
+    }
+}
+
+/// A recorded workflow run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    /// Run ID.
+    pub id: String,
+
+    /// Name of the workflow.
+    pub workflow: String,
+
+    /// Run status.
+    pub status: RunStatus,
+
+    /// Workflow input, with schema defaults applied.
+    pub input: Value,
+
+    /// Caller of the run.
+    pub context: RunContext,
+
+    /// Finished steps, in the order they finished.
+    #[serde(default)]
+    pub steps: Vec<StepRecord>,
+
+    /// Workflow output, once completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+
+    /// Error message, once failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Compensations run after the failure.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollbacks: Vec<Rollback>,
+
+    /// When the run started.
+    pub started_at: DateTime<Utc>,
+
+    /// When the run was last updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WorkflowRun {
+    /// Create a running workflow run with a new ID.
+    pub fn new(workflow: impl Into<String>, input: Value, context: &ToolContext) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            workflow: workflow.into(),
+            status: RunStatus::Running,
+            input,
+            context: context.into(),
+            steps: Vec::new(),
+            output: None,
+            error: None,
+            rollbacks: Vec::new(),
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Get the record of a finished step.
+    pub fn step(&self, id: &str) -> Option<&StepRecord> {
+        self.steps.iter().find(|record| record.step == id)
+    }
+
+    /// Check whether the run has finished.
+    pub fn is_finished(&self) -> bool {
+        self.status != RunStatus::Running
+    }
+}
+
+/// Criteria for listing runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunFilter {
+    /// Only runs of this workflow.
+    pub workflow: Option<String>,
+
+    /// Only runs with this status.
+    pub status: Option<RunStatus>,
+
+    /// Only runs started by this organization.
+    pub org_id: Option<Uuid>,
+
+    /// Maximum number of runs to return.
+    pub limit: Option<usize>,
+}
+
+impl RunFilter {
+    /// Create a filter matching every run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match runs of `workflow`.
+    pub fn with_workflow(mut self, workflow: impl Into<String>) -> Self {
+        self.workflow = Some(workflow.into());
+        self
+    }
+
+    /// Only match runs with `status`.
+    pub fn with_status(mut self, status: RunStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only match runs started by `org_id`.
+    pub fn with_org(mut self, org_id: Uuid) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    /// Return at most `limit` runs.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Check whether a run matches the filter, ignoring the limit.
+    pub fn matches(&self, run: &WorkflowRun) -> bool {
+        self.workflow.as_ref().is_none_or(|w| *w == run.workflow)
+            && self.status.is_none_or(|s| s == run.status)
+            && self.org_id.is_none_or(|o| Some(o) == run.context.org_id)
+    }
+
+    /// Keep the matching runs, newest first, up to the limit.
+    fn apply(&self, runs: impl IntoIterator<Item = WorkflowRun>) -> Vec<WorkflowRun> {
+        let mut runs: Vec<_> = runs.into_iter().filter(|run| self.matches(run)).collect();
+        runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+        if let Some(limit) = self.limit {
+            runs.truncate(limit);
+        }
+        runs
+    }
+}
+
+/// Stores workflow runs.
+///
+/// The engine saves a run after every step, so implementations should make
+/// `save` cheap and atomic: a run read back after a crash is either the
+/// previous or the new version.
+#[async_trait]
+pub trait RunStore: Send + Sync {
+    /// Insert or replace a run.
+    async fn save(&self, run: &WorkflowRun) -> Result<(), RunStoreError>;
+
+    /// Get a run by ID.
+    async fn get(&self, id: &str) -> Result<Option<WorkflowRun>, RunStoreError>;
+
+    /// List the runs matching `filter`, newest first.
+    async fn list(&self, filter: &RunFilter) -> Result<Vec<WorkflowRun>, RunStoreError>;
+}
+
+/// Insert `run` into `runs`, then drop the oldest finished runs beyond
+/// `capacity`, returning the IDs of the dropped runs.
+fn insert_run(
+    runs: &mut HashMap<String, WorkflowRun>,
+    run: &WorkflowRun,
+    capacity: usize,
+) -> Vec<String> {
+    runs.insert(run.id.clone(), run.clone());
+
+    let mut finished: Vec<_> = runs
+        .values()
+        .filter(|run| run.is_finished())
+        .map(|run| (run.updated_at, run.id.clone()))
+        .collect();
+    if finished.len() <= capacity {
+        return Vec::new();
+    }
+    finished.sort();
+    finished.truncate(finished.len() - capacity);
+    finished
+        .into_iter()
+        .map(|(_, id)| {
+            runs.remove(&id);
+            id
+        })
+        .collect()
+}
+
+/// In-memory run store.
+///
+/// Keeps every running run and up to a fixed number of finished runs,
+/// dropping the oldest finished runs first.
+pub struct MemoryRunStore {
+    /// Maximum number of finished runs kept.
+    capacity: usize,
+
+    /// Runs by ID.
+    runs: Mutex<HashMap<String, WorkflowRun>>,
+}
+
+impl MemoryRunStore {
+    /// Create a store keeping [`DEFAULT_RUN_CAPACITY`] finished runs.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_RUN_CAPACITY)
+    }
+
+    /// Create a store keeping `capacity` finished runs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            runs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the number of stored runs.
+    pub fn len(&self) -> usize {
+        self.runs.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Check whether the store has no runs.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for MemoryRunStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RunStore for MemoryRunStore {
+    async fn save(&self, run: &WorkflowRun) -> Result<(), RunStoreError> {
+        let mut runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        insert_run(&mut runs, run, self.capacity);
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<WorkflowRun>, RunStoreError> {
+        let runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(runs.get(id).cloned())
+    }
+
+    async fn list(&self, filter: &RunFilter) -> Result<Vec<WorkflowRun>, RunStoreError> {
+        let runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(filter.apply(runs.values().cloned()))
+    }
+}
+
+impl std::fmt::Debug for MemoryRunStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryRunStore")
+            .field("capacity", &self.capacity)
+            .field("runs", &self.len())
+            .finish()
+    }
+}
+
+/// Run store keeping one JSON file per run in a directory.
+///
+/// Runs are written to a temporary file and renamed into place, so a crash
+/// mid-write leaves the previous version intact. The runs are read once when
+/// the store is opened, skipping files that cannot be read, and then served
+/// from memory. Like [`MemoryRunStore`], the store keeps every running run
+/// and up to a fixed number of finished runs, deleting the files of the
+/// oldest finished runs first.
+pub struct FileRunStore {
+    /// Directory holding the run files.
+    dir: PathBuf,
+
+    /// Maximum number of finished runs kept.
+    capacity: usize,
+
+    /// Runs by ID.
+    runs: Mutex<HashMap<String, WorkflowRun>>,
+}
+
+impl FileRunStore {
+    /// Open a store in `dir` keeping [`DEFAULT_RUN_CAPACITY`] finished runs,
+    /// creating the directory if needed.
+    pub async fn open(dir: impl Into<PathBuf>) -> Result<Self, RunStoreError> {
+        Self::open_with_capacity(dir, DEFAULT_RUN_CAPACITY).await
+    }
+
+    /// Open a store in `dir` keeping `capacity` finished runs, creating the
+    /// directory if needed.
+    ///
+    /// Finished runs beyond the capacity are deleted.
+    pub async fn open_with_capacity(
+        dir: impl Into<PathBuf>,
+        capacity: usize,
+    ) -> Result<Self, RunStoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).await?;
+
+        let mut runs = HashMap::new();
+        let mut evicted = Vec::new();
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match Self::read(&path).await {
+                    Ok(run) => evicted.extend(insert_run(&mut runs, &run, capacity)),
+                    Err(e) => {
+                        warn!(path = %path.display(), error = %e, "Skipping unreadable workflow run")
+                    }
+                }
+            }
+        }
+
+        let store = Self {
+            dir,
+            capacity,
+            runs: Mutex::new(runs),
+        };
+        store.remove_files(evicted).await;
+        Ok(store)
+    }
+
+    /// Get the directory holding the run files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the number of stored runs.
+    pub fn len(&self) -> usize {
+        self.runs.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Check whether the store has no runs.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the file of a run, if `id` is a valid run ID.
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        // Run IDs are UUIDs; anything else could escape the directory.
+        Uuid::parse_str(id)
+            .ok()
+            .map(|id| self.dir.join(format!("{}.json", id)))
+    }
+
+    async fn read(path: &Path) -> Result<WorkflowRun, RunStoreError> {
+        Ok(serde_json::from_slice(&fs::read(path).await?)?)
+    }
+
+    /// Delete the files of evicted runs, logging failures.
+    async fn remove_files(&self, ids: Vec<String>) {
+        for path in ids.iter().filter_map(|id| self.path(id)) {
+            if let Err(e) = fs::remove_file(&path).await {
+                warn!(path = %path.display(), error = %e, "Failed to delete evicted workflow run");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RunStore for FileRunStore {
+    async fn save(&self, run: &WorkflowRun) -> Result<(), RunStoreError> {
+        let path = self
+            .path(&run.id)
+            .ok_or_else(|| RunStoreError::Io(format!("Invalid run ID: {}", run.id)))?;
+        let tmp = path.with_extension("json.tmp");
+        let mut file = fs::File::create(&tmp).await?;
+        file.write_all(&serde_json::to_vec_pretty(run)?).await?;
+        // Flush the contents before the rename makes them visible.
+        file.sync_all().await?;
+        fs::rename(&tmp, &path).await?;
+
+        let evicted = {
+            let mut runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+            insert_run(&mut runs, run, self.capacity)
+        };
+        self.remove_files(evicted).await;
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<WorkflowRun>, RunStoreError> {
+        let runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(runs.get(id).cloned())
+    }
+
+    async fn list(&self, filter: &RunFilter) -> Result<Vec<WorkflowRun>, RunStoreError> {
+        let runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(filter.apply(runs.values().cloned()))
+    }
+}
+
+impl std::fmt::Debug for FileRunStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileRunStore")
+            .field("dir", &self.dir)
+            .field("capacity", &self.capacity)
+            .field("runs", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn run(workflow: &str, org_id: Option<Uuid>) -> WorkflowRun {
+        let context = ToolContext {
+            org_id,
+            request_id: Some("call-1".to_string()),
+            api_key: Some("secret".to_string()),
+            ..ToolContext::empty()
+        };
+        WorkflowRun::new(workflow, json!({"meeting_id": "mtg-1"}), &context)
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_filters_and_evicts_finished_runs() {
+        let store = MemoryRunStore::with_capacity(1);
+        let org = Uuid::new_v4();
+        let running = run("sync", Some(org));
+        let mut first = run("sync", None);
+        first.status = RunStatus::Completed;
+        let mut second = run("verify", Some(org));
+        second.status = RunStatus::Failed;
+        second.updated_at = first.updated_at + chrono::Duration::seconds(1);
+
+        for run in [&running, &first, &second] {
+            store.save(run).await.unwrap();
+        }
+
+        // The oldest finished run was dropped; the running one is kept.
+        assert_eq!(store.len(), 2);
+        assert!(store.get(&first.id).await.unwrap().is_none());
+        let ids = |filter: RunFilter| {
+            let store = &store;
+            async move {
+                store
+                    .list(&filter)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|run| run.id)
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(
+            ids(RunFilter::new().with_status(RunStatus::Running)).await,
+            vec![running.id.clone()]
+        );
+        assert_eq!(
+            ids(RunFilter::new().with_workflow("verify")).await,
+            vec![second.id.clone()]
+        );
+        assert_eq!(ids(RunFilter::new().with_org(org)).await.len(), 2);
+        assert_eq!(ids(RunFilter::new().with_limit(1)).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_round_trips_runs() {
+        let dir = std::env::temp_dir().join(format!("workflow-runs-{}", Uuid::new_v4()));
+        let store = FileRunStore::open(&dir).await.unwrap();
+
+        let mut saved = run("sync", None);
+        let mut step = StepRecord::new("fetch", StepStatus::Completed);
+        step.output = json!({"items": []});
+        saved.steps.push(step);
+        store.save(&saved).await.unwrap();
+
+        // A corrupt file does not hide the other runs.
+        std::fs::write(dir.join(format!("{}.json", Uuid::new_v4())), b"{\"id\":").unwrap();
+
+        let reopened = FileRunStore::open(&dir).await.unwrap();
+        let loaded = reopened.get(&saved.id).await.unwrap().unwrap();
+        assert_eq!(loaded, saved);
+        assert_eq!(
+            loaded.context.to_tool_context().request_id.as_deref(),
+            Some("call-1")
+        );
+        // Credentials are never written to disk.
+        assert!(loaded.context.to_tool_context().api_key.is_none());
+        assert_eq!(reopened.list(&RunFilter::new()).await.unwrap().len(), 1);
+        assert!(reopened.get("../etc/passwd").await.unwrap().is_none());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_store_deletes_evicted_runs() {
+        let dir = std::env::temp_dir().join(format!("workflow-runs-{}", Uuid::new_v4()));
+        let store = FileRunStore::open_with_capacity(&dir, 1).await.unwrap();
+        let mut first = run("sync", None);
+        first.status = RunStatus::Completed;
+        let mut second = run("sync", None);
+        second.status = RunStatus::Completed;
+        second.updated_at = first.updated_at + chrono::Duration::seconds(1);
+
+        store.save(&first).await.unwrap();
+        store.save(&second).await.unwrap();
+
+        assert!(store.get(&first.id).await.unwrap().is_none());
+        assert!(!dir.join(format!("{}.json", first.id)).exists());
+        let reopened = FileRunStore::open(&dir).await.unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert!(reopened.get(&second.id).await.unwrap().is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
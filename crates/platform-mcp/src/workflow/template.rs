@@ -0,0 +1,327 @@
+//! Template rendering for workflow step inputs and outputs.
+//!
+//! See the [module documentation](super) for the template syntax.
+
+use super::engine::WorkflowError;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Names a path may start with.
+const ROOTS: &[&str] = &["input", "steps", "item", "context", "output", "event"];
+
+/// Part of a template string.
+enum Segment<'a> {
+    /// Literal text.
+    Text(&'a str),
+
+    /// Expression inside `${...}`.
+    Expression(&'a str),
+}
+
+/// A parsed `${...}` expression.
+struct Expression {
+    /// Alternatives separated by `??`.
+    terms: Vec<Term>,
+
+    /// Filters applied to the first non-null alternative.
+    filters: Vec<Filter>,
+}
+
+/// One alternative of an expression.
+enum Term {
+    /// JSON literal or single-quoted string.
+    Literal(Value),
+
+    /// Path into the scope, split on `.`.
+    Path(Vec<String>),
+}
+
+/// A filter applied with `|`.
+#[derive(Clone, Copy)]
+enum Filter {
+    Upper,
+    Lower,
+    Length,
+    Exists,
+}
+
+/// Render a template against a scope.
+///
+/// Strings are rendered as described in the module documentation; arrays and
+/// objects are rendered element by element.
+pub(crate) fn render(template: &Value, scope: &Value) -> Result<Value, WorkflowError> {
+    match template {
+        Value::String(s) => render_str(s, scope),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render(item, scope))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), render(value, scope)?)))
+            .collect::<Result<serde_json::Map<_, _>, WorkflowError>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Collect the IDs of the steps a template references.
+///
+/// Also checks that every expression in the template parses.
+pub(crate) fn references(
+    template: &Value,
+    steps: &mut BTreeSet<String>,
+) -> Result<(), WorkflowError> {
+    match template {
+        Value::String(s) => {
+            for segment in segments(s)? {
+                let Segment::Expression(expr) = segment else {
+                    continue;
+                };
+                for term in parse(expr)?.terms {
+                    if let Term::Path(path) = term {
+                        if path[0] == "steps" && path.len() > 1 {
+                            steps.insert(path[1].clone());
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        Value::Array(items) => items.iter().try_for_each(|item| references(item, steps)),
+        Value::Object(fields) => fields
+            .values()
+            .try_for_each(|value| references(value, steps)),
+        _ => Ok(()),
+    }
+}
+
+/// Check whether a value counts as true in a condition.
+pub(crate) fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        _ => !is_empty(value),
+    }
+}
+
+/// Check whether a value is null or an empty string, list or object.
+pub(crate) fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+        _ => false,
+    }
+}
+
+fn render_str(s: &str, scope: &Value) -> Result<Value, WorkflowError> {
+    let segments = segments(s)?;
+    if let [Segment::Expression(expr)] = segments.as_slice() {
+        return evaluate(expr, scope);
+    }
+
+    let mut rendered = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => rendered.push_str(text),
+            Segment::Expression(expr) => match evaluate(expr, scope)? {
+                Value::Null => {}
+                Value::String(value) => rendered.push_str(&value),
+                value => rendered.push_str(&value.to_string()),
+            },
+        }
+    }
+    Ok(Value::String(rendered))
+}
+
+/// Split a template string into text and `${...}` expressions.
+fn segments(s: &str) -> Result<Vec<Segment<'_>>, WorkflowError> {
+    let mut segments = Vec::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        if start > 0 {
+            segments.push(Segment::Text(&rest[..start]));
+        }
+        let Some(len) = rest[start + 2..].find('}') else {
+            return Err(WorkflowError::InvalidDefinition(format!(
+                "Unclosed expression in template: {}",
+                s
+            )));
+        };
+        segments.push(Segment::Expression(&rest[start + 2..start + 2 + len]));
+        rest = &rest[start + 3 + len..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+fn parse(expr: &str) -> Result<Expression, WorkflowError> {
+    let mut parts = expr.split('|');
+    let alternatives = parts.next().unwrap_or_default();
+
+    let terms = alternatives
+        .split("??")
+        .map(|term| parse_term(term.trim(), expr))
+        .collect::<Result<Vec<_>, _>>()?;
+    let filters = parts
+        .map(|filter| match filter.trim() {
+            "upper" => Ok(Filter::Upper),
+            "lower" => Ok(Filter::Lower),
+            "length" => Ok(Filter::Length),
+            "exists" => Ok(Filter::Exists),
+            other => Err(WorkflowError::InvalidDefinition(format!(
+                "Unknown filter '{}' in expression: {}",
+                other, expr
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Expression { terms, filters })
+}
+
+fn parse_term(term: &str, expr: &str) -> Result<Term, WorkflowError> {
+    if term.len() >= 2 && term.starts_with('\'') && term.ends_with('\'') {
+        return Ok(Term::Literal(Value::String(
+            term[1..term.len() - 1].to_string(),
+        )));
+    }
+    if let Ok(literal) = serde_json::from_str(term) {
+        return Ok(Term::Literal(literal));
+    }
+
+    let path: Vec<String> = term.split('.').map(str::to_string).collect();
+    if !ROOTS.contains(&path[0].as_str()) || path.iter().any(String::is_empty) {
+        return Err(WorkflowError::InvalidDefinition(format!(
+            "Invalid path '{}' in expression: {}",
+            term, expr
+        )));
+    }
+    Ok(Term::Path(path))
+}
+
+fn evaluate(expr: &str, scope: &Value) -> Result<Value, WorkflowError> {
+    let expression = parse(expr)?;
+
+    let value = expression
+        .terms
+        .iter()
+        .map(|term| match term {
+            Term::Literal(value) => value.clone(),
+            Term::Path(path) => lookup(scope, path),
+        })
+        .find(|value| !value.is_null())
+        .unwrap_or(Value::Null);
+
+    Ok(expression
+        .filters
+        .iter()
+        .fold(value, |value, filter| apply(*filter, value)))
+}
+
+/// Look up a path, mapping over lists at `*` segments.
+fn lookup(value: &Value, path: &[String]) -> Value {
+    let Some((segment, rest)) = path.split_first() else {
+        return value.clone();
+    };
+    match (value, segment.as_str()) {
+        (Value::Array(items), "*") => {
+            Value::Array(items.iter().map(|item| lookup(item, rest)).collect())
+        }
+        (Value::Array(items), index) => index
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| items.get(index))
+            .map(|item| lookup(item, rest))
+            .unwrap_or(Value::Null),
+        (Value::Object(fields), key) => fields
+            .get(key)
+            .map(|field| lookup(field, rest))
+            .unwrap_or(Value::Null),
+        _ => Value::Null,
+    }
+}
+
+fn apply(filter: Filter, value: Value) -> Value {
+    match (filter, value) {
+        (Filter::Upper, Value::String(s)) => Value::String(s.to_uppercase()),
+        (Filter::Lower, Value::String(s)) => Value::String(s.to_lowercase()),
+        (Filter::Length, Value::String(s)) => s.chars().count().into(),
+        (Filter::Length, Value::Array(items)) => items.len().into(),
+        (Filter::Length, Value::Object(fields)) => fields.len().into(),
+        (Filter::Length, Value::Null) => 0.into(),
+        (Filter::Exists, value) => Value::Bool(!value.is_null()),
+        (_, value) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scope() -> Value {
+        json!({
+            "input": {"meeting_id": "mtg-1", "limit": 3},
+            "steps": {
+                "finding": {"severity": "high", "line": null},
+                "verify": [{"verification_id": "v1"}, {"verification_id": "v2"}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_render_keeps_types_and_interpolates() {
+        let scope = scope();
+        let template = json!({
+            "limit": "${input.limit}",
+            "title": "Meeting ${input.meeting_id} (${input.limit})",
+            "ids": "${steps.verify.*.verification_id}",
+            "tags": ["${steps.finding.severity | upper}"],
+            "count": "${steps.verify | length}",
+            "line": "${steps.finding.line ?? 0}",
+            "workspace": "${input.workspace_id ?? 'default'}",
+            "missing": "${input.other}",
+            "has_line": "${steps.finding.line | exists}"
+        });
+
+        assert_eq!(
+            render(&template, &scope).unwrap(),
+            json!({
+                "limit": 3,
+                "title": "Meeting mtg-1 (3)",
+                "ids": ["v1", "v2"],
+                "tags": ["HIGH"],
+                "count": 2,
+                "line": 0,
+                "workspace": "default",
+                "missing": null,
+                "has_line": false
+            })
+        );
+    }
+
+    #[test]
+    fn test_references_and_errors() {
+        let mut steps = BTreeSet::new();
+        references(
+            &json!({"a": "${steps.fetch.content}", "b": ["${input.x ?? steps.other}"]}),
+            &mut steps,
+        )
+        .unwrap();
+        assert_eq!(
+            steps.into_iter().collect::<Vec<_>>(),
+            vec!["fetch", "other"]
+        );
+
+        let mut steps = BTreeSet::new();
+        assert!(references(&json!("${input.x"), &mut steps).is_err());
+        assert!(references(&json!("${secrets.key}"), &mut steps).is_err());
+        assert!(references(&json!("${input.x | reverse}"), &mut steps).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! MCP tools backed by workflow definitions.
+
+use super::definition::WorkflowDefinition;
+use super::engine::{Rollback, WorkflowEngine, WorkflowError};
+use crate::server::{McpServerError, McpServerResult, Tool, ToolContext};
+use crate::types::{ToolDefinition, ToolResult};
+use async_trait::async_trait;
+use platform_rbac::App;
+use tracing::{error, instrument};
+
+/// An MCP tool that runs a workflow definition.
+#[derive(Debug, Clone)]
+pub struct WorkflowTool {
+    definition: WorkflowDefinition,
+    engine: WorkflowEngine,
+}
+
+impl WorkflowTool {
+    /// Create a tool running `definition` on `engine`.
+    ///
+    /// Fails if the definition is invalid or uses an operation the engine
+    /// does not have.
+    pub fn new(
+        definition: WorkflowDefinition,
+        engine: WorkflowEngine,
+    ) -> Result<Self, WorkflowError> {
+        engine.validate(&definition)?;
+        Ok(Self { definition, engine })
+    }
+
+    /// Get the workflow definition.
+    pub fn workflow(&self) -> &WorkflowDefinition {
+        &self.definition
+    }
+}
+
+#[async_trait]
+impl Tool for WorkflowTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition::new(&self.definition.name, &self.definition.description)
+            .with_app(App::Shared)
+            .with_category(&self.definition.category)
+            .with_schema(self.definition.input_schema.clone())
+            .with_permissions(self.definition.permissions.clone())
+    }
+
+    #[instrument(skip(self, args, context), fields(workflow = %self.definition.name))]
+    async fn execute(
+        &self,
+        args: serde_json::Value,
+        context: &ToolContext,
+    ) -> McpServerResult<ToolResult> {
+        match self.engine.run(&self.definition, args, context).await {
+            Ok(output) => Ok(ToolResult::json(output)),
+            Err(WorkflowError::InvalidInput(message)) => {
+                Err(McpServerError::InvalidParams(message))
+            }
+            Err(WorkflowError::InvalidDefinition(message)) => {
+                Err(McpServerError::Internal(message))
+            }
+            Err(e @ (WorkflowError::NotResumable(_) | WorkflowError::Store(_))) => {
+                Err(McpServerError::Internal(e.to_string()))
+            }
+            Err(WorkflowError::StepFailed {
+                message, rollbacks, ..
+            }) => {
+                error!("{}", message);
+                Ok(ToolResult::error(failure_report(&message, &rollbacks)))
+            }
+        }
+    }
+}
+
+/// Describe a failed workflow run and the side effects that were rolled back.
+fn failure_report(message: &str, rollbacks: &[Rollback]) -> String {
+    let mut report = message.to_string();
+    let (rolled_back, failed): (Vec<_>, Vec<_>) =
+        rollbacks.iter().partition(|rollback| rollback.succeeded());
+
+    if !rolled_back.is_empty() {
+        report.push_str("\nRolled back:");
+        for rollback in rolled_back {
+            report.push_str(&format!("\n- {} ({})", rollback.step, rollback.operation));
+        }
+    }
+    if !failed.is_empty() {
+        report.push_str("\nNot rolled back:");
+        for rollback in failed {
+            report.push_str(&format!(
+                "\n- {} ({}): {}",
+                rollback.step,
+                rollback.operation,
+                rollback.error.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_report() {
+        assert_eq!(failure_report("Failed", &[]), "Failed");
+
+        let rollbacks = vec![
+            Rollback {
+                step: "verify".to_string(),
+                operation: "verity.delete_document".to_string(),
+                error: None,
+            },
+            Rollback {
+                step: "link".to_string(),
+                operation: "shipcheck.unlink_decision".to_string(),
+                error: Some("HTTP 500".to_string()),
+            },
+        ];
+        assert_eq!(
+            failure_report("Failed to verify: HTTP 503", &rollbacks),
+            "Failed to verify: HTTP 503\n\
+             Rolled back:\n- verify (verity.delete_document)\n\
+             Not rolled back:\n- link (shipcheck.unlink_decision): HTTP 500"
+        );
+    }
+}
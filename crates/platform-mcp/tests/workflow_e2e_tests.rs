@@ -16,7 +16,7 @@ use platform_mcp::clients::noteman::NoteManClient;
 use platform_mcp::clients::shipcheck::ShipCheckClient;
 use platform_mcp::clients::verity::VerityClient;
 use std::time::Duration;
-use wiremock::matchers::{header, method, path, path_regex};
+use wiremock::matchers::{header, header_exists, header_regex, method, path, path_regex};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 /// Test fixture providing mock servers for all platform services.
@@ -57,6 +57,9 @@ impl TestFixture {
             default_timeout_secs: 10,
             max_retries: 1,
             verify_tls: false,
+            service_auth: None,
+            tenants: Default::default(),
+            cache: None,
         };
 
         Self {
@@ -819,6 +822,1210 @@ async fn test_server_error_handling() {
     assert!(result.is_err());
 }
 
+// =============================================================================
+// Tool execution tests
+// =============================================================================
+
+/// Test running a workflow tool against services from an injected registry.
+#[tokio::test]
+async fn test_workflow_tool_uses_injected_registry() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::VerifyMeetingNotesTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/meetings/mtg-tool/content"))
+        .and(header("Authorization", "Bearer test-noteman-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "meeting_id": "mtg-tool",
+            "content_type": "summary",
+            "content": "We agreed to ship on Friday."
+        })))
+        .expect(1)
+        .mount(&fixture.noteman_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents"))
+        .and(header("Authorization", "Bearer test-verity-key"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "document_id": "doc-tool",
+            "status": "processing",
+            "verification_id": "ver-tool",
+            "message": "Document created"
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let services = Arc::new(ServiceRegistry::new(fixture.config.clone()));
+    let tool = VerifyMeetingNotesTool::new(services);
+    let result = tool
+        .execute(
+            serde_json::json!({ "meeting_id": "mtg-tool" }),
+            &ToolContext::empty(),
+        )
+        .await
+        .expect("Tool should execute");
+
+    assert!(!result.is_error);
+    let ContentBlock::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    let output: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(output["document_id"], "doc-tool");
+    assert_eq!(output["verification_id"], "ver-tool");
+
+    let requests = fixture.verity_server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(
+        body["metadata"],
+        serde_json::json!({
+            "meeting_id": "mtg-tool",
+            "content_type": "summary",
+            "notify_attendees": false
+        })
+    );
+}
+
+/// Test that an enterprise org's calls reach its dedicated deployment
+#[tokio::test]
+async fn test_enterprise_org_routed_to_dedicated_endpoint() {
+    use platform_mcp::clients::{ServiceRegistry, TenantConfig};
+    use platform_mcp::ToolContext;
+    use platform_org::Tier;
+
+    let fixture = TestFixture::new().await;
+    let dedicated = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/documents/doc-acme"))
+        .and(header("Authorization", "Bearer acme-verity-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "doc-acme",
+            "title": "Dedicated",
+            "status": "verified",
+            "created_at": "2026-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&dedicated)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/documents/doc-acme"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let acme = uuid::Uuid::now_v7();
+    let mut config = fixture.config.clone();
+    config.tenants.insert(
+        acme,
+        TenantConfig {
+            verity: Some(ServiceEndpoint {
+                base_url: dedicated.uri(),
+                api_key: Some("acme-verity-key".to_string()),
+                webhook_secret: None,
+            }),
+            ..TenantConfig::new(Tier::Enterprise)
+        },
+    );
+    let services = ServiceRegistry::new(config);
+
+    let mut context = ToolContext::empty();
+    context.org_id = Some(acme);
+    let doc = services
+        .verity(&context)
+        .get_document("doc-acme")
+        .await
+        .expect("Dedicated deployment should serve the document");
+    assert_eq!(doc.id, "doc-acme");
+
+    // Other orgs keep using the shared deployment.
+    context.org_id = Some(uuid::Uuid::now_v7());
+    assert!(services
+        .verity(&context)
+        .get_document("doc-acme")
+        .await
+        .is_err());
+}
+
+/// Test that a workflow's calls to both apps share one trace and correlation ID
+#[tokio::test]
+async fn test_workflow_propagates_trace_context() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::VerifyMeetingNotesTool;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+    let context = ToolContext {
+        correlation_id: Some("corr-trace-1".to_string()),
+        ..ToolContext::empty()
+    }
+    .traced();
+    let trace_id = context.trace.as_ref().unwrap().trace_id.clone();
+    let traceparent = format!("^00-{}-[0-9a-f]{{16}}-01$", trace_id);
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/meetings/mtg-trace/content"))
+        .and(header("X-Correlation-ID", "corr-trace-1"))
+        .and(header_regex("traceparent", &traceparent))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "meeting_id": "mtg-trace",
+            "content_type": "summary",
+            "content": "Ship it."
+        })))
+        .expect(1)
+        .mount(&fixture.noteman_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents"))
+        .and(header("X-Correlation-ID", "corr-trace-1"))
+        .and(header_regex("traceparent", &traceparent))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "document_id": "doc-trace",
+            "status": "processing",
+            "message": "Document created"
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let tool = VerifyMeetingNotesTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let result = tool
+        .execute(serde_json::json!({ "meeting_id": "mtg-trace" }), &context)
+        .await
+        .expect("Tool should execute");
+    assert!(!result.is_error);
+}
+
+/// Test that re-running a workflow call returns the recorded step result
+#[tokio::test]
+async fn test_workflow_step_is_idempotent() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::VerifyMeetingNotesTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/meetings/mtg-idem/content"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "meeting_id": "mtg-idem",
+            "content_type": "summary",
+            "content": "Launch is on Monday."
+        })))
+        .expect(2)
+        .mount(&fixture.noteman_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents"))
+        .and(header_exists("Idempotency-Key"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "document_id": "doc-idem",
+            "status": "processing",
+            "verification_id": "ver-idem",
+            "message": "Document created"
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let tool = VerifyMeetingNotesTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let context = ToolContext {
+        request_id: Some("call-42".to_string()),
+        ..ToolContext::empty()
+    };
+    let args = serde_json::json!({ "meeting_id": "mtg-idem" });
+
+    for _ in 0..2 {
+        let result = tool.execute(args.clone(), &context).await.unwrap();
+        assert!(!result.is_error);
+        let ContentBlock::Text { text } = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        let output: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(output["document_id"], "doc-idem");
+    }
+}
+
+/// Test that a retried tools/call creates the document only once
+#[tokio::test]
+async fn test_retried_tool_call_has_one_side_effect() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::VerifyMeetingNotesTool;
+    use platform_mcp::{McpRequest, McpServer, Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/meetings/mtg-retry/content"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "meeting_id": "mtg-retry",
+            "content_type": "summary",
+            "content": "Launch is on Monday."
+        })))
+        .mount(&fixture.noteman_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents"))
+        .and(header_exists("Idempotency-Key"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "document_id": "doc-retry",
+            "status": "processing",
+            "verification_id": "ver-retry",
+            "message": "Document created"
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let server = McpServer::platform();
+    let tool = VerifyMeetingNotesTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let context = ToolContext {
+        permissions: tool.definition().required_permissions,
+        ..ToolContext::empty()
+    };
+    server.register_tool(Arc::new(tool)).await;
+
+    // The client times out and sends the same call again.
+    for _ in 0..2 {
+        let request = McpRequest::new(7, "tools/call").with_params(serde_json::json!({
+            "name": "workflow_verify_meeting_notes",
+            "arguments": { "meeting_id": "mtg-retry" }
+        }));
+        let response = server.handle_request_with_context(request, &context).await;
+        assert!(response.error.is_none(), "{:?}", response.error);
+        let result = response.result.unwrap();
+        assert_eq!(result["is_error"], false);
+        assert!(result["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("doc-retry"));
+    }
+}
+
+/// Test that a workflow fans out over a step's output and skips failed items
+#[tokio::test]
+async fn test_workflow_fans_out_over_documentation_files() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::VerifyDocumentationTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+    use wiremock::matchers::body_partial_json;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/repositories/repo-docs/docs"))
+        .and(body_partial_json(
+            serde_json::json!({ "paths": ["README.md", "docs/"] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "repository_id": "repo-docs",
+            "files": [
+                {"path": "README.md", "content": "Install with cargo."},
+                {"path": "docs/api.md", "content": "GET /v1/things"},
+                {"path": "docs/broken.md", "content": "Broken"}
+            ]
+        })))
+        .expect(1)
+        .mount(&fixture.shipcheck_server)
+        .await;
+
+    for (file, verification_id) in [("README.md", "ver-readme"), ("docs/api.md", "ver-api")] {
+        Mock::given(method("POST"))
+            .and(path("/api/v1/verify"))
+            .and(body_partial_json(serde_json::json!({
+                "external_id": format!("repo-docs:{}", file),
+                "source_app": "shipcheck"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "verification_id": verification_id,
+                "status": "processing",
+                "assertions_found": 0,
+                "assertions_verified": 0
+            })))
+            .expect(1)
+            .mount(&fixture.verity_server)
+            .await;
+    }
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/verify"))
+        .and(body_partial_json(
+            serde_json::json!({ "external_id": "repo-docs:docs/broken.md" }),
+        ))
+        .respond_with(ResponseTemplate::new(400))
+        .mount(&fixture.verity_server)
+        .await;
+
+    let tool = VerifyDocumentationTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let result = tool
+        .execute(
+            serde_json::json!({ "repository_id": "repo-docs" }),
+            &ToolContext::empty(),
+        )
+        .await
+        .expect("Tool should execute");
+
+    assert!(!result.is_error);
+    let ContentBlock::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    let output: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(output["documents_created"], 3);
+    assert_eq!(
+        output["verification_ids"],
+        serde_json::json!(["ver-readme", "ver-api"])
+    );
+    assert_eq!(output["checks"]["code_examples"], true);
+}
+
+/// Test that a failed workflow deletes the document an earlier step created
+#[tokio::test]
+async fn test_failed_workflow_rolls_back_created_document() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::workflow::{WorkflowDefinition, WorkflowEngine, WorkflowTool};
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "document_id": "doc-saga",
+            "status": "created",
+            "message": "Document created"
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents/verify"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("verification rejected"))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/documents/doc-saga"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let definition = WorkflowDefinition::from_yaml(
+        r#"
+name: store_and_verify
+description: Store content in Verity and verify it
+steps:
+  - id: document
+    operation: verity.create_document
+    input:
+      title: ${input.title}
+      content: ${input.content}
+    compensate:
+      operation: verity.delete_document
+      input:
+        document_id: ${output.document_id}
+  - id: verification
+    operation: verity.verify_document
+    input:
+      document_id: ${steps.document.document_id}
+    error_message: Failed to verify document
+output:
+  job_id: ${steps.verification.job_id}
+"#,
+    )
+    .unwrap();
+    let services = Arc::new(ServiceRegistry::new(fixture.config.clone()));
+    let tool = WorkflowTool::new(definition, WorkflowEngine::new(services)).unwrap();
+
+    let result = tool
+        .execute(
+            serde_json::json!({ "title": "Notes", "content": "Ship on Friday." }),
+            &ToolContext::empty(),
+        )
+        .await
+        .expect("Tool should execute");
+
+    assert!(result.is_error);
+    let ContentBlock::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    assert!(text.starts_with("Failed to verify document:"), "{}", text);
+    assert!(
+        text.ends_with("Rolled back:\n- document (verity.delete_document)"),
+        "{}",
+        text
+    );
+}
+
+/// Test that a failure after linking a decision removes the link
+#[tokio::test]
+async fn test_failed_workflow_unlinks_decision() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::builtin_workflows;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::workflow::{StepDefinition, WorkflowEngine, WorkflowTool};
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/decisions/link"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "link_id": "link-saga",
+            "status": "linked",
+            "issue_number": null,
+            "message": "Decision linked"
+        })))
+        .expect(1)
+        .mount(&fixture.shipcheck_server)
+        .await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/decisions/link/link-saga"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&fixture.shipcheck_server)
+        .await;
+
+    // The built-in workflow, followed by a step that fails once the decision
+    // is linked.
+    let definition = builtin_workflows()
+        .into_iter()
+        .find(|d| d.name == "workflow_link_code_decision")
+        .unwrap()
+        .with_step(
+            StepDefinition::new("announce", "core.fail")
+                .with_dependency("link")
+                .with_input(serde_json::json!({ "message": "announcement failed" })),
+        );
+    let services = Arc::new(ServiceRegistry::new(fixture.config.clone()));
+    let tool = WorkflowTool::new(definition, WorkflowEngine::new(services)).unwrap();
+
+    let result = tool
+        .execute(
+            serde_json::json!({
+                "meeting_id": "mtg-saga",
+                "repository_id": "repo-saga",
+                "decision_text": "Ship on Friday"
+            }),
+            &ToolContext::empty(),
+        )
+        .await
+        .expect("Tool should execute");
+
+    assert!(result.is_error);
+    let ContentBlock::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    assert!(
+        text.ends_with("Rolled back:\n- link (shipcheck.unlink_decision)"),
+        "{}",
+        text
+    );
+}
+
+/// Test that a batch verifies the meetings in a date range, reporting failures per meeting
+/// and the matches left out by the search limit
+#[tokio::test]
+async fn test_batch_verifies_meetings_in_date_range() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::VerifyMeetingNotesBatchTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+    use wiremock::matchers::body_partial_json;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/meetings/search"))
+        .and(body_partial_json(serde_json::json!({
+            "date_range": {"start": "2026-03-01", "end": "2026-03-07"}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "query": "",
+            "total_results": 5,
+            "meetings": [
+                {"id": "mtg-mon", "title": "Planning", "date": "2026-03-02", "participants": [], "score": 1.0},
+                {"id": "mtg-wed", "title": "Standup", "date": "2026-03-04", "participants": [], "score": 1.0},
+                {"id": "mtg-fri", "title": "Retro", "date": "2026-03-06", "participants": [], "score": 1.0}
+            ]
+        })))
+        .expect(1)
+        .mount(&fixture.noteman_server)
+        .await;
+
+    for meeting in ["mtg-mon", "mtg-fri"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/v1/meetings/{}/content", meeting)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "meeting_id": meeting,
+                "content_type": "summary",
+                "content": "Revenue grew 25% in Q4."
+            })))
+            .expect(1)
+            .mount(&fixture.noteman_server)
+            .await;
+    }
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/meetings/mtg-wed/content"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&fixture.noteman_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/documents"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "document_id": "doc-batch",
+            "verification_id": "ver-batch",
+            "status": "verification_started",
+            "message": "Document created and verification initiated"
+        })))
+        .expect(2)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let tool =
+        VerifyMeetingNotesBatchTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let result = tool
+        .execute(
+            serde_json::json!({
+                "date_range": {"start": "2026-03-01", "end": "2026-03-07"},
+                "verification_level": "thorough",
+                "limit": 3,
+                "max_concurrency": 2
+            }),
+            &ToolContext::empty(),
+        )
+        .await
+        .expect("Tool should execute");
+
+    assert!(!result.is_error);
+    let ContentBlock::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    let report: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(report["status"], "partially_failed");
+    assert_eq!(report["total"], 3);
+    assert_eq!(report["succeeded"], 2);
+    assert_eq!(report["failed"], 1);
+    assert_eq!(report["truncated"], 2);
+
+    let items = report["items"].as_array().unwrap();
+    assert_eq!(items[0]["id"], "mtg-mon");
+    assert_eq!(items[0]["output"]["verification_level"], "thorough");
+    assert_eq!(items[1]["id"], "mtg-wed");
+    assert_eq!(items[1]["status"], "failed");
+    assert!(items[1]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Failed to fetch meeting content"));
+    assert_eq!(items[2]["output"]["verification_id"], "ver-batch");
+}
+
+/// Test that platform search fuses results from the apps that answer
+#[tokio::test]
+async fn test_platform_search_tolerates_a_down_app() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::PlatformSearchTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+    use wiremock::matchers::body_partial_json;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/knowledge/search"))
+        .and(body_partial_json(serde_json::json!({
+            "filters": {"project_id": "proj-billing", "tags": ["finance"]}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "query": "revenue",
+            "total_results": 2,
+            "results": [
+                {
+                    "id": "fact-1", "fact": "Q4 revenue grew 25%", "confidence": 0.9,
+                    "relevance": 0.95, "source_app": "noteman", "external_id": "mtg-q4"
+                },
+                {"id": "fact-2", "fact": "Revenue is reported monthly", "confidence": 0.8, "relevance": 0.5}
+            ]
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/meetings/search"))
+        .and(body_partial_json(serde_json::json!({
+            "project_id": "proj-billing",
+            "date_range": {"start": "2026-01-01", "end": "2026-03-31"}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "query": "revenue",
+            "total_results": 2,
+            "meetings": [
+                {"id": "mtg-budget", "title": "Budget review", "date": "2026-02-01", "participants": [], "score": 12.0},
+                {"id": "mtg-q4", "title": "Q4 results", "date": "2026-01-15", "participants": [], "score": 8.0}
+            ]
+        })))
+        .expect(1)
+        .mount(&fixture.noteman_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/findings/search"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&fixture.shipcheck_server)
+        .await;
+
+    let context = ToolContext {
+        permissions: vec![
+            "knowledge:read".to_string(),
+            "meeting:read".to_string(),
+            "code_finding:read".to_string(),
+        ],
+        ..ToolContext::empty()
+    };
+    let tool = PlatformSearchTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let result = tool
+        .execute(
+            serde_json::json!({
+                "query": "revenue",
+                "project_id": "proj-billing",
+                "tags": ["finance"],
+                "date_range": {"start": "2026-01-01", "end": "2026-03-31"}
+            }),
+            &context,
+        )
+        .await
+        .expect("Tool should execute");
+
+    assert!(!result.is_error);
+    let ContentBlock::Text { text } = &result.content[0] else {
+        panic!("Expected text content");
+    };
+    let output: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(output["sources"]["verity"]["status"], "ok");
+    assert_eq!(output["sources"]["noteman"]["count"], 2);
+    assert_eq!(output["sources"]["shipcheck"]["status"], "failed");
+
+    // The fact taken from the Q4 meeting is folded into it and lifts it to the top.
+    let results = output["results"].as_array().unwrap();
+    assert_eq!(output["total_results"], 3);
+    assert_eq!(results[0]["id"], "mtg-q4");
+    assert_eq!(results[0]["linked"], serde_json::json!(["verity:fact-1"]));
+    assert!(results.iter().all(|hit| hit["id"] != "fact-1"));
+}
+
+// =============================================================================
+// Response cache tests
+// =============================================================================
+
+/// Test that a failing health check publishes an event and fails tools fast
+#[tokio::test]
+async fn test_health_monitor_fails_fast_on_down_service() {
+    use platform_events::{EventBus, MemoryEventBus};
+    use platform_mcp::clients::{ServiceRegistry, TenantConfig};
+    use platform_mcp::health::{HealthCheckConfig, HealthChecker, HealthStatus};
+    use platform_mcp::monitor::{HealthChange, HealthMonitor, HealthMonitorConfig};
+    use platform_mcp::tools::VerifyDocumentTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    for server in [&fixture.noteman_server, &fixture.shipcheck_server] {
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(2)
+        .mount(&fixture.verity_server)
+        .await;
+    Mock::given(path_regex(r"^/api/"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let bus = Arc::new(MemoryEventBus::new());
+    let mut subscription = bus.subscribe("shared.system.health.changed").await.unwrap();
+
+    let checker = HealthChecker::new(fixture.config.clone(), HealthCheckConfig::default());
+    let monitor = Arc::new(
+        HealthMonitor::new(
+            checker,
+            HealthMonitorConfig {
+                failure_threshold: 2,
+                ..HealthMonitorConfig::default()
+            },
+        )
+        .with_event_bus(bus.clone()),
+    );
+    let changes = monitor.check_now().await;
+    assert_eq!(changes.len(), 1);
+    assert!(monitor.down_since("verity").is_none());
+    assert!(monitor.check_now().await.is_empty());
+    assert!(monitor.down_since("verity").is_some());
+
+    let event = subscription.recv().await.unwrap();
+    let change: HealthChange = event.parse_payload().unwrap();
+    assert_eq!(change.service, "verity");
+    assert_eq!(change.status, HealthStatus::Unhealthy);
+
+    // An organization whose routing only overrides the timeout still calls
+    // the shared deployment.
+    let org_id = uuid::Uuid::now_v7();
+    let mut config = fixture.config.clone();
+    config.tenants.insert(
+        org_id,
+        TenantConfig {
+            timeout_secs: Some(60),
+            ..TenantConfig::new(platform_org::Tier::Enterprise)
+        },
+    );
+    let mut tenant_context = ToolContext::empty();
+    tenant_context.org_id = Some(org_id);
+
+    let services = Arc::new(ServiceRegistry::new(config).with_health_monitor(monitor));
+    for context in [ToolContext::empty(), tenant_context] {
+        let result = VerifyDocumentTool::new(services.clone())
+            .execute(serde_json::json!({ "document_id": "doc-1" }), &context)
+            .await
+            .expect("Tool should execute");
+
+        assert!(result.is_error);
+        let ContentBlock::Text { text } = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        assert!(text.contains("Verity is unavailable"), "{}", text);
+    }
+}
+
+/// Test that the platform_health tool reports which service is down, showing internal URLs
+/// only to administrators
+#[tokio::test]
+async fn test_platform_health_tool_reports_down_service() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::tools::PlatformHealthTool;
+    use platform_mcp::types::ContentBlock;
+    use platform_mcp::{Tool, ToolContext};
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    for server in [&fixture.noteman_server, &fixture.shipcheck_server] {
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "version": "1.2.0" })),
+            )
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&fixture.verity_server)
+        .await;
+
+    let tool = PlatformHealthTool::new(Arc::new(ServiceRegistry::new(fixture.config.clone())));
+    let mut admin = ToolContext::empty();
+    admin.permissions = vec!["organization:manage".to_string()];
+
+    for (context, url) in [
+        (ToolContext::empty(), "[redacted]".to_string()),
+        (admin, fixture.verity_server.uri()),
+    ] {
+        let result = tool
+            .execute(serde_json::json!({}), &context)
+            .await
+            .expect("Tool should execute");
+
+        assert!(!result.is_error);
+        let ContentBlock::Text { text } = &result.content[0] else {
+            panic!("Expected text content");
+        };
+        let report: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(report["status"], "unhealthy");
+        let verity = report["services"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["name"] == "Verity")
+            .unwrap();
+        assert_eq!(verity["status"], "down");
+        assert_eq!(verity["error"], "HTTP 503 Service Unavailable");
+        assert_eq!(verity["url"], url);
+    }
+}
+
+/// Test that a slow search is hedged and the faster answer is used
+#[tokio::test]
+async fn test_search_request_is_hedged() {
+    use platform_mcp::clients::verity::SearchKnowledgeParams;
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::health::MetricsCollector;
+    use platform_mcp::retry::HedgeConfig;
+    use platform_mcp::ToolContext;
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+    let results =
+        |query: &str| serde_json::json!({ "query": query, "total_results": 0, "results": [] });
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/knowledge/search"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(results("slow"))
+                .set_delay(Duration::from_secs(2)),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/knowledge/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(results("fast")))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let metrics = Arc::new(MetricsCollector::new());
+    let services = ServiceRegistry::new(fixture.config.clone())
+        .with_metrics(metrics.clone())
+        .with_hedging(HedgeConfig {
+            max_delay: Duration::from_millis(50),
+            ..HedgeConfig::default()
+        });
+
+    let response = services
+        .verity(&ToolContext::empty())
+        .search_knowledge(SearchKnowledgeParams {
+            query: "revenue".to_string(),
+            limit: 10,
+            filters: None,
+        })
+        .await
+        .expect("Hedged search should succeed");
+
+    assert_eq!(response.query, "fast");
+    assert!(metrics
+        .render_openmetrics()
+        .contains("platform_mcp_hedged_requests_total{service=\"verity\",winner=\"hedge\"} 1.0"));
+}
+
+/// Test that a hedge answered with a server error does not beat a slower success
+#[tokio::test]
+async fn test_hedge_with_server_error_does_not_win() {
+    use platform_mcp::clients::verity::SearchKnowledgeParams;
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::health::MetricsCollector;
+    use platform_mcp::retry::HedgeConfig;
+    use platform_mcp::ToolContext;
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/knowledge/search"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "query": "slow",
+                    "total_results": 0,
+                    "results": []
+                }))
+                .set_delay(Duration::from_millis(500)),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/knowledge/search"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let mut config = fixture.config.clone();
+    config.max_retries = 0;
+    let metrics = Arc::new(MetricsCollector::new());
+    let services = ServiceRegistry::new(config)
+        .with_metrics(metrics.clone())
+        .with_hedging(HedgeConfig {
+            max_delay: Duration::from_millis(50),
+            ..HedgeConfig::default()
+        });
+
+    let response = services
+        .verity(&ToolContext::empty())
+        .search_knowledge(SearchKnowledgeParams {
+            query: "revenue".to_string(),
+            limit: 10,
+            filters: None,
+        })
+        .await
+        .expect("The primary request should win");
+
+    assert_eq!(response.query, "slow");
+    assert!(metrics
+        .render_openmetrics()
+        .contains("platform_mcp_hedged_requests_total{service=\"verity\",winner=\"primary\"} 1.0"));
+}
+
+/// Test that registry clients retry server errors until the retry budget runs out
+#[tokio::test]
+async fn test_retries_stop_when_budget_is_exhausted() {
+    use platform_mcp::clients::ServiceRegistry;
+    use platform_mcp::retry::RetryBudgets;
+    use platform_mcp::ToolContext;
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    // The first call is retried once, using up the budget; the second call
+    // is not retried.
+    Mock::given(method("GET"))
+        .and(path("/api/v1/meetings/mtg-busy/content"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("overloaded"))
+        .expect(3)
+        .mount(&fixture.noteman_server)
+        .await;
+
+    let mut config = fixture.config.clone();
+    config.max_retries = 4;
+    let budgets = Arc::new(RetryBudgets::new(1, 0.0));
+    let services = ServiceRegistry::new(config).with_retry_budgets(budgets.clone());
+
+    for _ in 0..2 {
+        let result = services
+            .noteman(&ToolContext::empty())
+            .get_meeting_content("mtg-busy", "summary")
+            .await;
+        assert!(result.is_err());
+    }
+    assert_eq!(budgets.get("noteman").available(), 0);
+}
+
+/// Test that cached reads are served locally and revalidated with ETags
+#[tokio::test]
+async fn test_response_cache_revalidates_with_etag() {
+    use platform_mcp::clients::ResponseCache;
+    use platform_mcp::ToolContext;
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+    let finding = serde_json::json!({
+        "id": "find-cached",
+        "repository_id": "repo-1",
+        "title": "Unchecked unwrap",
+        "description": "unwrap() on user input",
+        "severity": "medium",
+        "category": "reliability",
+        "file_path": "src/lib.rs",
+        "line_number": 42,
+        "status": "open",
+        "created_at": "2026-01-01T00:00:00Z"
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/findings/find-cached"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&fixture.shipcheck_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/findings/find-cached"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .set_body_json(finding),
+        )
+        .expect(2)
+        .mount(&fixture.shipcheck_server)
+        .await;
+
+    // Zero TTL: every call revalidates once an entry exists.
+    let cache = Arc::new(ResponseCache::in_memory(10).with_ttl("get_finding", Duration::ZERO));
+    let client = fixture.shipcheck_client().with_cache(cache.clone());
+
+    let mut context = ToolContext::empty();
+    context.org_id = Some(uuid::Uuid::now_v7());
+    let tenant_a = client.for_context(&context);
+
+    let first = tenant_a.get_finding("find-cached").await.unwrap();
+    let second = tenant_a.get_finding("find-cached").await.unwrap();
+    assert_eq!(first.id, second.id);
+
+    // Another organization never sees tenant A's entry.
+    context.org_id = Some(uuid::Uuid::now_v7());
+    let tenant_b = client.for_context(&context);
+    tenant_b.get_finding("find-cached").await.unwrap();
+}
+
+/// Test that a 304 for an entry evicted during revalidation refetches the body
+#[tokio::test]
+async fn test_response_cache_refetches_when_entry_evicted() {
+    use platform_mcp::clients::ResponseCache;
+    use std::sync::Arc;
+    use wiremock::{Match, Request, Respond};
+
+    /// Matches requests without `If-None-Match`.
+    struct Unconditional;
+
+    impl Match for Unconditional {
+        fn matches(&self, request: &Request) -> bool {
+            !request.headers.contains_key(&"if-none-match".into())
+        }
+    }
+
+    /// Answers `304 Not Modified` after evicting everything from the cache.
+    struct EvictThenNotModified(Arc<ResponseCache>);
+
+    impl Respond for EvictThenNotModified {
+        fn respond(&self, _request: &Request) -> ResponseTemplate {
+            self.0.clear();
+            ResponseTemplate::new(304)
+        }
+    }
+
+    let fixture = TestFixture::new().await;
+    let cache = Arc::new(ResponseCache::in_memory(10).with_ttl("get_document", Duration::ZERO));
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/documents/doc-evicted"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(EvictThenNotModified(cache.clone()))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/documents/doc-evicted"))
+        .and(Unconditional)
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .set_body_json(serde_json::json!({
+                    "id": "doc-evicted",
+                    "title": "Evicted",
+                    "content": "Content",
+                    "status": "verified",
+                    "created_at": "2026-01-01T00:00:00Z"
+                })),
+        )
+        .expect(2)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let client = fixture.verity_client().with_cache(cache);
+    client.get_document("doc-evicted").await.unwrap();
+    let refetched = client.get_document("doc-evicted").await.unwrap();
+    assert_eq!(refetched.id, "doc-evicted");
+}
+
+// =============================================================================
+// Service authentication tests
+// =============================================================================
+
+/// Test that service tokens replace the static API key and carry the caller.
+#[tokio::test]
+async fn test_service_token_authentication() {
+    use platform_auth::{AppId, JwtService};
+    use platform_mcp::clients::auth::ServiceTokenProvider;
+    use platform_mcp::ToolContext;
+    use std::sync::Arc;
+
+    let fixture = TestFixture::new().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/documents/doc-auth"))
+        .and(header_regex("Authorization", r"^Bearer ey"))
+        .and(header_exists("X-Cross-App-Token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "doc-auth",
+            "title": "Auth test",
+            "content": "Content",
+            "status": "verified",
+            "created_at": "2026-01-01T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&fixture.verity_server)
+        .await;
+
+    let provider = ServiceTokenProvider::new(
+        JwtService::with_secret("platform-secret").unwrap(),
+        AppId::ShipCheck,
+    );
+    let mut context = ToolContext::empty();
+    context.user_id = Some(uuid::Uuid::now_v7());
+    context.user_email = Some("user@example.com".to_string());
+
+    let verity = fixture
+        .verity_client()
+        .with_service_auth(Arc::new(provider))
+        .for_context(&context);
+    verity
+        .get_document("doc-auth")
+        .await
+        .expect("Should authenticate with service token");
+
+    let requests = fixture.verity_server.received_requests().await.unwrap();
+    let header_value = |name: &str| {
+        requests[0]
+            .headers
+            .iter()
+            .find(|(key, _)| key.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, values)| values.last().as_str().to_string())
+            .unwrap()
+    };
+    let bearer = header_value("authorization");
+    let token = bearer.trim_start_matches("Bearer ");
+    let jwt = JwtService::with_secret("platform-secret").unwrap();
+    let service_token = jwt.decode_service_token(token).unwrap();
+    assert_eq!(service_token.iss, AppId::ShipCheck);
+    assert!(service_token.is_valid_for(AppId::Verity));
+
+    let user_token = header_value("x-cross-app-token");
+    let forwarded = jwt.decode_cross_app_token(&user_token).unwrap();
+    assert_eq!(Some(forwarded.sub), context.user_id);
+}
+
 // =============================================================================
 // Integration sequence tests
 // =============================================================================
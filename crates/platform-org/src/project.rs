@@ -169,25 +169,20 @@ impl Project {
 /// Project visibility levels.
 ///
 /// Determines who can see and access a project.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectVisibility {
     /// Visible to everyone (public projects)
     Public,
 
     /// Visible to all organization members
+    #[default]
     Organization,
 
     /// Visible only to explicitly added members
     Private,
 }
 
-impl Default for ProjectVisibility {
-    fn default() -> Self {
-        Self::Organization
-    }
-}
-
 /// Summary of a project for list displays.
 ///
 /// This is a lightweight representation of a project that includes
@@ -31,13 +31,16 @@ use serde::{Deserialize, Serialize};
 /// assert!(admin.can_manage_projects());
 /// assert!(!admin.can_manage_settings());
 /// ```
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum OrganizationRole {
     /// Guest access (limited visibility)
     Guest = 0,
 
     /// Read-only access to organization resources
+    #[default]
     Viewer = 1,
 
     /// Can create and edit content
@@ -177,12 +180,6 @@ impl OrganizationRole {
     }
 }
 
-impl Default for OrganizationRole {
-    fn default() -> Self {
-        Self::Viewer
-    }
-}
-
 /// User role within a project.
 ///
 /// Similar to organization roles but scoped to a specific project.
@@ -203,10 +200,13 @@ impl Default for OrganizationRole {
 /// let role = ProjectRole::Editor;
 /// assert_eq!(role.as_str(), "editor");
 /// ```
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectRole {
     /// Read-only access
+    #[default]
     Viewer = 1,
 
     /// Can create and edit content
@@ -319,12 +319,6 @@ impl ProjectRole {
     }
 }
 
-impl Default for ProjectRole {
-    fn default() -> Self {
-        Self::Viewer
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,7 +24,7 @@ use serde::{Deserialize, Serialize};
 /// let settings = OrganizationSettings::default();
 /// assert!(!settings.security.require_mfa);
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct OrganizationSettings {
     /// Security settings
     #[serde(default)]
@@ -47,18 +47,6 @@ pub struct OrganizationSettings {
     pub features: FeatureFlags,
 }
 
-impl Default for OrganizationSettings {
-    fn default() -> Self {
-        Self {
-            security: SecuritySettings::default(),
-            defaults: DefaultSettings::default(),
-            integrations: IntegrationSettings::default(),
-            notifications: NotificationSettings::default(),
-            features: FeatureFlags::default(),
-        }
-    }
-}
-
 /// Security settings for an organization.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecuritySettings {
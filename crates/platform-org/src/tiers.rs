@@ -34,10 +34,13 @@ use serde::{Deserialize, Serialize};
 /// let limits = tier.limits();
 /// assert_eq!(limits.users, Some(50));
 /// ```
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum Tier {
     /// Free individual tier
+    #[default]
     IndividualFree,
 
     /// Professional individual tier
@@ -307,12 +310,6 @@ impl Tier {
     }
 }
 
-impl Default for Tier {
-    fn default() -> Self {
-        Tier::IndividualFree
-    }
-}
-
 /// Feature limits for a subscription tier.
 ///
 /// Values of `None` indicate unlimited.
@@ -98,19 +98,6 @@ impl Permission {
         }
     }
 
-    /// Get the string representation (e.g., "document:read" or "document:read:doc-123").
-    ///
-    /// # Returns
-    ///
-    /// A string in the format `resource:action` or `resource:action:id`
-    pub fn to_string(&self) -> String {
-        if let Some(ref id) = self.resource_id {
-            format!("{}:{}:{}", self.resource.as_str(), self.action.as_str(), id)
-        } else {
-            format!("{}:{}", self.resource.as_str(), self.action.as_str())
-        }
-    }
-
     /// Parse from string (e.g., "document:read" or "document:read:uuid").
     ///
     /// # Arguments
@@ -213,6 +200,24 @@ impl Permission {
     }
 }
 
+impl std::fmt::Display for Permission {
+    /// Formats as `resource:action` or `resource:action:id`
+    /// (e.g., "document:read" or "document:read:doc-123").
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ref id) = self.resource_id {
+            write!(
+                f,
+                "{}:{}:{}",
+                self.resource.as_str(),
+                self.action.as_str(),
+                id
+            )
+        } else {
+            write!(f, "{}:{}", self.resource.as_str(), self.action.as_str())
+        }
+    }
+}
+
 /// A set of permissions that can be assigned to roles or users.
 ///
 /// Uses internal string representation for efficient storage and comparison.